@@ -2,6 +2,7 @@
 #![allow(clippy::too_many_arguments)]
 
 use std::{
+    collections::HashMap,
     fmt::Display,
     fs::{OpenOptions, create_dir_all},
     io::Write,
@@ -23,41 +24,136 @@ use yaml_rust2::{
     yaml::{Array, Hash},
 };
 
+/// Whether a `Yamlable::write_to_file_with_mode` call should write the generated output or merely
+/// check that a previously-written file still matches it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Write the generated output to disk, creating or overwriting the file as requested.
+    Generate,
+    /// Don't write anything; instead compare the generated output against the existing file and
+    /// return an error if they differ. Intended for a CI step that fails when a committed file
+    /// has drifted from its source of truth.
+    Check,
+}
+impl FromStr for WriteMode {
+    type Err = PyErr;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "generate" => Ok(Self::Generate),
+            "check" => Ok(Self::Check),
+            _ => Err(PyValueError::new_err(
+                "Invalid write mode, expected 'generate' or 'check'",
+            )),
+        }
+    }
+}
+
+fn render_yaml_document(yaml: &Yaml) -> PyResult<String> {
+    let mut out_str = String::new();
+    let mut emitter = YamlEmitter::new(&mut out_str);
+    emitter.multiline_strings(true);
+    emitter
+        .dump(yaml)
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    Ok(out_str)
+}
+
 pub trait Yamlable {
     fn as_yaml(&self) -> Yaml;
     fn as_yaml_string(&self) -> PyResult<String> {
-        let yaml = self.as_yaml();
-        let mut out_str = String::new();
-        let mut emitter = YamlEmitter::new(&mut out_str);
-        emitter.multiline_strings(true);
-        emitter
-            .dump(&yaml)
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(out_str)
+        render_yaml_document(&self.as_yaml())
     }
     fn write_to_file(&self, path: impl AsRef<Path>, overwrite: bool) -> PyResult<()> {
+        self.write_to_file_with_mode(path, overwrite, WriteMode::Generate, None)
+    }
+    /// Like `write_to_file`, but supports `WriteMode::Check` for CI drift detection and an
+    /// optional generated-file header banner prepended to the emitted YAML.
+    fn write_to_file_with_mode(
+        &self,
+        path: impl AsRef<Path>,
+        overwrite: bool,
+        mode: WriteMode,
+        header: Option<&str>,
+    ) -> PyResult<()> {
         let path = path.as_ref();
-        if let Some(parent) = path.parent()
-            && !parent.as_os_str().is_empty()
-        {
-            create_dir_all(parent)?;
-        }
-        let mut opts = OpenOptions::new();
-        opts.write(true).create(true);
-        if overwrite {
-            opts.truncate(true);
-        } else {
-            opts.create_new(true);
-        }
-        let mut file = match opts.open(path) {
-            Ok(f) => f,
-            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return Ok(()),
-            Err(e) => return Err(PyErr::from(e)),
-        };
+        let mut contents = self.as_yaml_string()?;
+        if let Some(header) = header {
+            contents = format!("{header}\n{contents}");
+        }
+        match mode {
+            WriteMode::Check => {
+                let existing = std::fs::read_to_string(path).map_err(|e| {
+                    PyRuntimeError::new_err(format!(
+                        "{} does not exist or is unreadable ({e}); run generation to create it",
+                        path.display()
+                    ))
+                })?;
+                if existing != contents {
+                    return Err(PyRuntimeError::new_err(format!(
+                        "{} is stale and no longer matches the generated output; re-run generation to update it",
+                        path.display()
+                    )));
+                }
+                Ok(())
+            }
+            WriteMode::Generate => {
+                if let Some(parent) = path.parent()
+                    && !parent.as_os_str().is_empty()
+                {
+                    create_dir_all(parent)?;
+                }
+                let mut opts = OpenOptions::new();
+                opts.write(true).create(true);
+                if overwrite {
+                    opts.truncate(true);
+                } else {
+                    opts.create_new(true);
+                }
+                let mut file = match opts.open(path) {
+                    Ok(f) => f,
+                    Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return Ok(()),
+                    Err(e) => return Err(PyErr::from(e)),
+                };
 
-        file.write_all(self.as_yaml_string()?.as_bytes())?;
-        file.flush()?;
-        Ok(())
+                file.write_all(contents.as_bytes())?;
+                file.flush()?;
+                Ok(())
+            }
+        }
+    }
+    /// Like `as_yaml_string`, but factors structurally-identical repeated subtrees (shared step
+    /// lists, matrix fragments, env maps, ...) into YAML anchors and aliases instead of repeating
+    /// them verbatim. Only mappings and sequences are anchored (never scalars), and only once one
+    /// occurs more than once *and* its structural key (see `yaml_structural_key`) is at least
+    /// `min_size` bytes long.
+    fn as_yaml_string_deduped(&self, min_size: usize) -> PyResult<String> {
+        self.as_yaml_string_with_named_anchors(min_size, &HashMap::new())
+    }
+    /// Like `as_yaml_string_deduped`, but `named_anchors` maps the structural key of a subtree
+    /// (see `yaml_structural_key`) to an explicit anchor name that takes priority over the
+    /// generated `a1`/`a2`/... names and is always anchored regardless of `min_size`, even if it
+    /// only occurs once in this document.
+    fn as_yaml_string_with_named_anchors(
+        &self,
+        min_size: usize,
+        named_anchors: &HashMap<String, String>,
+    ) -> PyResult<String> {
+        let yaml = self.as_yaml();
+        let mut counts = HashMap::new();
+        count_structural_occurrences(&yaml, &mut counts);
+        let mut anchors = HashMap::new();
+        let mut out = String::new();
+        emit_deduped(
+            &yaml,
+            &counts,
+            min_size,
+            named_anchors,
+            &mut anchors,
+            &mut out,
+            0,
+        )?;
+        out.push('\n');
+        Ok(out)
     }
 }
 impl Yamlable for Yaml {
@@ -71,6 +167,144 @@ impl Yamlable for &Yaml {
     }
 }
 
+/// A canonical string representation of a `Yaml` node used only to group structurally-identical
+/// subtrees for anchor/alias deduplication; hash keys are sorted here (but not in the emitted
+/// output) so that insertion order doesn't affect whether two hashes are considered equal.
+fn yaml_structural_key(yaml: &Yaml) -> String {
+    match yaml {
+        Yaml::Real(s) => format!("r:{s}"),
+        Yaml::Integer(i) => format!("i:{i}"),
+        Yaml::String(s) => format!("s:{s}"),
+        Yaml::Boolean(b) => format!("b:{b}"),
+        Yaml::Null => "n:".to_string(),
+        Yaml::Array(items) => {
+            let parts: Vec<String> = items.iter().map(yaml_structural_key).collect();
+            format!("[{}]", parts.join(","))
+        }
+        Yaml::Hash(hash) => {
+            let mut parts: Vec<String> = hash
+                .iter()
+                .map(|(k, v)| format!("{}={}", yaml_structural_key(k), yaml_structural_key(v)))
+                .collect();
+            parts.sort();
+            format!("{{{}}}", parts.join(","))
+        }
+        Yaml::Alias(id) => format!("*{id}"),
+        Yaml::BadValue => "!".to_string(),
+    }
+}
+
+fn count_structural_occurrences(yaml: &Yaml, counts: &mut HashMap<String, usize>) {
+    *counts.entry(yaml_structural_key(yaml)).or_insert(0) += 1;
+    match yaml {
+        Yaml::Array(items) => items
+            .iter()
+            .for_each(|item| count_structural_occurrences(item, counts)),
+        Yaml::Hash(hash) => hash.iter().for_each(|(k, v)| {
+            count_structural_occurrences(k, counts);
+            count_structural_occurrences(v, counts);
+        }),
+        _ => {}
+    }
+}
+
+fn emit_deduped_scalar(yaml: &Yaml, out: &mut String) -> PyResult<()> {
+    // Reuse the real emitter for a single scalar so quoting/escaping stays identical to the
+    // non-deduplicated path, then strip the `---\n` document header it adds.
+    let mut scalar_doc = String::new();
+    let mut emitter = YamlEmitter::new(&mut scalar_doc);
+    emitter.multiline_strings(true);
+    emitter
+        .dump(yaml)
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    out.push_str(scalar_doc.trim_start_matches("---").trim());
+    Ok(())
+}
+
+fn emit_deduped(
+    yaml: &Yaml,
+    counts: &HashMap<String, usize>,
+    min_size: usize,
+    named: &HashMap<String, String>,
+    anchors: &mut HashMap<String, String>,
+    out: &mut String,
+    indent: usize,
+) -> PyResult<()> {
+    let key = yaml_structural_key(yaml);
+    let eligible = matches!(yaml, Yaml::Hash(_) | Yaml::Array(_))
+        && (named.contains_key(&key)
+            || (counts.get(&key).copied().unwrap_or(0) > 1 && key.len() >= min_size));
+    if eligible {
+        if let Some(name) = anchors.get(&key) {
+            out.push_str(&format!("*{name}"));
+            return Ok(());
+        }
+    }
+    let anchor = eligible.then(|| {
+        let name = named
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| format!("a{}", anchors.len() + 1));
+        anchors.insert(key.clone(), name.clone());
+        name
+    });
+    match yaml {
+        Yaml::Hash(hash) if !hash.is_empty() => {
+            if let Some(name) = &anchor {
+                out.push_str(&format!("&{name}\n"));
+                out.push_str(&" ".repeat(indent));
+            }
+            for (i, (k, v)) in hash.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indent));
+                }
+                emit_deduped_scalar(k, out)?;
+                out.push_str(": ");
+                match v {
+                    Yaml::Hash(h) if !h.is_empty() => {
+                        out.push('\n');
+                        out.push_str(&" ".repeat(indent + 2));
+                        emit_deduped(v, counts, min_size, named, anchors, out, indent + 2)?;
+                    }
+                    Yaml::Array(a) if !a.is_empty() => {
+                        out.push('\n');
+                        out.push_str(&" ".repeat(indent));
+                        emit_deduped(v, counts, min_size, named, anchors, out, indent)?;
+                    }
+                    _ => emit_deduped(v, counts, min_size, named, anchors, out, indent + 2)?,
+                }
+            }
+        }
+        Yaml::Array(items) if !items.is_empty() => {
+            if let Some(name) = &anchor {
+                out.push_str(&format!("&{name}\n"));
+                out.push_str(&" ".repeat(indent));
+            }
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indent));
+                }
+                out.push_str("- ");
+                match item {
+                    Yaml::Hash(h) if !h.is_empty() => {
+                        emit_deduped(item, counts, min_size, named, anchors, out, indent + 2)?;
+                    }
+                    Yaml::Array(a) if !a.is_empty() => {
+                        out.push('\n');
+                        out.push_str(&" ".repeat(indent + 2));
+                        emit_deduped(item, counts, min_size, named, anchors, out, indent + 2)?;
+                    }
+                    _ => emit_deduped(item, counts, min_size, named, anchors, out, indent + 2)?,
+                }
+            }
+        }
+        _ => emit_deduped_scalar(yaml, out)?,
+    }
+    Ok(())
+}
+
 fn push_escaped_control(out: &mut String, ch: char) -> bool {
     match ch {
         '\n' => out.push_str("\\n"),
@@ -206,21 +440,16 @@ where
         Self(LinkedHashMap::default())
     }
 }
-impl<'a, 'py, K, V> FromPyObject<'a, 'py> for PyMap<K, V>
+impl<'py, K, V> FromPyObject<'py> for PyMap<K, V>
 where
-    K: FromPyObjectOwned<'py> + std::cmp::Eq + std::hash::Hash,
-    V: FromPyObjectOwned<'py>,
+    K: FromPyObject<'py> + std::cmp::Eq + std::hash::Hash,
+    V: FromPyObject<'py>,
 {
-    type Error = PyErr;
-
-    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
         let dict = obj.cast::<PyDict>()?;
         let mut ret = LinkedHashMap::with_capacity(dict.len());
         for (k, v) in dict.iter() {
-            ret.insert(
-                k.extract().map_err(Into::into)?,
-                v.extract().map_err(Into::into)?,
-            );
+            ret.insert(k.extract()?, v.extract()?);
         }
         Ok(PyMap(ret))
     }
@@ -239,6 +468,14 @@ where
         Yaml::Hash(hash)
     }
 }
+impl<K, V> FromIterator<(K, V)> for PyMap<K, V>
+where
+    K: std::cmp::Eq + std::hash::Hash,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self(LinkedHashMap::from_iter(iter))
+    }
+}
 #[derive(Clone)]
 pub enum BoolOrString {
     Bool(bool),
@@ -252,10 +489,8 @@ impl Yamlable for &BoolOrString {
         }
     }
 }
-impl<'a, 'py> FromPyObject<'a, 'py> for BoolOrString {
-    type Error = PyErr;
-
-    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
+impl<'py> FromPyObject<'py> for BoolOrString {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
         if let Ok(b) = obj.extract::<bool>() {
             Ok(Self::Bool(b))
         } else if let Ok(s) = obj.extract::<String>() {
@@ -268,6 +503,59 @@ impl<'a, 'py> FromPyObject<'a, 'py> for BoolOrString {
     }
 }
 
+/// A value that accepts either a bare scalar or a list in hand-written YAML (`needs: build` vs
+/// `needs: [build, test]`), normalizing to a list internally and emitting a scalar again when
+/// there is exactly one element, to match idiomatic Actions YAML.
+#[derive(Clone)]
+pub struct OneOrVec<T>(Vec<T>);
+impl<T> OneOrVec<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+impl<T> From<Vec<T>> for OneOrVec<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self(values)
+    }
+}
+impl<'a, T> IntoIterator for &'a OneOrVec<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+impl<'py, T> FromPyObject<'py> for OneOrVec<T>
+where
+    T: FromPyObject<'py>,
+{
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(values) = obj.extract::<Vec<T>>() {
+            Ok(Self(values))
+        } else {
+            Ok(Self(vec![obj.extract::<T>()?]))
+        }
+    }
+}
+impl<T> Yamlable for &OneOrVec<T>
+where
+    for<'a> &'a T: Yamlable,
+{
+    fn as_yaml(&self) -> Yaml {
+        match self.0.as_slice() {
+            [single] => single.as_yaml(),
+            values => Yaml::Array(values.iter().map(|v| v.as_yaml()).collect()),
+        }
+    }
+}
+
 pub trait MaybeYamlable {
     fn maybe_as_yaml(&self) -> Option<Yaml>;
     fn maybe_as_yaml_string(&self) -> PyResult<String> {
@@ -383,14 +671,12 @@ where
         }
     }
 }
-impl<'a, 'py, A, B> FromPyObject<'a, 'py> for Either<A, B>
+impl<'py, A, B> FromPyObject<'py> for Either<A, B>
 where
-    A: FromPyObject<'a, 'py>,
-    B: FromPyObject<'a, 'py>,
+    A: FromPyObject<'py>,
+    B: FromPyObject<'py>,
 {
-    type Error = PyErr;
-
-    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
         if let Ok(a) = obj.extract::<A>() {
             Ok(Self::A(a))
         } else if let Ok(b) = obj.extract::<B>() {
@@ -425,31 +711,65 @@ where
     }
 }
 
+pyo3::create_exception!(
+    yamloom,
+    FilterConflictError,
+    PyValueError,
+    "Raised when an event specifies both an include filter (e.g. `branches`) and its matching \
+     `-ignore` counterpart, a combination GitHub Actions rejects at workflow-parse time."
+);
+
+pyo3::create_exception!(
+    yamloom,
+    ValidationError,
+    PyRuntimeError,
+    "Raised by `Workflow.validate`/`Action.validate` when the rendered YAML fails schema \
+     validation. `args[0]` is a newline-joined summary of every violation; `args[1]` is the full \
+     list of `ValidationIssue` records (one per violation, collected by iterating the validator \
+     instead of stopping at the first failure) so callers can map each problem back to the \
+     offending job/step instead of fixing them one at a time."
+);
+
 /// A Pythonic implementation of GitHub Actions syntax
 #[pymodule]
 #[pyo3(name = "_yamloom")]
 mod yamloom {
-    use std::{collections::HashMap, fmt::Display, path::PathBuf, str::FromStr};
+    use std::{
+        collections::{HashMap, HashSet},
+        fmt::Display,
+        path::PathBuf,
+        str::FromStr,
+    };
 
     use pyo3::{
         exceptions::{PyRuntimeError, PyValueError},
         prelude::*,
-        types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple},
+        types::{
+            PyBool, PyDateAccess, PyDateTime, PyDict, PyFloat, PyInt, PyList, PyString,
+            PyTimeAccess, PyTuple, PyTzInfo, PyTzInfoAccess,
+        },
     };
     use yaml_rust2::{
-        Yaml,
+        Yaml, YamlLoader,
         yaml::{Array, Hash},
     };
 
     use crate::{
-        Either, InsertYaml, MaybeYamlable, PushYaml, PyMap, TryArray, TryHash, TryYamlable,
-        WORKFLOW_SCHEMA, Yamlable, yaml_to_json,
+        ACTION_SCHEMA, Either, InsertYaml, MaybeYamlable, OneOrVec, PushYaml, PyMap, TryArray,
+        TryHash, TryYamlable, Validator, WORKFLOW_SCHEMA, WriteMode, Yamlable,
+        collect_schema_issues, render_yaml_document, yaml_structural_key, yaml_to_json,
+        yamloom::evaluate::{evaluate_value, json_to_py, value_to_yaml},
         yamloom::expressions::{
             Allowed, ArrayExpression, BooleanExpression, Contexts, Funcs, NumberExpression,
-            ObjectExpression, StringExpression, YamlExpression,
+            ObjectExpression, StringExpression, TaintSeverity, YamlExpression, parse_scalar,
         },
     };
 
+    #[pymodule_export]
+    use super::FilterConflictError;
+    #[pymodule_export]
+    use super::ValidationError;
+
     #[pymodule]
     mod expressions {
         use std::marker::PhantomData;
@@ -463,8 +783,8 @@ mod yamloom {
         use crate::push_escaped_control;
 
         use super::{
-            Bound, Display, Either, Py, PyAny, PyAnyMethods, PyResult, PyValueError, Yaml,
-            Yamlable, pyclass, pyfunction, pymethods,
+            Bound, Display, Either, FromPyObject, FromStr, Py, PyAny, PyAnyMethods, PyErr,
+            PyResult, PyValueError, Yaml, Yamlable, pyclass, pyfunction, pymethods,
         };
 
         type StringLike = Either<StringExpression, String>;
@@ -499,13 +819,72 @@ mod yamloom {
                 const CANCELLED = 1 << 2;
                 const SUCCESS = 1 << 3;
                 const FAILURE = 1 << 4;
-            }
+                const FORMAT = 1 << 5;
+                const JOIN = 1 << 6;
+                const CONTAINS = 1 << 7;
+                const STARTS_WITH = 1 << 8;
+                const ENDS_WITH = 1 << 9;
+                const FROM_JSON = 1 << 10;
+            }
+        }
+
+        /// `github.event.*` (and similar) paths whose values are controlled by whoever triggered
+        /// the workflow (issue/PR title & body, commit messages, branch names, …). Interpolating
+        /// one of these directly into a shell `run:` body is the classic GitHub Actions
+        /// script-injection vector; `ExprBase::with_contexts` checks new expressions against this
+        /// table so the taint flag propagates automatically through every combinator.
+        pub(super) const UNTRUSTED_PATHS: &[&str] = &[
+            "github.event.issue.title",
+            "github.event.issue.body",
+            "github.event.pull_request.title",
+            "github.event.pull_request.body",
+            "github.event.comment.body",
+            "github.event.review.body",
+            "github.event.review_comment.body",
+            "github.event.pages",
+            "github.event.head_commit.message",
+            "github.event.head_commit.author.name",
+            "github.event.head_commit.author.email",
+            "github.event.commits",
+            "github.head_ref",
+        ];
+
+        fn is_untrusted_path(text: &str, extra: &[String]) -> bool {
+            UNTRUSTED_PATHS
+                .iter()
+                .copied()
+                .chain(extra.iter().map(String::as_str))
+                .any(|path| {
+                    text == path
+                        || text.starts_with(&format!("{path}."))
+                        || text.starts_with(&format!("{path}["))
+                })
+        }
+
+        /// GitHub's own coercion lattice, as used by `==`/`!=`/ordering: `null`, `bool`, `number`
+        /// and `string` are all mutually coercible, so the only combination that's ever actually
+        /// nonsensical at runtime is comparing a structured `object`/array value (e.g. the result
+        /// of `fromJSON`) against one of those scalars.
+        fn kinds_coercible(a: InferredKind, b: InferredKind) -> bool {
+            a == b || (a != InferredKind::Object && b != InferredKind::Object)
         }
 
         #[derive(Clone, Copy, Debug, PartialEq, Eq)]
         struct ExprMeta {
             contexts: Contexts,
             funcs: Funcs,
+            tainted: bool,
+            /// The kind this expression is known to synthesize, when it can be determined
+            /// without ambiguity (a literal, or the documented return type of a builtin
+            /// function). Left `None` for context paths, whose runtime shape can't be known
+            /// statically. Populated opt-in by the `parse_expr` parser (`scan_meta` leaves it
+            /// `None`) and by the `as_*` cast methods; see `check_types`.
+            inferred_kind: Option<InferredKind>,
+            /// Set once an operator has combined two operands (via `union`) whose `inferred_kind`
+            /// isn't reconcilable under `kinds_coercible`, or once an `as_*` cast has contradicted
+            /// an already-known kind. Never turns a validation into a hard error on its own;
+            /// surfaced by `check_types` and folded into `Allowed::validate`'s message.
+            type_conflict: bool,
         }
 
         impl ExprMeta {
@@ -513,6 +892,9 @@ mod yamloom {
                 Self {
                     contexts: Contexts::NONE,
                     funcs: Funcs::NONE,
+                    tainted: false,
+                    inferred_kind: None,
+                    type_conflict: false,
                 }
             }
 
@@ -520,6 +902,9 @@ mod yamloom {
                 Self {
                     contexts,
                     funcs: Funcs::NONE,
+                    tainted: false,
+                    inferred_kind: None,
+                    type_conflict: false,
                 }
             }
 
@@ -527,13 +912,45 @@ mod yamloom {
                 Self {
                     contexts: Contexts::NONE,
                     funcs,
+                    tainted: false,
+                    inferred_kind: None,
+                    type_conflict: false,
+                }
+            }
+
+            fn with_kind(mut self, kind: InferredKind) -> Self {
+                self.inferred_kind = Some(kind);
+                self
+            }
+
+            /// Opt-in type check for an `as_*` cast: records a conflict if this expression's
+            /// kind was already known and isn't coercible with `target`, then adopts `target`
+            /// going forward, since the cast's phantom `Kind` is now authoritative.
+            fn checked_cast(self, target: InferredKind) -> Self {
+                let type_conflict = self.type_conflict
+                    || self
+                        .inferred_kind
+                        .is_some_and(|known| !kinds_coercible(known, target));
+                Self {
+                    inferred_kind: Some(target),
+                    type_conflict,
+                    ..self
                 }
             }
 
             fn union(self, other: Self) -> Self {
+                let type_conflict = self.type_conflict
+                    || other.type_conflict
+                    || matches!(
+                        (self.inferred_kind, other.inferred_kind),
+                        (Some(a), Some(b)) if !kinds_coercible(a, b)
+                    );
                 Self {
                     contexts: self.contexts | other.contexts,
                     funcs: self.funcs | other.funcs,
+                    tainted: self.tainted || other.tainted,
+                    inferred_kind: self.inferred_kind.or(other.inferred_kind),
+                    type_conflict,
                 }
             }
         }
@@ -550,7 +967,10 @@ mod yamloom {
             }
 
             fn with_contexts(text: impl Into<String>, contexts: Contexts) -> Self {
-                Self::new(text.into(), ExprMeta::with_contexts(contexts))
+                let text = text.into();
+                let mut meta = ExprMeta::with_contexts(contexts);
+                meta.tainted = is_untrusted_path(&text, &[]);
+                Self::new(text, meta)
             }
         }
 
@@ -614,6 +1034,10 @@ mod yamloom {
                 }
             }
 
+            pub(super) fn label(&self) -> &'static str {
+                self.label
+            }
+
             fn validate(self, meta: ExprMeta, expr: &str) -> PyResult<()> {
                 let disallowed_contexts = meta.contexts & !self.contexts;
                 let disallowed_funcs = meta.funcs & !self.funcs;
@@ -645,10 +1069,77 @@ mod yamloom {
                         allowed_funcs.join(", ")
                     ));
                 }
+                if meta.type_conflict {
+                    message.push_str(
+                        "\n\nThis expression also combines operands of incompatible inferred \
+                         types; see check_types() for details.",
+                    );
+                }
                 Err(PyRuntimeError::new_err(message))
             }
         }
 
+        /// Whether a tainted expression should be rejected outright or merely reported.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub(super) enum TaintSeverity {
+            Warn,
+            Error,
+        }
+        impl FromStr for TaintSeverity {
+            type Err = PyErr;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.to_lowercase().as_str() {
+                    "warn" => Ok(Self::Warn),
+                    "error" => Ok(Self::Error),
+                    _ => Err(PyValueError::new_err(
+                        "Invalid untrusted-input severity, expected 'warn' or 'error'",
+                    )),
+                }
+            }
+        }
+
+        /// Parallel to `Allowed::validate`: rejects (or warns about) an expression whose `meta`
+        /// was marked tainted by `ExprBase::with_contexts`, i.e. one that reads an attacker
+        /// controllable `github.event.*`-style field. Intended for callers that are about to
+        /// interpolate `expr` into a shell `run:` body, the classic script-injection vector.
+        pub(super) fn validate_untrusted(
+            meta: ExprMeta,
+            expr: &str,
+            severity: TaintSeverity,
+        ) -> PyResult<()> {
+            if !meta.tainted {
+                return Ok(());
+            }
+            let message = format!(
+                "Expression reads an attacker-controllable value and may allow script injection \
+                 if interpolated directly into a shell command:\n{expr}\n\nBind it to an `env:` \
+                 entry via `StepOptions.env` and reference `$VAR` in the script instead of \
+                 inlining the expression."
+            );
+            match severity {
+                TaintSeverity::Error => Err(PyRuntimeError::new_err(message)),
+                TaintSeverity::Warn => {
+                    eprintln!("yamloom: warning: {message}");
+                    Ok(())
+                }
+            }
+        }
+
+        /// Opt-in counterpart to `validate_untrusted`: surfaces `meta.type_conflict` (set by
+        /// `ExprMeta::union`/`checked_cast` when two operands' inferred kinds aren't reconcilable
+        /// under GitHub's coercion rules) as a diagnostic string rather than a hard error, since a
+        /// type mismatch here is a code smell rather than something GitHub itself rejects.
+        fn type_conflict_diagnostic(meta: ExprMeta, expr: &str) -> Option<String> {
+            meta.type_conflict.then(|| {
+                format!(
+                    "Expression combines or casts between operands of incompatible inferred \
+                     types (comparing or casting a structured object/array value, e.g. the \
+                     result of `fromJSON`, against a scalar is never meaningful under GitHub's \
+                     coercion rules):\n{expr}"
+                )
+            })
+        }
+
         fn contexts_to_names(contexts: Contexts) -> Vec<&'static str> {
             let mut out = Vec::new();
             if contexts.contains(Contexts::GITHUB) {
@@ -707,6 +1198,24 @@ mod yamloom {
             if funcs.contains(Funcs::FAILURE) {
                 out.push("failure");
             }
+            if funcs.contains(Funcs::FORMAT) {
+                out.push("format");
+            }
+            if funcs.contains(Funcs::JOIN) {
+                out.push("join");
+            }
+            if funcs.contains(Funcs::CONTAINS) {
+                out.push("contains");
+            }
+            if funcs.contains(Funcs::STARTS_WITH) {
+                out.push("startsWith");
+            }
+            if funcs.contains(Funcs::ENDS_WITH) {
+                out.push("endsWith");
+            }
+            if funcs.contains(Funcs::FROM_JSON) {
+                out.push("fromJSON");
+            }
             out
         }
 
@@ -738,6 +1247,63 @@ mod yamloom {
             }
         }
 
+        /// Any operand `==`/`!=` can unify against: any of the four typed expressions, or a raw
+        /// Rust literal standing in for one. GitHub's equality operator coerces freely across
+        /// bool/number/string (and treats an unparsed value as its string form), so unlike
+        /// `BoolLike`/`NumberLike`/`StringLike` this does not restrict `other` to the receiver's
+        /// own kind — `union()` still records a `type_conflict` diagnostic if both sides carry a
+        /// known, incoercible `inferred_kind` (e.g. comparing against a `fromJSON` object).
+        enum AnyLike {
+            Bool(BooleanExpression),
+            Number(NumberExpression),
+            Str(StringExpression),
+            Obj(ObjectExpression),
+            BoolRaw(bool),
+            NumberRaw(f64),
+            StrRaw(String),
+        }
+        impl<'py> FromPyObject<'py> for AnyLike {
+            fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+                if let Ok(v) = obj.extract::<BooleanExpression>() {
+                    Ok(Self::Bool(v))
+                } else if let Ok(v) = obj.extract::<NumberExpression>() {
+                    Ok(Self::Number(v))
+                } else if let Ok(v) = obj.extract::<StringExpression>() {
+                    Ok(Self::Str(v))
+                } else if let Ok(v) = obj.extract::<ObjectExpression>() {
+                    Ok(Self::Obj(v))
+                } else if let Ok(v) = obj.extract::<bool>() {
+                    Ok(Self::BoolRaw(v))
+                } else if let Ok(v) = obj.extract::<f64>() {
+                    Ok(Self::NumberRaw(v))
+                } else if let Ok(v) = obj.extract::<String>() {
+                    Ok(Self::StrRaw(v))
+                } else {
+                    Err(PyValueError::new_err("Invalid value"))
+                }
+            }
+        }
+
+        fn render_any_like(value: AnyLike) -> (String, ExprMeta) {
+            match value {
+                AnyLike::Bool(expr) => (expr.to_string(), expr.meta()),
+                AnyLike::Number(expr) => (expr.to_string(), expr.meta()),
+                AnyLike::Str(expr) => (expr.to_string(), expr.meta()),
+                AnyLike::Obj(expr) => (expr.to_string(), expr.meta()),
+                AnyLike::BoolRaw(raw) => (
+                    if raw { "true".to_string() } else { "false".to_string() },
+                    ExprMeta::empty().with_kind(InferredKind::Bool),
+                ),
+                AnyLike::NumberRaw(raw) => {
+                    (raw.to_string(), ExprMeta::empty().with_kind(InferredKind::Number))
+                }
+                AnyLike::StrRaw(raw) => (
+                    escape_string(&raw),
+                    ExprMeta::empty().with_kind(InferredKind::String),
+                ),
+            }
+        }
+
         pub trait YamlExpression {
             fn stringify(&self) -> &str;
             fn as_expression_string(&self) -> String {
@@ -786,13 +1352,19 @@ mod yamloom {
         #[pymethods]
         impl BooleanExpression {
             fn as_num(&self) -> NumberExpression {
-                NumberExpression::new_expr(self.to_string(), self.meta())
+                NumberExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Number))
             }
             fn as_str(&self) -> StringExpression {
-                StringExpression::new_expr(self.to_string(), self.meta())
+                StringExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::String))
             }
             fn as_obj(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(self.to_string(), self.meta())
+                ObjectExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Object))
+            }
+            /// Opt-in type diagnostic: `None` unless an earlier operator or `as_*` cast combined
+            /// or reinterpreted operands whose inferred kinds aren't coercible under GitHub's
+            /// rules (e.g. a `fromJSON` object compared against or cast to a scalar).
+            fn check_types(&self) -> Option<String> {
+                type_conflict_diagnostic(self.meta(), &self.as_expression_string())
             }
             fn __invert__(&self) -> Self {
                 Self::new_expr(format!("!({self})"), self.meta())
@@ -811,16 +1383,16 @@ mod yamloom {
                     self.meta().union(other_meta),
                 )
             }
-            fn __eq__(&self, other: BoolLike) -> Self {
-                let (other, other_meta) = render_bool_like(other);
-                Self::new_expr(
+            fn __eq__(&self, other: AnyLike) -> BooleanExpression {
+                let (other, other_meta) = render_any_like(other);
+                BooleanExpression::new_expr(
                     format!("({self} == {other})"),
                     self.meta().union(other_meta),
                 )
             }
-            fn __ne__(&self, other: BoolLike) -> Self {
-                let (other, other_meta) = render_bool_like(other);
-                Self::new_expr(
+            fn __ne__(&self, other: AnyLike) -> BooleanExpression {
+                let (other, other_meta) = render_any_like(other);
+                BooleanExpression::new_expr(
                     format!("({self} != {other})"),
                     self.meta().union(other_meta),
                 )
@@ -831,8 +1403,8 @@ mod yamloom {
                 let meta = self.meta().union(condition_meta).union(else_meta);
                 BooleanExpression::new_expr(format!("({condition} && {self} || {else_expr})"), meta)
             }
-            fn to_json(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(format!("toJSON({self})"), self.meta())
+            fn to_json(&self) -> StringExpression {
+                StringExpression::new_expr(format!("toJSON({self})"), self.meta())
             }
             fn __str__(&self) -> String {
                 self.as_expression_string()
@@ -863,6 +1435,90 @@ mod yamloom {
                 ExprMeta::with_funcs(Funcs::FAILURE),
             )
         }
+        #[pyfunction]
+        fn contains(haystack: StringLike, needle: StringLike) -> BooleanExpression {
+            let (haystack, haystack_meta) = render_string_like(haystack);
+            let (needle, needle_meta) = render_string_like(needle);
+            BooleanExpression::new_expr(
+                format!("contains({haystack}, {needle})"),
+                haystack_meta
+                    .union(needle_meta)
+                    .union(ExprMeta::with_funcs(Funcs::CONTAINS)),
+            )
+        }
+        #[pyfunction]
+        fn starts_with(value: StringLike, prefix: StringLike) -> BooleanExpression {
+            let (value, value_meta) = render_string_like(value);
+            let (prefix, prefix_meta) = render_string_like(prefix);
+            BooleanExpression::new_expr(
+                format!("startsWith({value}, {prefix})"),
+                value_meta
+                    .union(prefix_meta)
+                    .union(ExprMeta::with_funcs(Funcs::STARTS_WITH)),
+            )
+        }
+        #[pyfunction]
+        fn ends_with(value: StringLike, suffix: StringLike) -> BooleanExpression {
+            let (value, value_meta) = render_string_like(value);
+            let (suffix, suffix_meta) = render_string_like(suffix);
+            BooleanExpression::new_expr(
+                format!("endsWith({value}, {suffix})"),
+                value_meta
+                    .union(suffix_meta)
+                    .union(ExprMeta::with_funcs(Funcs::ENDS_WITH)),
+            )
+        }
+        #[pyfunction]
+        fn format(template: StringLike, args: Vec<StringLike>) -> StringExpression {
+            let (template, template_meta) = render_string_like(template);
+            let mut meta = template_meta.union(ExprMeta::with_funcs(Funcs::FORMAT));
+            let args = args
+                .into_iter()
+                .map(|arg| {
+                    let (text, arg_meta) = render_string_like(arg);
+                    meta = meta.union(arg_meta);
+                    text
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            StringExpression::new_expr(format!("format({template}, {args})"), meta)
+        }
+        #[pyfunction]
+        #[pyo3(signature = (array, separator = None))]
+        fn join(array: &ArrayExpression, separator: Option<StringLike>) -> StringExpression {
+            array.join(separator)
+        }
+        #[pyfunction]
+        fn from_json(value: StringLike) -> ObjectExpression {
+            let (value, value_meta) = render_string_like(value);
+            ObjectExpression::new_expr(
+                format!("fromJSON({value})"),
+                value_meta.union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+            )
+        }
+        /// `hashFiles(pattern, *others)` as a free function, for building the expression without
+        /// already having a `StringExpression` to call `.hash_files()` on (e.g. a plain glob
+        /// literal like `"**/*.sum"`).
+        #[pyfunction]
+        #[pyo3(signature = (pattern, others = None))]
+        fn hash_files(pattern: StringLike, others: Option<Vec<StringLike>>) -> StringExpression {
+            let (pattern, mut meta) = render_string_like(pattern);
+            meta = meta.union(ExprMeta::with_funcs(Funcs::HASH_FILES));
+            if let Some(others) = others {
+                let args = others
+                    .into_iter()
+                    .map(|other| {
+                        let (text, other_meta) = render_string_like(other);
+                        meta = meta.union(other_meta);
+                        text
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                StringExpression::new_expr(format!("hashFiles({pattern}, {args})"), meta)
+            } else {
+                StringExpression::new_expr(format!("hashFiles({pattern})"), meta)
+            }
+        }
         #[pyclass]
         #[derive(Clone)]
         pub struct NumberExpression(Expression<NumberKind>);
@@ -893,16 +1549,24 @@ mod yamloom {
                 allowed.validate(self.meta(), &self.as_expression_string())
             }
         }
+        // No `__add__`/`__sub__`/`__mul__`/`__truediv__` here: GitHub's expression grammar has
+        // no arithmetic operators at all (only `!`, the comparisons, `&&`/`||`, `()`, `.`, and
+        // `[]`), so emitting one would render syntax the runner can't evaluate. Numeric
+        // expressions still unify against other kinds through `__eq__`/`__ne__`/`AnyLike`, and
+        // ordering (`__lt__`/`__le__`/`__gt__`/`__ge__`) below, exactly as GitHub supports.
         #[pymethods]
         impl NumberExpression {
             fn as_bool(&self) -> BooleanExpression {
-                BooleanExpression::new_expr(self.to_string(), self.meta())
+                BooleanExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Bool))
             }
             fn as_str(&self) -> StringExpression {
-                StringExpression::new_expr(self.to_string(), self.meta())
+                StringExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::String))
             }
             fn as_obj(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(self.to_string(), self.meta())
+                ObjectExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Object))
+            }
+            fn check_types(&self) -> Option<String> {
+                type_conflict_diagnostic(self.meta(), &self.as_expression_string())
             }
             fn __lt__(&self, other: NumberLike) -> BooleanExpression {
                 let (other, other_meta) = render_number_like(other);
@@ -932,15 +1596,15 @@ mod yamloom {
                     self.meta().union(other_meta),
                 )
             }
-            fn __eq__(&self, other: NumberLike) -> BooleanExpression {
-                let (other, other_meta) = render_number_like(other);
+            fn __eq__(&self, other: AnyLike) -> BooleanExpression {
+                let (other, other_meta) = render_any_like(other);
                 BooleanExpression::new_expr(
                     format!("({self} == {other})"),
                     self.meta().union(other_meta),
                 )
             }
-            fn __ne__(&self, other: NumberLike) -> BooleanExpression {
-                let (other, other_meta) = render_number_like(other);
+            fn __ne__(&self, other: AnyLike) -> BooleanExpression {
+                let (other, other_meta) = render_any_like(other);
                 BooleanExpression::new_expr(
                     format!("({self} != {other})"),
                     self.meta().union(other_meta),
@@ -952,8 +1616,8 @@ mod yamloom {
                 let meta = self.meta().union(condition_meta).union(else_meta);
                 NumberExpression::new_expr(format!("({condition} && {self} || {else_expr})"), meta)
             }
-            fn to_json(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(format!("toJSON({self})"), self.meta())
+            fn to_json(&self) -> StringExpression {
+                StringExpression::new_expr(format!("toJSON({self})"), self.meta())
             }
             fn __str__(&self) -> String {
                 self.as_expression_string()
@@ -988,27 +1652,36 @@ mod yamloom {
             pub(super) fn validate_allowed(&self, allowed: Allowed) -> PyResult<()> {
                 allowed.validate(self.meta(), &self.as_expression_string())
             }
+
+            /// Script-injection check for a `run:` line: rejects (or warns about) this
+            /// expression if it reads an attacker-controllable `github.event.*`-style field.
+            pub(super) fn validate_untrusted_input(&self, severity: TaintSeverity) -> PyResult<()> {
+                validate_untrusted(self.meta(), &self.as_expression_string(), severity)
+            }
         }
         #[pymethods]
         impl StringExpression {
             fn as_bool(&self) -> BooleanExpression {
-                BooleanExpression::new_expr(self.to_string(), self.meta())
+                BooleanExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Bool))
             }
             fn as_num(&self) -> NumberExpression {
-                NumberExpression::new_expr(self.to_string(), self.meta())
+                NumberExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Number))
             }
             fn as_obj(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(self.to_string(), self.meta())
+                ObjectExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Object))
             }
-            fn __eq__(&self, other: StringLike) -> BooleanExpression {
-                let (other, other_meta) = render_string_like(other);
+            fn check_types(&self) -> Option<String> {
+                type_conflict_diagnostic(self.meta(), &self.as_expression_string())
+            }
+            fn __eq__(&self, other: AnyLike) -> BooleanExpression {
+                let (other, other_meta) = render_any_like(other);
                 BooleanExpression::new_expr(
                     format!("({self} == {other})"),
                     self.meta().union(other_meta),
                 )
             }
-            fn __ne__(&self, other: StringLike) -> BooleanExpression {
-                let (other, other_meta) = render_string_like(other);
+            fn __ne__(&self, other: AnyLike) -> BooleanExpression {
+                let (other, other_meta) = render_any_like(other);
                 BooleanExpression::new_expr(
                     format!("({self} != {other})"),
                     self.meta().union(other_meta),
@@ -1018,25 +1691,31 @@ mod yamloom {
                 let (other, other_meta) = render_string_like(other);
                 BooleanExpression::new_expr(
                     format!("contains({self}, {other})"),
-                    self.meta().union(other_meta),
+                    self.meta()
+                        .union(other_meta)
+                        .union(ExprMeta::with_funcs(Funcs::CONTAINS)),
                 )
             }
             fn startswith(&self, other: StringLike) -> BooleanExpression {
                 let (other, other_meta) = render_string_like(other);
                 BooleanExpression::new_expr(
                     format!("startsWith({self}, {other})"),
-                    self.meta().union(other_meta),
+                    self.meta()
+                        .union(other_meta)
+                        .union(ExprMeta::with_funcs(Funcs::STARTS_WITH)),
                 )
             }
             fn endswith(&self, other: StringLike) -> BooleanExpression {
                 let (other, other_meta) = render_string_like(other);
                 BooleanExpression::new_expr(
                     format!("endsWith({self}, {other})"),
-                    self.meta().union(other_meta),
+                    self.meta()
+                        .union(other_meta)
+                        .union(ExprMeta::with_funcs(Funcs::ENDS_WITH)),
                 )
             }
             fn format(&self, args: Vec<StringLike>) -> StringExpression {
-                let mut meta = self.meta();
+                let mut meta = self.meta().union(ExprMeta::with_funcs(Funcs::FORMAT));
                 let args = args
                     .into_iter()
                     .map(|arg| {
@@ -1049,23 +1728,38 @@ mod yamloom {
                 StringExpression::new_expr(format!("format({self}, {args})"), meta)
             }
             // I don't think we need join for single strings despite the docs
-            fn to_json(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(format!("toJSON({self})"), self.meta())
+            fn to_json(&self) -> StringExpression {
+                StringExpression::new_expr(format!("toJSON({self})"), self.meta())
             }
             fn from_json_to_bool(&self) -> BooleanExpression {
-                BooleanExpression::new_expr(format!("fromJSON({self})"), self.meta())
+                BooleanExpression::new_expr(
+                    format!("fromJSON({self})"),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn from_json_to_num(&self) -> NumberExpression {
-                NumberExpression::new_expr(format!("fromJSON({self})"), self.meta())
+                NumberExpression::new_expr(
+                    format!("fromJSON({self})"),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn from_json_to_str(&self) -> Self {
-                Self::new_expr(format!("fromJSON({self})"), self.meta())
+                Self::new_expr(
+                    format!("fromJSON({self})"),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn from_json_to_array(&self) -> ArrayExpression {
-                ArrayExpression::new_expr(format!("fromJSON({self})"), self.meta())
+                ArrayExpression::new_expr(
+                    format!("fromJSON({self})"),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn from_json_to_obj(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(format!("fromJSON({self})"), self.meta())
+                ObjectExpression::new_expr(
+                    format!("fromJSON({self})"),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn hash_files(&self, others: Option<Vec<StringLike>>) -> StringExpression {
                 if let Some(others) = others {
@@ -1128,30 +1822,33 @@ mod yamloom {
         #[pymethods]
         impl ArrayExpression {
             fn as_num(&self) -> NumberExpression {
-                NumberExpression::new_expr(self.to_string(), self.meta())
+                NumberExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Number))
             }
             fn as_obj(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(self.to_string(), self.meta())
+                ObjectExpression::new_expr(self.to_string(), self.meta().checked_cast(InferredKind::Object))
+            }
+            fn check_types(&self) -> Option<String> {
+                type_conflict_diagnostic(self.meta(), &self.as_expression_string())
             }
             fn contains(&self, other: &ObjectExpression) -> BooleanExpression {
                 BooleanExpression::new_expr(
                     format!("contains({}, {})", self, other.stringify()),
-                    self.meta().union(other.meta()),
+                    self.meta()
+                        .union(other.meta())
+                        .union(ExprMeta::with_funcs(Funcs::CONTAINS)),
                 )
             }
             fn join(&self, separator: Option<StringLike>) -> StringExpression {
+                let meta = self.meta().union(ExprMeta::with_funcs(Funcs::JOIN));
                 if let Some(sep) = separator {
                     let (sep, sep_meta) = render_string_like(sep);
-                    StringExpression::new_expr(
-                        format!("join({self}, {sep})"),
-                        self.meta().union(sep_meta),
-                    )
+                    StringExpression::new_expr(format!("join({self}, {sep})"), meta.union(sep_meta))
                 } else {
-                    StringExpression::new_expr(format!("join({self})"), self.meta())
+                    StringExpression::new_expr(format!("join({self})"), meta)
                 }
             }
-            fn to_json(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(format!("toJSON({self})"), self.meta())
+            fn to_json(&self) -> StringExpression {
+                StringExpression::new_expr(format!("toJSON({self})"), self.meta())
             }
             fn __str__(&self) -> String {
                 self.as_expression_string()
@@ -1199,34 +1896,61 @@ mod yamloom {
         #[pymethods]
         impl ObjectExpression {
             fn as_num(&self) -> NumberExpression {
-                NumberExpression::new_expr(self.stringify().to_string(), self.meta())
+                NumberExpression::new_expr(
+                    self.stringify().to_string(),
+                    self.meta().checked_cast(InferredKind::Number),
+                )
             }
             fn as_str(&self) -> StringExpression {
-                StringExpression::new_expr(self.stringify().to_string(), self.meta())
+                StringExpression::new_expr(
+                    self.stringify().to_string(),
+                    self.meta().checked_cast(InferredKind::String),
+                )
             }
             fn as_bool(&self) -> BooleanExpression {
-                BooleanExpression::new_expr(self.stringify().to_string(), self.meta())
+                BooleanExpression::new_expr(
+                    self.stringify().to_string(),
+                    self.meta().checked_cast(InferredKind::Bool),
+                )
             }
             fn as_array(&self) -> ArrayExpression {
                 ArrayExpression::new_expr(self.stringify().to_string(), self.meta())
             }
-            fn to_json(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(format!("toJSON({})", self.stringify()), self.meta())
+            fn check_types(&self) -> Option<String> {
+                type_conflict_diagnostic(self.meta(), &self.as_expression_string())
+            }
+            fn to_json(&self) -> StringExpression {
+                StringExpression::new_expr(format!("toJSON({})", self.stringify()), self.meta())
             }
             fn from_json_to_bool(&self) -> BooleanExpression {
-                BooleanExpression::new_expr(format!("fromJSON({})", self.stringify()), self.meta())
+                BooleanExpression::new_expr(
+                    format!("fromJSON({})", self.stringify()),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn from_json_to_num(&self) -> NumberExpression {
-                NumberExpression::new_expr(format!("fromJSON({})", self.stringify()), self.meta())
+                NumberExpression::new_expr(
+                    format!("fromJSON({})", self.stringify()),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn from_json_to_str(&self) -> Self {
-                Self::new_expr(format!("fromJSON({})", self.stringify()), self.meta())
+                Self::new_expr(
+                    format!("fromJSON({})", self.stringify()),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn from_json_to_array(&self) -> ArrayExpression {
-                ArrayExpression::new_expr(format!("fromJSON({})", self.stringify()), self.meta())
+                ArrayExpression::new_expr(
+                    format!("fromJSON({})", self.stringify()),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             fn from_json_to_obj(&self) -> ObjectExpression {
-                ObjectExpression::new_expr(format!("fromJSON({})", self.stringify()), self.meta())
+                ObjectExpression::new_expr(
+                    format!("fromJSON({})", self.stringify()),
+                    self.meta().union(ExprMeta::with_funcs(Funcs::FROM_JSON)),
+                )
             }
             #[classattr]
             const __contains__: Option<Py<PyAny>> = None;
@@ -1241,305 +1965,132 @@ mod yamloom {
             }
         }
 
+        /// Declares a batch of fixed-path context getters for `$for` in one table, so that each
+        /// row names both the Python attribute and the GitHub expression path it resolves to
+        /// side by side, instead of a hand-copied `ExprBase::with_contexts` block per field.
+        /// `$parent` is the expression root (e.g. `"github"`) and `$ctx` the `Contexts` flag
+        /// every field in the table shares; `$path` is joined onto `$parent` with a `.` to form
+        /// the full expression text. An optional trailing `extra { ... }` holds any hand-written
+        /// methods (e.g. `expr`, or a getter returning a nested context struct) that have to live
+        /// alongside the table-driven getters: pyo3 rejects more than one `#[pymethods] impl` per
+        /// `#[pyclass]` unless the crate enables the `multiple-pymethods` feature (which this one
+        /// doesn't), so the whole impl block for `$for` is generated by this single macro call.
+        macro_rules! context_fields {
+            ($for:ty, $parent:literal, $ctx:expr, { $($name:ident : $ty:ident => $path:literal),* $(,)? } $(, extra { $($extra:tt)* })?) => {
+                #[pymethods]
+                impl $for {
+                    $($($extra)*)?
+                    $(
+                        #[getter]
+                        fn $name(&self) -> $ty {
+                            $ty::from_base(ExprBase::with_contexts(
+                                concat!($parent, ".", $path),
+                                $ctx,
+                            ))
+                        }
+                    )*
+                }
+            };
+        }
+
         #[pyclass]
         pub struct GithubContext;
-        #[pymethods]
-        impl GithubContext {
+        context_fields!(GithubContext, "github", Contexts::GITHUB, {
+            action: StringExpression => "action",
+            action_path: StringExpression => "action_path",
+            action_ref: StringExpression => "action_ref",
+            action_repository: StringExpression => "action_repository",
+            action_status: StringExpression => "action_status",
+            actor: StringExpression => "actor",
+            actor_id: StringExpression => "actor_id",
+            api_url: StringExpression => "api_url",
+            base_ref: StringExpression => "base_ref",
+            env: StringExpression => "env",
+            event_name: StringExpression => "event_name",
+            event_path: StringExpression => "event_path",
+            graphql_url: StringExpression => "graphql_url",
+            head_ref: StringExpression => "head_ref",
+            job: StringExpression => "job",
+            path: StringExpression => "path",
+            ref_name: StringExpression => "ref_name",
+            ref_type: StringExpression => "ref_type",
+            repository: StringExpression => "repository",
+            repository_id: StringExpression => "repository_id",
+            repository_owner: StringExpression => "repository_owner",
+            repository_owner_id: StringExpression => "repository_owner_id",
+            repository_url: StringExpression => "repositoryUrl",
+            retention_days: StringExpression => "retention_days",
+            run_id: StringExpression => "run_id",
+            run_number: StringExpression => "run_number",
+            run_attempt: StringExpression => "run_attempt",
+            secret_source: StringExpression => "secret_source",
+            server_url: StringExpression => "server_url",
+            sha: StringExpression => "sha",
+            token: StringExpression => "token",
+            triggering_actor: StringExpression => "triggering_actor",
+            workflow: StringExpression => "workflow",
+            workflow_ref: StringExpression => "workflow_ref",
+            workflow_sha: StringExpression => "workflow_sha",
+        }, extra {
             #[getter]
             fn expr(&self) -> ObjectExpression {
                 ObjectExpression::from_base(ExprBase::with_contexts("github", Contexts::GITHUB))
             }
             #[getter]
-            fn action(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.action",
+            fn event(&self) -> ObjectExpression {
+                ObjectExpression::from_base(ExprBase::with_contexts(
+                    "github.event",
                     Contexts::GITHUB,
                 ))
             }
             #[getter]
-            fn action_path(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.action_path",
-                    Contexts::GITHUB,
-                ))
+            fn r#ref(&self) -> StringExpression {
+                StringExpression::from_base(ExprBase::with_contexts("github.ref", Contexts::GITHUB))
             }
             #[getter]
-            fn action_ref(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.action_ref",
+            fn ref_protected(&self) -> BooleanExpression {
+                BooleanExpression::from_base(ExprBase::with_contexts(
+                    "github.ref_protected",
                     Contexts::GITHUB,
                 ))
             }
             #[getter]
-            fn action_repository(&self) -> StringExpression {
+            fn workspace(&self) -> StringExpression {
                 StringExpression::from_base(ExprBase::with_contexts(
-                    "github.action_repository",
+                    "github.workspace",
                     Contexts::GITHUB,
                 ))
             }
+        });
+
+        #[pyclass]
+        pub struct EnvContext;
+        #[pymethods]
+        impl EnvContext {
             #[getter]
-            fn action_status(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.action_status",
-                    Contexts::GITHUB,
-                ))
+            fn expr(&self) -> ObjectExpression {
+                ObjectExpression::from_base(ExprBase::with_contexts("env", Contexts::ENV))
             }
-            #[getter]
-            fn actor(&self) -> StringExpression {
+            #[classattr]
+            const __contains__: Option<Py<PyAny>> = None;
+            fn __getitem__(&self, key: &str) -> StringExpression {
                 StringExpression::from_base(ExprBase::with_contexts(
-                    "github.actor",
-                    Contexts::GITHUB,
+                    ObjectExpression::format_access("env", key),
+                    Contexts::ENV,
                 ))
             }
-            #[getter]
-            fn actor_id(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.actor_id",
-                    Contexts::GITHUB,
-                ))
+            fn __getattr__(&self, key: &str) -> StringExpression {
+                self.__getitem__(key)
             }
+        }
+
+        #[pyclass]
+        pub struct VarsContext;
+        #[pymethods]
+        impl VarsContext {
             #[getter]
-            fn api_url(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.api_url",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn base_ref(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.base_ref",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn env(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts("github.env", Contexts::GITHUB))
-            }
-            #[getter]
-            fn event(&self) -> ObjectExpression {
-                ObjectExpression::from_base(ExprBase::with_contexts(
-                    "github.event",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn event_name(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.event_name",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn event_path(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.event_path",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn graphql_url(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.graphql_url",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn head_ref(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.head_ref",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn job(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts("github.job", Contexts::GITHUB))
-            }
-            #[getter]
-            fn path(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.path",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn r#ref(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts("github.ref", Contexts::GITHUB))
-            }
-            #[getter]
-            fn ref_name(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.ref_name",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn ref_protected(&self) -> BooleanExpression {
-                BooleanExpression::from_base(ExprBase::with_contexts(
-                    "github.ref_protected",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn ref_type(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.ref_type",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn repository(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.repository",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn reporitory_id(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.reporitory_id",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn repositor_owner(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.repositor_owner",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn repository_owner_id(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.repository_owner_id",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn repository_url(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.repositoryUrl",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn retention_days(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.retention_days",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn run_id(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.run_id",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn run_number(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.run_number",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn run_attempt(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.run_attempt",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn secret_source(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.secret_source",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn server_url(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.server_url",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn sha(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts("github.sha", Contexts::GITHUB))
-            }
-            #[getter]
-            fn token(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.token",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn triggering_actor(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.triggering_actor",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn workflow(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.workflow",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn workflow_ref(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.workflow_ref",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn workflow_sha(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.workflow_sha",
-                    Contexts::GITHUB,
-                ))
-            }
-            #[getter]
-            fn workspace(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "github.workspace",
-                    Contexts::GITHUB,
-                ))
-            }
-        }
-
-        #[pyclass]
-        pub struct EnvContext;
-        #[pymethods]
-        impl EnvContext {
-            #[getter]
-            fn expr(&self) -> ObjectExpression {
-                ObjectExpression::from_base(ExprBase::with_contexts("env", Contexts::ENV))
-            }
-            #[classattr]
-            const __contains__: Option<Py<PyAny>> = None;
-            fn __getitem__(&self, key: &str) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    ObjectExpression::format_access("env", key),
-                    Contexts::ENV,
-                ))
-            }
-            fn __getattr__(&self, key: &str) -> StringExpression {
-                self.__getitem__(key)
-            }
-        }
-
-        #[pyclass]
-        pub struct VarsContext;
-        #[pymethods]
-        impl VarsContext {
-            #[getter]
-            fn expr(&self) -> ObjectExpression {
-                ObjectExpression::from_base(ExprBase::with_contexts("vars", Contexts::VARS))
+            fn expr(&self) -> ObjectExpression {
+                ObjectExpression::from_base(ExprBase::with_contexts("vars", Contexts::VARS))
             }
             #[classattr]
             const __contains__: Option<Py<PyAny>> = None;
@@ -1556,27 +2107,15 @@ mod yamloom {
 
         #[pyclass]
         pub struct JobContainerContext;
-        #[pymethods]
-        impl JobContainerContext {
+        context_fields!(JobContainerContext, "job.container", Contexts::JOB, {
+            id: StringExpression => "id",
+            network: StringExpression => "network",
+        }, extra {
             #[getter]
             fn expr(&self) -> ObjectExpression {
                 ObjectExpression::from_base(ExprBase::with_contexts("job.container", Contexts::JOB))
             }
-            #[getter]
-            fn id(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "job.container.id",
-                    Contexts::JOB,
-                ))
-            }
-            #[getter]
-            fn network(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "job.container.network",
-                    Contexts::JOB,
-                ))
-            }
-        }
+        });
 
         #[pyclass]
         pub struct JobServicesIdContext(String);
@@ -1629,20 +2168,15 @@ mod yamloom {
 
         #[pyclass]
         pub struct JobContext;
-        #[pymethods]
-        impl JobContext {
+        context_fields!(JobContext, "job", Contexts::JOB, {
+            check_run_id: NumberExpression => "check_run_id",
+            status: StringExpression => "status",
+        }, extra {
             #[getter]
             fn expr(&self) -> ObjectExpression {
                 ObjectExpression::from_base(ExprBase::with_contexts("job", Contexts::JOB))
             }
             #[getter]
-            fn check_run_id(&self) -> NumberExpression {
-                NumberExpression::from_base(ExprBase::with_contexts(
-                    "job.check_run_id",
-                    Contexts::JOB,
-                ))
-            }
-            #[getter]
             fn container(&self) -> JobContainerContext {
                 JobContainerContext
             }
@@ -1650,11 +2184,7 @@ mod yamloom {
             fn services(&self) -> JobServicesContext {
                 JobServicesContext
             }
-            #[getter]
-            fn status(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts("job.status", Contexts::JOB))
-            }
-        }
+        });
 
         #[pyclass]
         pub struct JobsJobIdOutputsContext(String);
@@ -1791,59 +2321,20 @@ mod yamloom {
 
         #[pyclass]
         pub struct RunnerContext;
-        #[pymethods]
-        impl RunnerContext {
+        context_fields!(RunnerContext, "runner", Contexts::RUNNER, {
+            name: StringExpression => "name",
+            os: StringExpression => "os",
+            arch: StringExpression => "arch",
+            temp: StringExpression => "temp",
+            tool_cache: StringExpression => "tool_cache",
+            debug: StringExpression => "debug",
+            environment: StringExpression => "environment",
+        }, extra {
             #[getter]
             fn expr(&self) -> ObjectExpression {
                 ObjectExpression::from_base(ExprBase::with_contexts("runner", Contexts::RUNNER))
             }
-            #[getter]
-            fn name(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "runner.name",
-                    Contexts::RUNNER,
-                ))
-            }
-            #[getter]
-            fn os(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts("runner.os", Contexts::RUNNER))
-            }
-            #[getter]
-            fn arch(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "runner.arch",
-                    Contexts::RUNNER,
-                ))
-            }
-            #[getter]
-            fn temp(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "runner.temp",
-                    Contexts::RUNNER,
-                ))
-            }
-            #[getter]
-            fn tool_cache(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "runner.tool_cache",
-                    Contexts::RUNNER,
-                ))
-            }
-            #[getter]
-            fn debug(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "runner.debug",
-                    Contexts::RUNNER,
-                ))
-            }
-            #[getter]
-            fn environment(&self) -> StringExpression {
-                StringExpression::from_base(ExprBase::with_contexts(
-                    "runner.environment",
-                    Contexts::RUNNER,
-                ))
-            }
-        }
+        });
 
         #[pyclass]
         pub struct SecretsContext;
@@ -1875,41 +2366,17 @@ mod yamloom {
 
         #[pyclass]
         pub struct StrategyContext;
-        #[pymethods]
-        impl StrategyContext {
+        context_fields!(StrategyContext, "strategy", Contexts::STRATEGY, {
+            fail_fast: BooleanExpression => "fail-fast",
+            job_index: NumberExpression => "job-index",
+            job_total: NumberExpression => "job-total",
+            max_parallel: NumberExpression => "max-parallel",
+        }, extra {
             #[getter]
             fn expr(&self) -> ObjectExpression {
                 ObjectExpression::from_base(ExprBase::with_contexts("strategy", Contexts::STRATEGY))
             }
-            #[getter]
-            fn fail_fast(&self) -> BooleanExpression {
-                BooleanExpression::from_base(ExprBase::with_contexts(
-                    "strategy.fail-fast",
-                    Contexts::STRATEGY,
-                ))
-            }
-            #[getter]
-            fn job_index(&self) -> NumberExpression {
-                NumberExpression::from_base(ExprBase::with_contexts(
-                    "strategy.job-index",
-                    Contexts::STRATEGY,
-                ))
-            }
-            #[getter]
-            fn job_total(&self) -> NumberExpression {
-                NumberExpression::from_base(ExprBase::with_contexts(
-                    "strategy.job-total",
-                    Contexts::STRATEGY,
-                ))
-            }
-            #[getter]
-            fn max_parallel(&self) -> NumberExpression {
-                NumberExpression::from_base(ExprBase::with_contexts(
-                    "strategy.max-parallel",
-                    Contexts::STRATEGY,
-                ))
-            }
-        }
+        });
 
         #[pyclass]
         pub struct MatrixContext;
@@ -2050,8 +2517,6 @@ mod yamloom {
             const inputs: InputsContext = InputsContext;
         }
 
-        // TODO: Does toJSON return a string?
-
         fn escape_string(s: &str) -> String {
             let mut out = String::with_capacity(s.len() + 2);
             out.push('\'');
@@ -2108,3346 +2573,9144 @@ mod yamloom {
                 Err(PyValueError::new_err("Expected a number"))
             }
         }
-    }
-
-    type StringLike = Either<StringExpression, String>;
-    type BoolLike = Either<BooleanExpression, bool>;
-    type IntLike = Either<NumberExpression, i64>;
 
-    macro_rules! ctx {
-        ($first:ident) => {
-            Contexts::$first
-        };
-        ($first:ident, $($rest:ident),+ $(,)?) => {
-            Contexts::$first$(.union(Contexts::$rest))+
-        };
+        const CONTEXT_PREFIXES: &[(&str, Contexts)] = &[
+            ("github.", Contexts::GITHUB),
+            ("needs.", Contexts::NEEDS),
+            ("strategy.", Contexts::STRATEGY),
+            ("matrix.", Contexts::MATRIX),
+            ("jobs.", Contexts::JOBS),
+            ("job.", Contexts::JOB),
+            ("runner.", Contexts::RUNNER),
+            ("steps.", Contexts::STEPS),
+            ("env.", Contexts::ENV),
+            ("vars.", Contexts::VARS),
+            ("secrets.", Contexts::SECRETS),
+            ("inputs.", Contexts::INPUTS),
+        ];
+
+        const FUNC_NAMES: &[(&str, Funcs)] = &[
+            ("hashFiles(", Funcs::HASH_FILES),
+            ("always(", Funcs::ALWAYS),
+            ("cancelled(", Funcs::CANCELLED),
+            ("success(", Funcs::SUCCESS),
+            ("failure(", Funcs::FAILURE),
+            ("format(", Funcs::FORMAT),
+            ("join(", Funcs::JOIN),
+            ("contains(", Funcs::CONTAINS),
+            ("startsWith(", Funcs::STARTS_WITH),
+            ("endsWith(", Funcs::ENDS_WITH),
+            ("fromJSON(", Funcs::FROM_JSON),
+        ];
+
+        /// Scan raw expression text (without the surrounding `${{ }}`) for known context
+        /// prefixes and function calls, recovering an approximation of the `ExprMeta` that
+        /// would have been tracked had the expression been built through the normal API.
+        ///
+        /// This is necessarily a heuristic: it cannot distinguish `github.event.foo` used as a
+        /// literal string from a context access embedded in a larger template, so it simply
+        /// looks for each prefix/function name anywhere in the text.
+        fn scan_meta(text: &str) -> ExprMeta {
+            let mut contexts = Contexts::NONE;
+            for (prefix, flag) in CONTEXT_PREFIXES {
+                if text.contains(prefix) {
+                    contexts |= *flag;
+                }
+            }
+            let mut funcs = Funcs::NONE;
+            for (name, flag) in FUNC_NAMES {
+                if text.contains(name) {
+                    funcs |= *flag;
+                }
+            }
+            ExprMeta {
+                contexts,
+                funcs,
+                tainted: is_untrusted_path(text, &[]),
+                inferred_kind: None,
+                type_conflict: false,
+            }
+        }
+
+        /// Strip a single layer of `${{ ... }}` delimiters from a YAML scalar, if present.
+        fn strip_delimiters(scalar: &str) -> Option<&str> {
+            let trimmed = scalar.trim();
+            trimmed
+                .strip_prefix("${{")
+                .and_then(|s| s.strip_suffix("}}"))
+                .map(str::trim)
+        }
+
+        /// Returns `true` if `scalar` is a GitHub Actions expression, i.e. it is wrapped in
+        /// `${{ ... }}`. Plain scalars that merely look numeric or boolean (e.g. because they
+        /// came from an expression that was itself rendered to a string) are left untouched by
+        /// `parse_scalar`, since only the `${{ }}` wrapper unambiguously marks an expression.
+        #[pyfunction]
+        fn is_expression(scalar: &str) -> bool {
+            strip_delimiters(scalar).is_some()
+        }
+
+        /// Parse a YAML scalar loaded from an existing workflow file back into a typed
+        /// expression, if it is one.
+        ///
+        /// Returns `None` for plain scalars. For `${{ ... }}` scalars, the inner text is kept
+        /// verbatim and its `Contexts`/`Funcs` metadata is recovered via `scan_meta` so that the
+        /// resulting expression still validates correctly against `Allowed` fields. The result
+        /// is always a `StringExpression`; since recovering the original phantom kind from text
+        /// alone isn't possible, callers that need a `BooleanExpression` or `NumberExpression`
+        /// should re-cast with `as_bool`/`as_num`.
+        #[pyfunction]
+        pub(super) fn parse_scalar(scalar: &str) -> Option<StringExpression> {
+            let inner = strip_delimiters(scalar)?;
+            let meta = scan_meta(inner);
+            Some(StringExpression::new_expr(inner.to_string(), meta))
+        }
+
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        enum InferredKind {
+            Bool,
+            Number,
+            String,
+            Object,
+        }
+
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        enum Token {
+            Ident(String),
+            Str,
+            Num,
+            Bool(bool),
+            Null,
+            Bang,
+            AndAnd,
+            OrOr,
+            EqEq,
+            Ne,
+            Lt,
+            Le,
+            Gt,
+            Ge,
+            LParen,
+            RParen,
+            LBracket,
+            RBracket,
+            Dot,
+            Comma,
+        }
+
+        /// Tokenizes `s`, pairing each token with the character position it starts at so parse
+        /// errors can point at the offending substring instead of only naming the whole input.
+        fn tokenize(s: &str) -> PyResult<Vec<(Token, usize)>> {
+            let chars: Vec<char> = s.chars().collect();
+            let mut tokens = Vec::new();
+            let mut i = 0;
+            while i < chars.len() {
+                let start = i;
+                let c = chars[i];
+                match c {
+                    c if c.is_whitespace() => i += 1,
+                    '(' => {
+                        tokens.push((Token::LParen, start));
+                        i += 1;
+                    }
+                    ')' => {
+                        tokens.push((Token::RParen, start));
+                        i += 1;
+                    }
+                    '[' => {
+                        tokens.push((Token::LBracket, start));
+                        i += 1;
+                    }
+                    ']' => {
+                        tokens.push((Token::RBracket, start));
+                        i += 1;
+                    }
+                    '.' => {
+                        tokens.push((Token::Dot, start));
+                        i += 1;
+                    }
+                    ',' => {
+                        tokens.push((Token::Comma, start));
+                        i += 1;
+                    }
+                    '!' => {
+                        if chars.get(i + 1) == Some(&'=') {
+                            tokens.push((Token::Ne, start));
+                            i += 2;
+                        } else {
+                            tokens.push((Token::Bang, start));
+                            i += 1;
+                        }
+                    }
+                    '=' if chars.get(i + 1) == Some(&'=') => {
+                        tokens.push((Token::EqEq, start));
+                        i += 2;
+                    }
+                    '&' if chars.get(i + 1) == Some(&'&') => {
+                        tokens.push((Token::AndAnd, start));
+                        i += 2;
+                    }
+                    '|' if chars.get(i + 1) == Some(&'|') => {
+                        tokens.push((Token::OrOr, start));
+                        i += 2;
+                    }
+                    '<' => {
+                        if chars.get(i + 1) == Some(&'=') {
+                            tokens.push((Token::Le, start));
+                            i += 2;
+                        } else {
+                            tokens.push((Token::Lt, start));
+                            i += 1;
+                        }
+                    }
+                    '>' => {
+                        if chars.get(i + 1) == Some(&'=') {
+                            tokens.push((Token::Ge, start));
+                            i += 2;
+                        } else {
+                            tokens.push((Token::Gt, start));
+                            i += 1;
+                        }
+                    }
+                    '\'' => {
+                        i += 1;
+                        loop {
+                            if i >= chars.len() {
+                                return Err(PyValueError::new_err(format!(
+                                    "Unterminated string literal in expression at position {start}: {s}"
+                                )));
+                            }
+                            if chars[i] == '\'' {
+                                if chars.get(i + 1) == Some(&'\'') {
+                                    i += 2;
+                                    continue;
+                                }
+                                i += 1;
+                                break;
+                            }
+                            i += 1;
+                        }
+                        tokens.push((Token::Str, start));
+                    }
+                    c if c.is_ascii_digit()
+                        || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) =>
+                    {
+                        if c == '-' {
+                            i += 1;
+                        }
+                        while chars
+                            .get(i)
+                            .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+                        {
+                            i += 1;
+                        }
+                        tokens.push((Token::Num, start));
+                    }
+                    c if c.is_ascii_alphabetic() || c == '_' => {
+                        while chars
+                            .get(i)
+                            .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+                        {
+                            i += 1;
+                        }
+                        let word: String = chars[start..i].iter().collect();
+                        tokens.push((
+                            match word.as_str() {
+                                "true" => Token::Bool(true),
+                                "false" => Token::Bool(false),
+                                "null" => Token::Null,
+                                _ => Token::Ident(word),
+                            },
+                            start,
+                        ));
+                    }
+                    other => {
+                        return Err(PyValueError::new_err(format!(
+                            "Unexpected character '{other}' in expression at position {start}: {s}"
+                        )));
+                    }
+                }
+            }
+            Ok(tokens)
+        }
+
+        /// A hand-written precedence-climbing parser over the token stream, used only to
+        /// recover `ExprMeta` and the inferred result `Kind` for `parse_expr`; the original text is
+        /// kept verbatim in the resulting `Expression` rather than being regenerated from the
+        /// parsed tree, so `[...]` vs `.` access and all other formatting round-trips exactly.
+        struct Parser<'a> {
+            tokens: &'a [(Token, usize)],
+            pos: usize,
+            end: usize,
+        }
+
+        impl<'a> Parser<'a> {
+            fn peek(&self) -> Option<&Token> {
+                self.tokens.get(self.pos).map(|(tok, _)| tok)
+            }
+
+            /// The character position of the current token, or the end of input if exhausted —
+            /// used to anchor error messages at the point parsing actually failed.
+            fn current_pos(&self) -> usize {
+                self.tokens
+                    .get(self.pos)
+                    .map_or(self.end, |(_, pos)| *pos)
+            }
+
+            fn advance(&mut self) -> Option<&Token> {
+                let tok = self.tokens.get(self.pos).map(|(tok, _)| tok);
+                self.pos += 1;
+                tok
+            }
+
+            fn expect(&mut self, tok: &Token) -> PyResult<()> {
+                let pos = self.current_pos();
+                if self.advance() == Some(tok) {
+                    Ok(())
+                } else {
+                    Err(PyValueError::new_err(format!(
+                        "Expected {tok:?} in expression at position {pos}"
+                    )))
+                }
+            }
+
+            fn parse_or(&mut self) -> PyResult<(InferredKind, ExprMeta)> {
+                let (_, mut meta) = self.parse_and()?;
+                while matches!(self.peek(), Some(Token::OrOr)) {
+                    self.advance();
+                    let (_, rhs) = self.parse_and()?;
+                    meta = meta.union(rhs);
+                }
+                Ok((InferredKind::Bool, meta))
+            }
+
+            fn parse_and(&mut self) -> PyResult<(InferredKind, ExprMeta)> {
+                let (_, mut meta) = self.parse_not()?;
+                while matches!(self.peek(), Some(Token::AndAnd)) {
+                    self.advance();
+                    let (_, rhs) = self.parse_not()?;
+                    meta = meta.union(rhs);
+                }
+                Ok((InferredKind::Bool, meta))
+            }
+
+            fn parse_not(&mut self) -> PyResult<(InferredKind, ExprMeta)> {
+                if matches!(self.peek(), Some(Token::Bang)) {
+                    self.advance();
+                    let (_, meta) = self.parse_not()?;
+                    return Ok((InferredKind::Bool, meta));
+                }
+                self.parse_comparison()
+            }
+
+            fn parse_comparison(&mut self) -> PyResult<(InferredKind, ExprMeta)> {
+                let (lhs_kind, mut meta) = self.parse_primary()?;
+                let is_cmp = matches!(
+                    self.peek(),
+                    Some(Token::EqEq | Token::Ne | Token::Lt | Token::Le | Token::Gt | Token::Ge)
+                );
+                if is_cmp {
+                    self.advance();
+                    let (_, rhs) = self.parse_primary()?;
+                    meta = meta.union(rhs);
+                    return Ok((InferredKind::Bool, meta));
+                }
+                Ok((lhs_kind, meta))
+            }
+
+            fn parse_primary(&mut self) -> PyResult<(InferredKind, ExprMeta)> {
+                match self.advance().cloned() {
+                    Some(Token::Bang) => {
+                        let (_, meta) = self.parse_primary()?;
+                        Ok((InferredKind::Bool, meta))
+                    }
+                    Some(Token::LParen) => {
+                        let inner = self.parse_or()?;
+                        self.expect(&Token::RParen)?;
+                        Ok(inner)
+                    }
+                    Some(Token::Str) => Ok((
+                        InferredKind::String,
+                        ExprMeta::empty().with_kind(InferredKind::String),
+                    )),
+                    Some(Token::Num) => Ok((
+                        InferredKind::Number,
+                        ExprMeta::empty().with_kind(InferredKind::Number),
+                    )),
+                    Some(Token::Bool(_)) => Ok((
+                        InferredKind::Bool,
+                        ExprMeta::empty().with_kind(InferredKind::Bool),
+                    )),
+                    // `null` is left unconstrained (no `inferred_kind`) rather than `Object`: it
+                    // is coercible with every scalar, and marking it `Object` would flag the
+                    // extremely common `x == null` idiom as a false-positive type conflict.
+                    Some(Token::Null) => Ok((InferredKind::Object, ExprMeta::empty())),
+                    Some(Token::Ident(name)) => self.parse_ident_tail(name),
+                    other => Err(PyValueError::new_err(format!(
+                        "Unexpected token {other:?} in expression at position {}",
+                        self.tokens.get(self.pos - 1).map_or(self.end, |(_, pos)| *pos)
+                    ))),
+                }
+            }
+
+            fn parse_ident_tail(&mut self, name: String) -> PyResult<(InferredKind, ExprMeta)> {
+                if matches!(self.peek(), Some(Token::LParen)) {
+                    self.advance();
+                    let mut meta = ExprMeta::empty();
+                    if !matches!(self.peek(), Some(Token::RParen)) {
+                        loop {
+                            let (_, arg_meta) = self.parse_or()?;
+                            meta = meta.union(arg_meta);
+                            if matches!(self.peek(), Some(Token::Comma)) {
+                                self.advance();
+                                continue;
+                            }
+                            break;
+                        }
+                    }
+                    self.expect(&Token::RParen)?;
+                    let kind = match name.as_str() {
+                        "fromJSON" => InferredKind::Object,
+                        "toJSON" | "format" | "join" | "hashFiles" => InferredKind::String,
+                        "contains" | "startsWith" | "endsWith" | "success" | "failure"
+                        | "cancelled" | "always" => InferredKind::Bool,
+                        _ => InferredKind::Object,
+                    };
+                    let func_flag = match name.as_str() {
+                        "hashFiles" => Funcs::HASH_FILES,
+                        "always" => Funcs::ALWAYS,
+                        "cancelled" => Funcs::CANCELLED,
+                        "success" => Funcs::SUCCESS,
+                        "failure" => Funcs::FAILURE,
+                        "format" => Funcs::FORMAT,
+                        "join" => Funcs::JOIN,
+                        "contains" => Funcs::CONTAINS,
+                        "startsWith" => Funcs::STARTS_WITH,
+                        "endsWith" => Funcs::ENDS_WITH,
+                        "fromJSON" => Funcs::FROM_JSON,
+                        _ => Funcs::NONE,
+                    };
+                    // Only `fromJSON`'s result is genuinely unconstrained (it parses arbitrary
+                    // JSON), so only it is worth marking `Object` for conflict detection; other
+                    // builtins already have a documented scalar return type.
+                    let result_kind = if name == "fromJSON" {
+                        Some(InferredKind::Object)
+                    } else if func_flag != Funcs::NONE {
+                        Some(kind)
+                    } else {
+                        None
+                    };
+                    let meta = match result_kind {
+                        Some(result_kind) => meta.with_kind(result_kind),
+                        None => meta,
+                    };
+                    return Ok((
+                        kind,
+                        meta.union(ExprMeta::with_funcs(func_flag)),
+                    ));
+                }
+                let context_flag = match name.as_str() {
+                    "github" => Contexts::GITHUB,
+                    "env" => Contexts::ENV,
+                    "secrets" => Contexts::SECRETS,
+                    "vars" => Contexts::VARS,
+                    "needs" => Contexts::NEEDS,
+                    "strategy" => Contexts::STRATEGY,
+                    "matrix" => Contexts::MATRIX,
+                    "job" => Contexts::JOB,
+                    "jobs" => Contexts::JOBS,
+                    "runner" => Contexts::RUNNER,
+                    "steps" => Contexts::STEPS,
+                    "inputs" => Contexts::INPUTS,
+                    _ => Contexts::NONE,
+                };
+                let mut meta = ExprMeta::with_contexts(context_flag);
+                loop {
+                    match self.peek() {
+                        Some(Token::Dot) => {
+                            self.advance();
+                            self.advance();
+                        }
+                        Some(Token::LBracket) => {
+                            self.advance();
+                            let (_, index_meta) = self.parse_or()?;
+                            meta = meta.union(index_meta);
+                            self.expect(&Token::RBracket)?;
+                        }
+                        _ => break,
+                    }
+                }
+                Ok((InferredKind::Object, meta))
+            }
+        }
+
+        /// Parse a GitHub Actions expression (with or without the surrounding `${{ }}`) into
+        /// the correctly-kinded `*Expression` object, rebuilding its `ExprMeta` from the
+        /// context roots and function calls found in the text. Malformed input — an unknown
+        /// character, an unterminated string, a missing closing token, or trailing garbage
+        /// after a complete expression — is rejected with an error naming the character
+        /// position at fault, so this can also be used to lint hand-written expressions.
+        #[pyfunction]
+        fn parse_expr(py: Python<'_>, text: &str) -> PyResult<Py<PyAny>> {
+            let inner = strip_delimiters(text).unwrap_or(text.trim());
+            let tokens = tokenize(inner)?;
+            let mut parser = Parser {
+                tokens: &tokens,
+                pos: 0,
+                end: inner.chars().count(),
+            };
+            let (kind, meta) = parser.parse_or()?;
+            if parser.pos != tokens.len() {
+                return Err(PyValueError::new_err(format!(
+                    "Trailing tokens after parsing expression at position {}: {inner}",
+                    parser.current_pos()
+                )));
+            }
+            let text = inner.to_string();
+            Ok(match kind {
+                InferredKind::Bool => Py::new(py, BooleanExpression::new_expr(text, meta))?.into_any(),
+                InferredKind::Number => Py::new(py, NumberExpression::new_expr(text, meta))?.into_any(),
+                InferredKind::String => Py::new(py, StringExpression::new_expr(text, meta))?.into_any(),
+                InferredKind::Object => Py::new(py, ObjectExpression::new_expr(text, meta))?.into_any(),
+            })
+        }
     }
 
-    macro_rules! funcs {
-        ($first:ident) => {
-            Funcs::$first
-        };
-        ($first:ident, $($rest:ident),+ $(,)?) => {
-            Funcs::$first$(.union(Funcs::$rest))+
+    #[pymodule]
+    mod evaluate {
+        use pyo3::{
+            exceptions::{PyRuntimeError, PyValueError},
+            prelude::*,
+            types::{PyBool, PyDict, PyDictMethods, PyFloat, PyInt, PyList, PyListMethods, PyString},
         };
-    }
+        use serde_json::{Map, Number, Value};
+        use yaml_rust2::{Yaml, yaml::Hash};
+
+        #[derive(Clone, Debug)]
+        enum Token {
+            Ident(String),
+            Str(String),
+            Num(f64),
+            Bool(bool),
+            Null,
+            Bang,
+            AndAnd,
+            OrOr,
+            EqEq,
+            Ne,
+            Lt,
+            Le,
+            Gt,
+            Ge,
+            LParen,
+            RParen,
+            LBracket,
+            RBracket,
+            Dot,
+            Comma,
+        }
+
+        fn tokenize(s: &str) -> PyResult<Vec<Token>> {
+            let chars: Vec<char> = s.chars().collect();
+            let mut tokens = Vec::new();
+            let mut i = 0;
+            while i < chars.len() {
+                let c = chars[i];
+                match c {
+                    c if c.is_whitespace() => i += 1,
+                    '(' => {
+                        tokens.push(Token::LParen);
+                        i += 1;
+                    }
+                    ')' => {
+                        tokens.push(Token::RParen);
+                        i += 1;
+                    }
+                    '[' => {
+                        tokens.push(Token::LBracket);
+                        i += 1;
+                    }
+                    ']' => {
+                        tokens.push(Token::RBracket);
+                        i += 1;
+                    }
+                    '.' => {
+                        tokens.push(Token::Dot);
+                        i += 1;
+                    }
+                    ',' => {
+                        tokens.push(Token::Comma);
+                        i += 1;
+                    }
+                    '!' => {
+                        if chars.get(i + 1) == Some(&'=') {
+                            tokens.push(Token::Ne);
+                            i += 2;
+                        } else {
+                            tokens.push(Token::Bang);
+                            i += 1;
+                        }
+                    }
+                    '=' if chars.get(i + 1) == Some(&'=') => {
+                        tokens.push(Token::EqEq);
+                        i += 2;
+                    }
+                    '&' if chars.get(i + 1) == Some(&'&') => {
+                        tokens.push(Token::AndAnd);
+                        i += 2;
+                    }
+                    '|' if chars.get(i + 1) == Some(&'|') => {
+                        tokens.push(Token::OrOr);
+                        i += 2;
+                    }
+                    '<' => {
+                        if chars.get(i + 1) == Some(&'=') {
+                            tokens.push(Token::Le);
+                            i += 2;
+                        } else {
+                            tokens.push(Token::Lt);
+                            i += 1;
+                        }
+                    }
+                    '>' => {
+                        if chars.get(i + 1) == Some(&'=') {
+                            tokens.push(Token::Ge);
+                            i += 2;
+                        } else {
+                            tokens.push(Token::Gt);
+                            i += 1;
+                        }
+                    }
+                    '\'' => {
+                        i += 1;
+                        let mut lit = String::new();
+                        loop {
+                            if i >= chars.len() {
+                                return Err(PyRuntimeError::new_err(format!(
+                                    "Unterminated string literal in expression: {s}"
+                                )));
+                            }
+                            if chars[i] == '\'' {
+                                if chars.get(i + 1) == Some(&'\'') {
+                                    lit.push('\'');
+                                    i += 2;
+                                    continue;
+                                }
+                                i += 1;
+                                break;
+                            }
+                            lit.push(chars[i]);
+                            i += 1;
+                        }
+                        tokens.push(Token::Str(lit));
+                    }
+                    c if c.is_ascii_digit()
+                        || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) =>
+                    {
+                        let start = i;
+                        if c == '-' {
+                            i += 1;
+                        }
+                        while chars
+                            .get(i)
+                            .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+                        {
+                            i += 1;
+                        }
+                        let text: String = chars[start..i].iter().collect();
+                        let value = text.parse::<f64>().map_err(|_| {
+                            PyRuntimeError::new_err(format!("Invalid number literal: {text}"))
+                        })?;
+                        tokens.push(Token::Num(value));
+                    }
+                    c if c.is_ascii_alphabetic() || c == '_' => {
+                        let start = i;
+                        while chars
+                            .get(i)
+                            .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+                        {
+                            i += 1;
+                        }
+                        let word: String = chars[start..i].iter().collect();
+                        tokens.push(match word.as_str() {
+                            "true" => Token::Bool(true),
+                            "false" => Token::Bool(false),
+                            "null" => Token::Null,
+                            _ => Token::Ident(word),
+                        });
+                    }
+                    other => {
+                        return Err(PyRuntimeError::new_err(format!(
+                            "Unexpected character '{other}' in expression: {s}"
+                        )));
+                    }
+                }
+            }
+            Ok(tokens)
+        }
 
-    const ALLOWED_WORKFLOW_RUN_NAME: Allowed =
-        Allowed::new(ctx!(GITHUB, INPUTS, VARS), Funcs::NONE, "run-name");
-    const ALLOWED_WORKFLOW_CONCURRENCY: Allowed =
-        Allowed::new(ctx!(GITHUB, INPUTS, VARS), Funcs::NONE, "concurrency");
-    const ALLOWED_WORKFLOW_ENV: Allowed =
-        Allowed::new(ctx!(GITHUB, SECRETS, INPUTS, VARS), Funcs::NONE, "env");
-    const ALLOWED_WORKFLOW_CALL_INPUT_DEFAULT: Allowed = Allowed::new(
-        ctx!(GITHUB, INPUTS, VARS),
-        Funcs::NONE,
-        "on.workflow_call.inputs.<inputs_id>.default",
-    );
-    const ALLOWED_WORKFLOW_CALL_OUTPUT_VALUE: Allowed = Allowed::new(
-        ctx!(GITHUB, JOBS, VARS, INPUTS),
-        Funcs::NONE,
-        "on.workflow_call.outputs.<output_id>.value",
-    );
+        #[derive(Clone, Debug, PartialEq)]
+        enum CmpOp {
+            Eq,
+            Ne,
+            Lt,
+            Le,
+            Gt,
+            Ge,
+        }
 
-    const ALLOWED_JOB_NAME: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.name",
-    );
-    const ALLOWED_JOB_IF: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, VARS, INPUTS),
-        funcs!(ALWAYS, CANCELLED, SUCCESS, FAILURE),
-        "jobs.<job_id>.if",
-    );
-    const ALLOWED_JOB_RUNS_ON: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.runs-on",
-    );
-    const ALLOWED_JOB_ENV: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, SECRETS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.env",
-    );
-    const ALLOWED_JOB_ENVIRONMENT: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.environment",
-    );
-    const ALLOWED_JOB_ENVIRONMENT_URL: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, STEPS, INPUTS
-        ),
-        Funcs::NONE,
-        "jobs.<job_id>.environment.url",
-    );
-    const ALLOWED_JOB_CONCURRENCY: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, INPUTS, VARS),
-        Funcs::NONE,
-        "jobs.<job_id>.concurrency",
-    );
-    const ALLOWED_JOB_OUTPUTS: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
-        ),
-        Funcs::NONE,
-        "jobs.<job_id>.outputs.<output_id>",
-    );
-    const ALLOWED_JOB_CONTINUE_ON_ERROR: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, VARS, MATRIX, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.continue-on-error",
-    );
-    const ALLOWED_JOB_DEFAULTS_RUN: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, ENV, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.defaults.run",
-    );
-    const ALLOWED_JOB_STRATEGY: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.strategy",
-    );
-    const ALLOWED_JOB_TIMEOUT_MINUTES: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.timeout-minutes",
-    );
-    const ALLOWED_JOB_WITH: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, INPUTS, VARS),
-        Funcs::NONE,
-        "jobs.<job_id>.with.<with_id>",
-    );
-    const ALLOWED_JOB_SECRETS: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, SECRETS, INPUTS, VARS),
-        Funcs::NONE,
-        "jobs.<job_id>.secrets.<secrets_id>",
-    );
+        #[derive(Clone, Debug)]
+        enum Node {
+            Or(Box<Node>, Box<Node>),
+            And(Box<Node>, Box<Node>),
+            Not(Box<Node>),
+            Cmp(CmpOp, Box<Node>, Box<Node>),
+            Lit(Value),
+            /// A context/property access path, e.g. `github.event.issue.number`.
+            Path(Vec<Node>),
+            Index(Box<Node>),
+            Call(String, Vec<Node>),
+        }
 
-    const ALLOWED_JOB_CONTAINER: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.container",
-    );
-    const ALLOWED_JOB_CONTAINER_CREDENTIALS: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, ENV, VARS, SECRETS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.container.credentials",
-    );
-    const ALLOWED_JOB_CONTAINER_ENV: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, INPUTS
-        ),
-        Funcs::NONE,
-        "jobs.<job_id>.container.env.<env_id>",
-    );
-    const ALLOWED_JOB_CONTAINER_IMAGE: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.container.image",
-    );
+        struct Parser<'a> {
+            tokens: &'a [Token],
+            pos: usize,
+        }
 
-    const ALLOWED_JOB_SERVICES: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.services",
-    );
-    const ALLOWED_JOB_SERVICES_CREDENTIALS: Allowed = Allowed::new(
-        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, ENV, VARS, SECRETS, INPUTS),
-        Funcs::NONE,
-        "jobs.<job_id>.services.<service_id>.credentials",
-    );
-    const ALLOWED_JOB_SERVICES_ENV: Allowed = Allowed::new(
-        ctx!(
+        impl Parser<'_> {
+            fn peek(&self) -> Option<&Token> {
+                self.tokens.get(self.pos)
+            }
+
+            fn advance(&mut self) -> Option<Token> {
+                let tok = self.tokens.get(self.pos).cloned();
+                self.pos += 1;
+                tok
+            }
+
+            fn expect(&mut self, matches: impl Fn(&Token) -> bool, what: &str) -> PyResult<()> {
+                match self.advance() {
+                    Some(tok) if matches(&tok) => Ok(()),
+                    other => Err(PyRuntimeError::new_err(format!(
+                        "Expected {what}, found {other:?}"
+                    ))),
+                }
+            }
+
+            fn parse_or(&mut self) -> PyResult<Node> {
+                let mut node = self.parse_and()?;
+                while matches!(self.peek(), Some(Token::OrOr)) {
+                    self.advance();
+                    let rhs = self.parse_and()?;
+                    node = Node::Or(Box::new(node), Box::new(rhs));
+                }
+                Ok(node)
+            }
+
+            fn parse_and(&mut self) -> PyResult<Node> {
+                let mut node = self.parse_not()?;
+                while matches!(self.peek(), Some(Token::AndAnd)) {
+                    self.advance();
+                    let rhs = self.parse_not()?;
+                    node = Node::And(Box::new(node), Box::new(rhs));
+                }
+                Ok(node)
+            }
+
+            fn parse_not(&mut self) -> PyResult<Node> {
+                if matches!(self.peek(), Some(Token::Bang)) {
+                    self.advance();
+                    return Ok(Node::Not(Box::new(self.parse_not()?)));
+                }
+                self.parse_comparison()
+            }
+
+            fn parse_comparison(&mut self) -> PyResult<Node> {
+                let lhs = self.parse_primary()?;
+                let op = match self.peek() {
+                    Some(Token::EqEq) => Some(CmpOp::Eq),
+                    Some(Token::Ne) => Some(CmpOp::Ne),
+                    Some(Token::Lt) => Some(CmpOp::Lt),
+                    Some(Token::Le) => Some(CmpOp::Le),
+                    Some(Token::Gt) => Some(CmpOp::Gt),
+                    Some(Token::Ge) => Some(CmpOp::Ge),
+                    _ => None,
+                };
+                if let Some(op) = op {
+                    self.advance();
+                    let rhs = self.parse_primary()?;
+                    return Ok(Node::Cmp(op, Box::new(lhs), Box::new(rhs)));
+                }
+                Ok(lhs)
+            }
+
+            fn parse_primary(&mut self) -> PyResult<Node> {
+                match self.advance() {
+                    Some(Token::Bang) => Ok(Node::Not(Box::new(self.parse_primary()?))),
+                    Some(Token::LParen) => {
+                        let inner = self.parse_or()?;
+                        self.expect(|t| matches!(t, Token::RParen), "')'")?;
+                        Ok(inner)
+                    }
+                    Some(Token::Str(s)) => Ok(Node::Lit(Value::String(s))),
+                    Some(Token::Num(n)) => Ok(Node::Lit(
+                        Number::from_f64(n).map(Value::Number).unwrap_or(Value::Null),
+                    )),
+                    Some(Token::Bool(b)) => Ok(Node::Lit(Value::Bool(b))),
+                    Some(Token::Null) => Ok(Node::Lit(Value::Null)),
+                    Some(Token::Ident(name)) => self.parse_ident_tail(name),
+                    other => Err(PyRuntimeError::new_err(format!(
+                        "Unexpected token in expression: {other:?}"
+                    ))),
+                }
+            }
+
+            fn parse_ident_tail(&mut self, name: String) -> PyResult<Node> {
+                if matches!(self.peek(), Some(Token::LParen)) {
+                    self.advance();
+                    let mut args = Vec::new();
+                    if !matches!(self.peek(), Some(Token::RParen)) {
+                        loop {
+                            args.push(self.parse_or()?);
+                            if matches!(self.peek(), Some(Token::Comma)) {
+                                self.advance();
+                                continue;
+                            }
+                            break;
+                        }
+                    }
+                    self.expect(|t| matches!(t, Token::RParen), "')'")?;
+                    return Ok(Node::Call(name, args));
+                }
+                let mut segments = vec![Node::Lit(Value::String(name))];
+                loop {
+                    match self.peek() {
+                        Some(Token::Dot) => {
+                            self.advance();
+                            match self.advance() {
+                                Some(Token::Ident(field)) => {
+                                    segments.push(Node::Lit(Value::String(field)))
+                                }
+                                other => {
+                                    return Err(PyRuntimeError::new_err(format!(
+                                        "Expected a property name after '.', found {other:?}"
+                                    )));
+                                }
+                            }
+                        }
+                        Some(Token::LBracket) => {
+                            self.advance();
+                            let index = self.parse_or()?;
+                            self.expect(|t| matches!(t, Token::RBracket), "']'")?;
+                            segments.push(Node::Index(Box::new(index)));
+                        }
+                        _ => break,
+                    }
+                }
+                Ok(Node::Path(segments))
+            }
+        }
+
+        fn parse(text: &str) -> PyResult<Node> {
+            let trimmed = text.trim();
+            let inner = trimmed
+                .strip_prefix("${{")
+                .and_then(|s| s.strip_suffix("}}"))
+                .map(str::trim)
+                .unwrap_or(trimmed);
+            let tokens = tokenize(inner)?;
+            let mut parser = Parser {
+                tokens: &tokens,
+                pos: 0,
+            };
+            let node = parser.parse_or()?;
+            if parser.pos != tokens.len() {
+                return Err(PyRuntimeError::new_err(format!(
+                    "Trailing tokens after parsing expression: {inner}"
+                )));
+            }
+            Ok(node)
+        }
+
+        /// GitHub's falsy set: `null`, `false`, `0`, `""`, and `NaN` (we represent `NaN` as
+        /// `Value::Null` since `serde_json::Number` cannot hold it).
+        fn is_truthy(value: &Value) -> bool {
+            match value {
+                Value::Null => false,
+                Value::Bool(b) => *b,
+                Value::Number(n) => n.as_f64().is_some_and(|f| f != 0.0),
+                Value::String(s) => !s.is_empty(),
+                Value::Array(_) | Value::Object(_) => true,
+            }
+        }
+
+        fn as_number(value: &Value) -> Option<f64> {
+            match value {
+                Value::Null => Some(0.0),
+                Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+                Value::Number(n) => n.as_f64(),
+                Value::String(s) => s.parse::<f64>().ok(),
+                _ => None,
+            }
+        }
+
+        /// GitHub's loose equality: coerce both sides to numbers when both support it, compare
+        /// strings case-insensitively, otherwise fall back to strict equality.
+        fn loose_eq(a: &Value, b: &Value) -> bool {
+            match (a, b) {
+                (Value::String(a), Value::String(b)) => a.eq_ignore_ascii_case(b),
+                (Value::Null, Value::Null) => true,
+                (Value::Array(_) | Value::Object(_), _) | (_, Value::Array(_) | Value::Object(_)) => {
+                    a == b
+                }
+                _ => match (as_number(a), as_number(b)) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => a == b,
+                },
+            }
+        }
+
+        fn loose_cmp(a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
+            match (as_number(a), as_number(b)) {
+                (Some(a), Some(b)) => a.partial_cmp(&b),
+                _ => None,
+            }
+        }
+
+        fn call_builtin(name: &str, args: &[Value]) -> PyResult<Value> {
+            match name {
+                "contains" => {
+                    let (haystack, needle) = (args.first(), args.get(1));
+                    let result = match (haystack, needle) {
+                        (Some(Value::Array(items)), Some(needle)) => {
+                            items.iter().any(|i| loose_eq(i, needle))
+                        }
+                        (Some(Value::String(s)), Some(needle)) => {
+                            s.to_lowercase().contains(&stringify(needle).to_lowercase())
+                        }
+                        _ => false,
+                    };
+                    Ok(Value::Bool(result))
+                }
+                "startsWith" | "endsWith" => {
+                    let s = args.first().and_then(Value::as_str).unwrap_or_default();
+                    let prefix = args.get(1).and_then(Value::as_str).unwrap_or_default();
+                    let result = if name == "startsWith" {
+                        s.to_lowercase().starts_with(&prefix.to_lowercase())
+                    } else {
+                        s.to_lowercase().ends_with(&prefix.to_lowercase())
+                    };
+                    Ok(Value::Bool(result))
+                }
+                "join" => {
+                    let sep = args.get(1).and_then(Value::as_str).unwrap_or(",");
+                    let items: Vec<String> = match args.first() {
+                        Some(Value::Array(items)) => {
+                            items.iter().map(|v| stringify(v)).collect()
+                        }
+                        Some(other) => vec![stringify(other)],
+                        None => Vec::new(),
+                    };
+                    Ok(Value::String(items.join(sep)))
+                }
+                "format" => {
+                    let template = args.first().and_then(Value::as_str).unwrap_or_default();
+                    let rest = &args[1.min(args.len())..];
+                    Ok(Value::String(format_template(template, rest)))
+                }
+                "toJSON" => Ok(Value::String(
+                    serde_json::to_string(args.first().unwrap_or(&Value::Null))
+                        .unwrap_or_default(),
+                )),
+                "fromJSON" => {
+                    let text = args.first().and_then(Value::as_str).unwrap_or_default();
+                    serde_json::from_str(text)
+                        .map_err(|e| PyRuntimeError::new_err(format!("fromJSON: {e}")))
+                }
+                "always" => Ok(Value::Bool(true)),
+                "success" | "cancelled" | "failure" => Ok(Value::Bool(false)),
+                "hashFiles" => Ok(Value::String(String::new())),
+                other => Err(PyRuntimeError::new_err(format!(
+                    "Unknown function '{other}' in expression evaluation"
+                ))),
+            }
+        }
+
+        fn stringify(value: &Value) -> String {
+            match value {
+                Value::String(s) => s.clone(),
+                Value::Null => String::new(),
+                other => other.to_string(),
+            }
+        }
+
+        fn format_template(template: &str, args: &[Value]) -> String {
+            let mut out = String::new();
+            let mut chars = template.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '{' if chars.peek() == Some(&'{') => {
+                        chars.next();
+                        out.push('{');
+                    }
+                    '}' if chars.peek() == Some(&'}') => {
+                        chars.next();
+                        out.push('}');
+                    }
+                    '{' => {
+                        let mut digits = String::new();
+                        for d in chars.by_ref() {
+                            if d == '}' {
+                                break;
+                            }
+                            digits.push(d);
+                        }
+                        if let Ok(idx) = digits.parse::<usize>() {
+                            out.push_str(&args.get(idx).map(stringify).unwrap_or_default());
+                        }
+                    }
+                    other => out.push(other),
+                }
+            }
+            out
+        }
+
+        /// Reports a root context (`github`, `env`, …) that the expression referenced but that
+        /// was missing from the supplied context map entirely, as opposed to a known root with a
+        /// missing leaf (which resolves to `null` per GitHub's own semantics).
+        fn eval(node: &Node, context: &Map<String, Value>, missing_roots: &mut Vec<String>) -> PyResult<Value> {
+            Ok(match node {
+                Node::Lit(v) => v.clone(),
+                Node::Or(l, r) => {
+                    let lv = eval(l, context, missing_roots)?;
+                    if is_truthy(&lv) { lv } else { eval(r, context, missing_roots)? }
+                }
+                Node::And(l, r) => {
+                    let lv = eval(l, context, missing_roots)?;
+                    if !is_truthy(&lv) { lv } else { eval(r, context, missing_roots)? }
+                }
+                Node::Not(x) => Value::Bool(!is_truthy(&eval(x, context, missing_roots)?)),
+                Node::Cmp(op, l, r) => {
+                    let lv = eval(l, context, missing_roots)?;
+                    let rv = eval(r, context, missing_roots)?;
+                    Value::Bool(match op {
+                        CmpOp::Eq => loose_eq(&lv, &rv),
+                        CmpOp::Ne => !loose_eq(&lv, &rv),
+                        CmpOp::Lt => loose_cmp(&lv, &rv).is_some_and(std::cmp::Ordering::is_lt),
+                        CmpOp::Le => loose_cmp(&lv, &rv).is_some_and(std::cmp::Ordering::is_le),
+                        CmpOp::Gt => loose_cmp(&lv, &rv).is_some_and(std::cmp::Ordering::is_gt),
+                        CmpOp::Ge => loose_cmp(&lv, &rv).is_some_and(std::cmp::Ordering::is_ge),
+                    })
+                }
+                Node::Index(_) => return Err(PyRuntimeError::new_err("Unexpected bare index node")),
+                Node::Call(name, args) => {
+                    let mut values = Vec::with_capacity(args.len());
+                    for arg in args {
+                        values.push(eval(arg, context, missing_roots)?);
+                    }
+                    call_builtin(name, &values)?
+                }
+                Node::Path(segments) => {
+                    let mut iter = segments.iter();
+                    let root = match iter.next() {
+                        Some(Node::Lit(Value::String(s))) => s.clone(),
+                        _ => return Err(PyRuntimeError::new_err("Malformed property path")),
+                    };
+                    if !context.contains_key(&root) {
+                        missing_roots.push(root.clone());
+                    }
+                    let mut current = context.get(&root).cloned().unwrap_or(Value::Null);
+                    for segment in iter {
+                        let key = match segment {
+                            Node::Lit(Value::String(s)) => s.clone(),
+                            Node::Index(inner) => {
+                                stringify(&eval(inner, context, missing_roots)?)
+                            }
+                            _ => return Err(PyRuntimeError::new_err("Malformed property path")),
+                        };
+                        current = match &current {
+                            Value::Object(map) => map.get(&key).cloned().unwrap_or(Value::Null),
+                            Value::Array(items) => key
+                                .parse::<usize>()
+                                .ok()
+                                .and_then(|i| items.get(i).cloned())
+                                .unwrap_or(Value::Null),
+                            _ => Value::Null,
+                        };
+                    }
+                    current
+                }
+            })
+        }
+
+        fn py_to_json(value: &Bound<'_, PyAny>) -> PyResult<Value> {
+            if value.is_none() {
+                Ok(Value::Null)
+            } else if value.is_instance_of::<PyBool>() {
+                Ok(Value::Bool(value.extract::<bool>()?))
+            } else if value.is_instance_of::<PyInt>() {
+                Ok(Value::Number(value.extract::<i64>()?.into()))
+            } else if value.is_instance_of::<PyFloat>() {
+                Ok(Number::from_f64(value.extract::<f64>()?)
+                    .map(Value::Number)
+                    .unwrap_or(Value::Null))
+            } else if value.is_instance_of::<PyString>() {
+                Ok(Value::String(value.extract::<String>()?))
+            } else if let Ok(list) = value.cast::<PyList>() {
+                let mut out = Vec::with_capacity(list.len());
+                for item in list.iter() {
+                    out.push(py_to_json(&item)?);
+                }
+                Ok(Value::Array(out))
+            } else if let Ok(dict) = value.cast::<PyDict>() {
+                let mut out = Map::new();
+                for (k, v) in dict.iter() {
+                    out.insert(k.extract::<String>()?, py_to_json(&v)?);
+                }
+                Ok(Value::Object(out))
+            } else {
+                Err(PyValueError::new_err(
+                    "Unsupported value in evaluation context: expected None/bool/int/float/str/list/dict",
+                ))
+            }
+        }
+
+        pub(super) fn json_to_py<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+            match value {
+                Value::Null => Ok(py.None().into_bound(py)),
+                Value::Bool(b) => Ok(b.into_pyobject(py)?.to_owned().into_any()),
+                Value::Number(n) => {
+                    if let Some(i) = n.as_i64() {
+                        Ok(i.into_pyobject(py)?.into_any())
+                    } else {
+                        Ok(n.as_f64().unwrap_or_default().into_pyobject(py)?.into_any())
+                    }
+                }
+                Value::String(s) => Ok(s.into_pyobject(py)?.into_any()),
+                Value::Array(items) => {
+                    let list = PyList::empty(py);
+                    for item in items {
+                        list.append(json_to_py(py, item)?)?;
+                    }
+                    Ok(list.into_any())
+                }
+                Value::Object(map) => {
+                    let dict = PyDict::new(py);
+                    for (k, v) in map {
+                        dict.set_item(k, json_to_py(py, v)?)?;
+                    }
+                    Ok(dict.into_any())
+                }
+            }
+        }
+
+        /// Like `evaluate`, but returns the raw `serde_json::Value` instead of converting it back
+        /// to a Python object, for callers (e.g. `render_with_context`) that want to embed the
+        /// result into a `Yaml` tree rather than hand it back to Python.
+        pub(super) fn evaluate_value(text: &str, context: &Bound<'_, PyDict>) -> PyResult<Value> {
+            let node = parse(text)?;
+            let mut json_context = Map::new();
+            for (k, v) in context.iter() {
+                json_context.insert(k.extract::<String>()?, py_to_json(&v)?);
+            }
+            let mut missing_roots = Vec::new();
+            let result = eval(&node, &json_context, &mut missing_roots)?;
+            if !missing_roots.is_empty() {
+                missing_roots.sort();
+                missing_roots.dedup();
+                return Err(PyRuntimeError::new_err(format!(
+                    "Expression references context root(s) not present in the supplied \
+                     environment: {}",
+                    missing_roots.join(", ")
+                )));
+            }
+            Ok(result)
+        }
+
+        /// Convert an evaluated expression result into the `Yaml` node it should render as, so a
+        /// resolved boolean/number/object keeps its native YAML type instead of being stringified.
+        pub(super) fn value_to_yaml(value: &Value) -> Yaml {
+            match value {
+                Value::Null => Yaml::Null,
+                Value::Bool(b) => Yaml::Boolean(*b),
+                Value::Number(n) => n
+                    .as_i64()
+                    .map(Yaml::Integer)
+                    .unwrap_or_else(|| Yaml::Real(n.to_string())),
+                Value::String(s) => Yaml::String(s.clone()),
+                Value::Array(items) => Yaml::Array(items.iter().map(value_to_yaml).collect()),
+                Value::Object(map) => {
+                    let mut hash = Hash::new();
+                    for (k, v) in map {
+                        hash.insert(Yaml::String(k.clone()), value_to_yaml(v));
+                    }
+                    Yaml::Hash(hash)
+                }
+            }
+        }
+
+        /// Evaluate a GitHub Actions expression (with or without the surrounding `${{ }}`)
+        /// against a concrete `context` dict keyed by root (`github`, `env`, `vars`, `runner`,
+        /// `needs`, `steps`, `matrix`, `inputs`, `secrets`, …) holding JSON-like nested values,
+        /// implementing GitHub's own evaluation semantics: loose (coercing) equality and
+        /// ordering, case-insensitive string comparison, the falsy set `null`/`false`/`0`/`''`,
+        /// `&&`/`||` returning the last-evaluated operand rather than a strict boolean, and
+        /// missing property paths resolving to `null`. A root named in the expression but absent
+        /// from `context` entirely is still evaluated as `null`, but is also reported back so
+        /// callers can tell a genuinely partial dry-run environment from a real `null` value.
+        #[pyfunction]
+        fn evaluate(py: Python<'_>, text: &str, context: &Bound<'_, PyDict>) -> PyResult<Py<PyAny>> {
+            Ok(json_to_py(py, &evaluate_value(text, context)?)?.unbind())
+        }
+    }
+
+    #[pymodule]
+    mod patch {
+        use std::path::PathBuf;
+
+        use pyo3::{exceptions::PyValueError, prelude::*};
+        use yaml_rust2::{Yaml, YamlLoader, yaml::Hash};
+
+        use crate::Yamlable;
+
+        /// A value that, given for a key during a merge, deletes that key from the parent,
+        /// e.g. `{"foo": "!yamloom-delete"}` removes `foo` from the base document.
+        const DELETE_MARKER: &str = "!yamloom-delete";
+
+        /// The key used to splice another YAML file's mapping into this one before merging.
+        const INCLUDE_KEY: &str = "_include";
+
+        fn load_one(text: &str) -> PyResult<Yaml> {
+            let mut docs =
+                YamlLoader::load_from_str(text).map_err(|e| PyValueError::new_err(e.to_string()))?;
+            match docs.len() {
+                1 => Ok(docs.remove(0)),
+                0 => Ok(Yaml::Hash(Hash::new())),
+                _ => Err(PyValueError::new_err(
+                    "Expected a single YAML document, found multiple",
+                )),
+            }
+        }
+
+        fn resolve_includes(yaml: Yaml, base_dir: &std::path::Path) -> PyResult<Yaml> {
+            match yaml {
+                Yaml::Hash(hash) => {
+                    let mut merged = Hash::new();
+                    for (k, v) in hash {
+                        if let Yaml::String(key) = &k
+                            && key == INCLUDE_KEY
+                            && let Yaml::String(path) = &v
+                        {
+                            let included_text = std::fs::read_to_string(base_dir.join(path))?;
+                            let included = resolve_includes(load_one(&included_text)?, base_dir)?;
+                            if let Yaml::Hash(included_hash) = included {
+                                for (ik, iv) in included_hash {
+                                    merged.insert(ik, iv);
+                                }
+                            }
+                            continue;
+                        }
+                        merged.insert(k, resolve_includes(v, base_dir)?);
+                    }
+                    Ok(Yaml::Hash(merged))
+                }
+                other => Ok(other),
+            }
+        }
+
+        fn is_delete_marker(value: &Yaml) -> bool {
+            matches!(value, Yaml::String(s) if s == DELETE_MARKER)
+        }
+
+        /// Recursively deep-merge `child` onto `parent` in place, following svdtools-style
+        /// `update_dict` semantics: missing keys are inserted, matching hashes recurse, matching
+        /// arrays are appended to (skipping exact-equal duplicates), and anything else is
+        /// overwritten by the child's value.
+        fn update_dict(parent: &mut Hash, child: Hash, duplicates_ignored: &mut Vec<String>) {
+            for (key, child_value) in child {
+                if is_delete_marker(&child_value) {
+                    parent.remove(&key);
+                    continue;
+                }
+                match parent.remove(&key) {
+                    Some(Yaml::Hash(mut parent_hash)) if matches!(child_value, Yaml::Hash(_)) => {
+                        if let Yaml::Hash(child_hash) = child_value {
+                            update_dict(&mut parent_hash, child_hash, duplicates_ignored);
+                        }
+                        parent.insert(key, Yaml::Hash(parent_hash));
+                    }
+                    Some(Yaml::Array(mut parent_array))
+                        if matches!(child_value, Yaml::Array(_)) =>
+                    {
+                        if let Yaml::Array(child_array) = child_value {
+                            for item in child_array {
+                                if parent_array.contains(&item) {
+                                    duplicates_ignored
+                                        .push(format!("{key:?}: duplicate entry, ignored"));
+                                    continue;
+                                }
+                                parent_array.push(item);
+                            }
+                        }
+                        parent.insert(key, Yaml::Array(parent_array));
+                    }
+                    _ => {
+                        parent.insert(key, child_value);
+                    }
+                }
+            }
+        }
+
+        /// Recursively deep-merge `overlay` onto `base`, resolving any `_include` directives in
+        /// either document first. Returns the merged document as a YAML string, ready to be
+        /// written alongside (or in place of) a hand-maintained base workflow.
+        #[pyfunction]
+        #[pyo3(signature = (base, overlay, *, base_dir = None))]
+        fn merge_yaml(base: &str, overlay: &str, base_dir: Option<PathBuf>) -> PyResult<String> {
+            let base_dir = base_dir.unwrap_or_else(|| PathBuf::from("."));
+            let base_yaml = resolve_includes(load_one(base)?, &base_dir)?;
+            let overlay_yaml = resolve_includes(load_one(overlay)?, &base_dir)?;
+            let mut base_hash = match base_yaml {
+                Yaml::Hash(hash) => hash,
+                _ => return Err(PyValueError::new_err("base document must be a mapping")),
+            };
+            let overlay_hash = match overlay_yaml {
+                Yaml::Hash(hash) => hash,
+                _ => return Err(PyValueError::new_err("overlay document must be a mapping")),
+            };
+            let mut duplicates_ignored = Vec::new();
+            update_dict(&mut base_hash, overlay_hash, &mut duplicates_ignored);
+            for note in duplicates_ignored {
+                eprintln!("yamloom patch: {note}");
+            }
+            Yaml::Hash(base_hash).as_yaml_string()
+        }
+
+        /// The sentinel value that, when set as a key's value in an overlay document, deletes that
+        /// key from the base document during `merge_yaml`.
+        #[pyfunction]
+        fn delete_marker() -> &'static str {
+            DELETE_MARKER
+        }
+    }
+
+    #[pymodule]
+    mod live {
+        use std::collections::{HashMap, HashSet};
+
+        use pyo3::{exceptions::PyRuntimeError, prelude::*};
+
+        use super::{Job, PyMap, Step, StepAction, Workflow, is_commit_sha};
+
+        /// Scan expression text for `prefix.NAME` accesses and collect the referenced names,
+        /// mirroring the key a `format_access("prefix", "NAME")` call would have produced.
+        fn extract_keys(text: &str, prefix: &str) -> Vec<String> {
+            let pat = format!("{prefix}.");
+            let mut out = Vec::new();
+            let mut search_from = 0;
+            while let Some(rel) = text[search_from..].find(pat.as_str()) {
+                let start = search_from + rel + pat.len();
+                let end = text[start..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .map(|i| start + i)
+                    .unwrap_or(text.len());
+                if end > start {
+                    out.push(text[start..end].to_string());
+                }
+                search_from = start.max(search_from + rel + 1);
+            }
+            out
+        }
+
+        fn fetch_names(token: &str, owner: &str, repo: &str, endpoint: &str, key: &str) -> PyResult<HashSet<String>> {
+            let url = format!("https://api.github.com/repos/{owner}/{repo}/{endpoint}?per_page=100");
+            let response: serde_json::Value = ureq::get(&url)
+                .set("Authorization", &format!("Bearer {token}"))
+                .set("Accept", "application/vnd.github+json")
+                .set("User-Agent", "yamloom")
+                .call()
+                .map_err(|e| {
+                    PyRuntimeError::new_err(format!("GitHub API request to {endpoint} failed: {e}"))
+                })?
+                .into_json()
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            Ok(response
+                .get(key)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|item| item.get("name")?.as_str().map(str::to_string))
+                .collect())
+        }
+
+        /// Resolve a human-friendly `ref` (a tag like `v4` or a branch like `main`) on `action`
+        /// (an `{owner}/{repo}` string, as passed to `action()`/`make_action`) to the full
+        /// 40-character commit SHA it currently points to, via the GitHub REST API. Pass the
+        /// returned SHA as `action()`'s `ref` and the original `ref` as its `pin_comment` to
+        /// generate a SHA-pinned `uses` line that still shows the human-friendly tag.
+        #[pyfunction]
+        fn resolve_action_ref(token: &str, action: &str, r#ref: &str) -> PyResult<String> {
+            let mut parts = action.splitn(3, '/');
+            let (Some(owner), Some(repo)) = (parts.next(), parts.next()) else {
+                return Err(PyRuntimeError::new_err(format!(
+                    "Invalid action '{action}', expected a string of the form '{{owner}}/{{repo}}'"
+                )));
+            };
+            let url = format!("https://api.github.com/repos/{owner}/{repo}/commits/{ref}");
+            let response: serde_json::Value = ureq::get(&url)
+                .set("Authorization", &format!("Bearer {token}"))
+                .set("Accept", "application/vnd.github+json")
+                .set("User-Agent", "yamloom")
+                .call()
+                .map_err(|e| {
+                    PyRuntimeError::new_err(format!(
+                        "GitHub API request to resolve '{action}@{ref}' failed: {e}"
+                    ))
+                })?
+                .into_json()
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            response
+                .get("sha")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    PyRuntimeError::new_err(format!(
+                        "GitHub API response for '{action}@{ref}' did not include a commit SHA"
+                    ))
+                })
+        }
+
+        /// Split a step's ``uses:`` reference into its `owner/repo[/path]` prefix and human-friendly
+        /// `ref`, or `None` if the reference should be left untouched: a local reusable workflow
+        /// (`./...`), a Docker action (`docker://...`), or one already pinned to a commit SHA.
+        fn splittable_action_ref(uses: &str) -> Option<(&str, &str)> {
+            if uses.starts_with("./") || uses.starts_with("docker://") {
+                return None;
+            }
+            let (action, r#ref) = uses.rsplit_once('@')?;
+            if is_commit_sha(r#ref) {
+                return None;
+            }
+            Some((action, r#ref))
+        }
+
+        /// Rewrite a single step's `uses:` in place via `resolve`, preserving the original
+        /// human-friendly ref as the step's `pin_comment`. Leaves the step untouched if its
+        /// `uses` is a local path, a Docker action, or already SHA-pinned; steps with `run`
+        /// instead of `uses` are always left untouched.
+        fn pin_step<F>(step: Step, resolve: &mut F) -> PyResult<Step>
+        where
+            F: FnMut(&str, &str) -> PyResult<String>,
+        {
+            let Step {
+                name,
+                step_action,
+                options,
+                recommended_permissions,
+            } = step;
+            let step_action = match step_action {
+                StepAction::Action {
+                    uses,
+                    pin_comment,
+                    with,
+                } => {
+                    match splittable_action_ref(&uses) {
+                        Some((action, r#ref)) => {
+                            let sha = resolve(action, r#ref)?;
+                            StepAction::Action {
+                                uses: format!("{action}@{sha}"),
+                                pin_comment: Some(r#ref.to_string()),
+                                with,
+                            }
+                        }
+                        None => StepAction::Action {
+                            uses,
+                            pin_comment,
+                            with,
+                        },
+                    }
+                }
+                other => other,
+            };
+            Ok(Step {
+                name,
+                step_action,
+                options,
+                recommended_permissions,
+            })
+        }
+
+        fn pin_job<F>(job: &Job, resolve: &mut F) -> PyResult<Job>
+        where
+            F: FnMut(&str, &str) -> PyResult<String>,
+        {
+            let mut job = job.clone();
+            if let Some(steps) = job.steps.take() {
+                job.steps = Some(
+                    steps
+                        .into_iter()
+                        .map(|s| pin_step(s, resolve))
+                        .collect::<PyResult<Vec<_>>>()?,
+                );
+            }
+            Ok(job)
+        }
+
+        fn pin_workflow<F>(workflow: &Workflow, resolve: &mut F) -> PyResult<Workflow>
+        where
+            F: FnMut(&str, &str) -> PyResult<String>,
+        {
+            let jobs = workflow
+                .jobs
+                .iter()
+                .map(|(id, job)| Ok((id.clone(), pin_job(job, resolve)?)))
+                .collect::<PyResult<Vec<_>>>()?
+                .into_iter()
+                .collect::<PyMap<String, Job>>();
+            Ok(Workflow {
+                name: workflow.name.clone(),
+                run_name: workflow.run_name.clone(),
+                on: workflow.on.clone(),
+                permissions: workflow.permissions.clone(),
+                env: workflow.env.clone(),
+                defaults: workflow.defaults.clone(),
+                concurrency: workflow.concurrency.clone(),
+                jobs,
+            })
+        }
+
+        fn lookup_lock(lock: &HashMap<String, String>, action: &str, r#ref: &str) -> PyResult<String> {
+            let key = format!("{action}@{ref}");
+            lock.get(&key).cloned().ok_or_else(|| {
+                PyRuntimeError::new_err(format!(
+                    "No pinned SHA for '{key}' in the provided lock file; add an entry mapping \
+                     '{key}' to its commit SHA"
+                ))
+            })
+        }
+
+        /// Rewrite every step's `uses:` in `job` to its immutable commit SHA, resolved live via the
+        /// GitHub REST API, preserving the original tag/branch as a trailing `pin_comment` (the
+        /// conventional `owner/repo@<sha> # v4` pattern). Local reusable workflows (`./...`),
+        /// Docker actions, and references already pinned to a SHA are left untouched.
+        #[pyfunction]
+        fn pin_job_live(token: &str, job: &Job) -> PyResult<Job> {
+            pin_job(job, &mut |action, r#ref| {
+                resolve_action_ref(token, action, r#ref)
+            })
+        }
+
+        /// Like `pin_job_live`, but resolves every `owner/repo@ref` offline against `lock`, a
+        /// mapping from that exact string to its commit SHA. Raises a `RuntimeError` naming the
+        /// first reference missing from `lock` instead of making a network request, so CI can
+        /// verify pins reproducibly without live GitHub access.
+        #[pyfunction]
+        fn pin_job_offline(job: &Job, lock: HashMap<String, String>) -> PyResult<Job> {
+            pin_job(job, &mut |action, r#ref| lookup_lock(&lock, action, r#ref))
+        }
+
+        /// Like `pin_job_live`, applied to every job in `workflow`.
+        #[pyfunction]
+        fn pin_workflow_live(token: &str, workflow: &Workflow) -> PyResult<Workflow> {
+            pin_workflow(workflow, &mut |action, r#ref| {
+                resolve_action_ref(token, action, r#ref)
+            })
+        }
+
+        /// Like `pin_job_offline`, applied to every job in `workflow`.
+        #[pyfunction]
+        fn pin_workflow_offline(workflow: &Workflow, lock: HashMap<String, String>) -> PyResult<Workflow> {
+            pin_workflow(workflow, &mut |action, r#ref| lookup_lock(&lock, action, r#ref))
+        }
+
+        /// The set of secret, Actions-variable, and environment names declared on a live GitHub
+        /// repository, fetched once via the REST API (in the style of a repository service like
+        /// `hubcaps`) and then checked against the names an expression actually references, so a
+        /// typo'd `secrets.X`/`vars.Y` is caught in CI instead of silently resolving to empty.
+        #[pyclass]
+        pub struct LiveRepository {
+            secrets: HashSet<String>,
+            vars: HashSet<String>,
+            environments: HashSet<String>,
+        }
+
+        #[pymethods]
+        impl LiveRepository {
+            #[new]
+            fn new(token: &str, owner: &str, repo: &str) -> PyResult<Self> {
+                Ok(Self {
+                    secrets: fetch_names(token, owner, repo, "actions/secrets", "secrets")?,
+                    vars: fetch_names(token, owner, repo, "actions/variables", "variables")?,
+                    environments: fetch_names(token, owner, repo, "environments", "environments")?,
+                })
+            }
+
+            /// Check every `secrets.X`/`vars.Y` access found in `expr`'s text against the names
+            /// fetched from the live repository, raising a `RuntimeError` naming every unknown
+            /// reference at once.
+            fn validate(&self, expr: &str) -> PyResult<()> {
+                let mut unknown = Vec::new();
+                for name in extract_keys(expr, "secrets") {
+                    if !self.secrets.contains(&name) {
+                        unknown.push(format!("secrets.{name}"));
+                    }
+                }
+                for name in extract_keys(expr, "vars") {
+                    if !self.vars.contains(&name) {
+                        unknown.push(format!("vars.{name}"));
+                    }
+                }
+                if unknown.is_empty() {
+                    Ok(())
+                } else {
+                    Err(PyRuntimeError::new_err(format!(
+                        "Expression references unknown name(s) on the live repository: {}",
+                        unknown.join(", ")
+                    )))
+                }
+            }
+
+            /// Check that `name` is a configured environment on this repository.
+            fn validate_environment(&self, name: &str) -> PyResult<()> {
+                if self.environments.contains(name) {
+                    Ok(())
+                } else {
+                    Err(PyRuntimeError::new_err(format!(
+                        "Unknown environment '{name}' on this repository"
+                    )))
+                }
+            }
+        }
+    }
+
+    type StringLike = Either<StringExpression, String>;
+    type BoolLike = Either<BooleanExpression, bool>;
+    type IntLike = Either<NumberExpression, i64>;
+
+    macro_rules! ctx {
+        ($first:ident) => {
+            Contexts::$first
+        };
+        ($first:ident, $($rest:ident),+ $(,)?) => {
+            Contexts::$first$(.union(Contexts::$rest))+
+        };
+    }
+
+    macro_rules! funcs {
+        ($first:ident) => {
+            Funcs::$first
+        };
+        ($first:ident, $($rest:ident),+ $(,)?) => {
+            Funcs::$first$(.union(Funcs::$rest))+
+        };
+    }
+
+    const ALLOWED_WORKFLOW_RUN_NAME: Allowed =
+        Allowed::new(ctx!(GITHUB, INPUTS, VARS), Funcs::NONE, "run-name");
+    const ALLOWED_WORKFLOW_CONCURRENCY: Allowed =
+        Allowed::new(ctx!(GITHUB, INPUTS, VARS), Funcs::NONE, "concurrency");
+    const ALLOWED_WORKFLOW_ENV: Allowed =
+        Allowed::new(ctx!(GITHUB, SECRETS, INPUTS, VARS), Funcs::NONE, "env");
+    const ALLOWED_WORKFLOW_CALL_INPUT_DEFAULT: Allowed = Allowed::new(
+        ctx!(GITHUB, INPUTS, VARS),
+        Funcs::NONE,
+        "on.workflow_call.inputs.<inputs_id>.default",
+    );
+    const ALLOWED_WORKFLOW_CALL_OUTPUT_VALUE: Allowed = Allowed::new(
+        ctx!(GITHUB, JOBS, VARS, INPUTS),
+        Funcs::NONE,
+        "on.workflow_call.outputs.<output_id>.value",
+    );
+
+    const ALLOWED_JOB_NAME: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.name",
+    );
+    const ALLOWED_JOB_IF: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, VARS, INPUTS),
+        funcs!(ALWAYS, CANCELLED, SUCCESS, FAILURE),
+        "jobs.<job_id>.if",
+    );
+    const ALLOWED_JOB_RUNS_ON: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.runs-on",
+    );
+    const ALLOWED_JOB_ENV: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, SECRETS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.env",
+    );
+    const ALLOWED_JOB_ENVIRONMENT: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.environment",
+    );
+    const ALLOWED_JOB_ENVIRONMENT_URL: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, STEPS, INPUTS
+        ),
+        Funcs::NONE,
+        "jobs.<job_id>.environment.url",
+    );
+    const ALLOWED_JOB_CONCURRENCY: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, INPUTS, VARS),
+        Funcs::NONE,
+        "jobs.<job_id>.concurrency",
+    );
+    const ALLOWED_JOB_OUTPUTS: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
+        ),
+        Funcs::NONE,
+        "jobs.<job_id>.outputs.<output_id>",
+    );
+    const ALLOWED_JOB_CONTINUE_ON_ERROR: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, VARS, MATRIX, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.continue-on-error",
+    );
+    const ALLOWED_JOB_DEFAULTS_RUN: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, ENV, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.defaults.run",
+    );
+    const ALLOWED_JOB_STRATEGY: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.strategy",
+    );
+    const ALLOWED_JOB_TIMEOUT_MINUTES: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.timeout-minutes",
+    );
+    const ALLOWED_JOB_WITH: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, INPUTS, VARS),
+        Funcs::NONE,
+        "jobs.<job_id>.with.<with_id>",
+    );
+    const ALLOWED_JOB_SECRETS: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, SECRETS, INPUTS, VARS),
+        Funcs::NONE,
+        "jobs.<job_id>.secrets.<secrets_id>",
+    );
+
+    const ALLOWED_JOB_CONTAINER: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.container",
+    );
+    const ALLOWED_JOB_CONTAINER_CREDENTIALS: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, ENV, VARS, SECRETS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.container.credentials",
+    );
+    const ALLOWED_JOB_CONTAINER_ENV: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, INPUTS
+        ),
+        Funcs::NONE,
+        "jobs.<job_id>.container.env.<env_id>",
+    );
+    const ALLOWED_JOB_CONTAINER_IMAGE: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.container.image",
+    );
+
+    const ALLOWED_JOB_SERVICES: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, VARS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.services",
+    );
+    const ALLOWED_JOB_SERVICES_CREDENTIALS: Allowed = Allowed::new(
+        ctx!(GITHUB, NEEDS, STRATEGY, MATRIX, ENV, VARS, SECRETS, INPUTS),
+        Funcs::NONE,
+        "jobs.<job_id>.services.<service_id>.credentials",
+    );
+    const ALLOWED_JOB_SERVICES_ENV: Allowed = Allowed::new(
+        ctx!(
             GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, INPUTS
         ),
         Funcs::NONE,
         "jobs.<job_id>.services.<service_id>.env.<env_id>",
     );
 
-    const ALLOWED_STEP_IF: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, STEPS, INPUTS
-        ),
-        funcs!(ALWAYS, CANCELLED, SUCCESS, FAILURE, HASH_FILES),
-        "jobs.<job_id>.steps.if",
-    );
-    const ALLOWED_STEP_NAME: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
-        ),
-        Funcs::HASH_FILES,
-        "jobs.<job_id>.steps.name",
-    );
-    const ALLOWED_STEP_RUN: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
-        ),
-        Funcs::HASH_FILES,
-        "jobs.<job_id>.steps.run",
-    );
-    const ALLOWED_STEP_ENV: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
-        ),
-        Funcs::HASH_FILES,
-        "jobs.<job_id>.steps.env",
-    );
-    const ALLOWED_STEP_WITH: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
-        ),
-        Funcs::HASH_FILES,
-        "jobs.<job_id>.steps.with",
-    );
-    const ALLOWED_STEP_WORKING_DIRECTORY: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
-        ),
-        Funcs::HASH_FILES,
-        "jobs.<job_id>.steps.working-directory",
-    );
-    const ALLOWED_STEP_CONTINUE_ON_ERROR: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
-        ),
-        Funcs::HASH_FILES,
-        "jobs.<job_id>.steps.continue-on-error",
-    );
-    const ALLOWED_STEP_TIMEOUT_MINUTES: Allowed = Allowed::new(
-        ctx!(
-            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
-        ),
-        Funcs::HASH_FILES,
-        "jobs.<job_id>.steps.timeout-minutes",
-    );
+    const ALLOWED_STEP_IF: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, STEPS, INPUTS
+        ),
+        funcs!(ALWAYS, CANCELLED, SUCCESS, FAILURE, HASH_FILES),
+        "jobs.<job_id>.steps.if",
+    );
+    const ALLOWED_STEP_NAME: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
+        ),
+        Funcs::HASH_FILES,
+        "jobs.<job_id>.steps.name",
+    );
+    const ALLOWED_STEP_RUN: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
+        ),
+        Funcs::HASH_FILES,
+        "jobs.<job_id>.steps.run",
+    );
+    const ALLOWED_STEP_ENV: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
+        ),
+        Funcs::HASH_FILES,
+        "jobs.<job_id>.steps.env",
+    );
+    const ALLOWED_STEP_WITH: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
+        ),
+        Funcs::HASH_FILES,
+        "jobs.<job_id>.steps.with",
+    );
+    const ALLOWED_STEP_WORKING_DIRECTORY: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
+        ),
+        Funcs::HASH_FILES,
+        "jobs.<job_id>.steps.working-directory",
+    );
+    const ALLOWED_STEP_CONTINUE_ON_ERROR: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
+        ),
+        Funcs::HASH_FILES,
+        "jobs.<job_id>.steps.continue-on-error",
+    );
+    const ALLOWED_STEP_TIMEOUT_MINUTES: Allowed = Allowed::new(
+        ctx!(
+            GITHUB, NEEDS, STRATEGY, MATRIX, JOB, RUNNER, ENV, VARS, SECRETS, STEPS, INPUTS
+        ),
+        Funcs::HASH_FILES,
+        "jobs.<job_id>.steps.timeout-minutes",
+    );
+
+    /// The canonical workflow positions `validate_placement` knows how to check, each mapped to
+    /// the same `Allowed` mask the corresponding builder (`Job`, `Step`, …) already enforces
+    /// internally. Exposed so a caller holding an already-built expression — e.g. one recovered
+    /// from round-tripped YAML, or assembled outside of a builder — can check in advance whether
+    /// GitHub would accept it at a given position, instead of finding out when the run starts.
+    enum Placement {
+        WorkflowEnv,
+        WorkflowRunName,
+        WorkflowConcurrency,
+        JobIf,
+        JobName,
+        JobRunsOn,
+        JobEnv,
+        JobEnvironment,
+        JobConcurrency,
+        JobStrategy,
+        JobWith,
+        JobSecrets,
+        StepIf,
+        StepName,
+        StepRun,
+        StepEnv,
+        StepWith,
+    }
+    impl FromStr for Placement {
+        type Err = PyErr;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "workflow.env" | "env" => Ok(Self::WorkflowEnv),
+                "workflow.run-name" | "run-name" => Ok(Self::WorkflowRunName),
+                "workflow.concurrency" | "concurrency" => Ok(Self::WorkflowConcurrency),
+                "jobs.<job_id>.if" | "job.if" => Ok(Self::JobIf),
+                "jobs.<job_id>.name" | "job.name" => Ok(Self::JobName),
+                "jobs.<job_id>.runs-on" | "job.runs-on" | "runs-on" => Ok(Self::JobRunsOn),
+                "jobs.<job_id>.env" | "job.env" => Ok(Self::JobEnv),
+                "jobs.<job_id>.environment" | "job.environment" => Ok(Self::JobEnvironment),
+                "jobs.<job_id>.concurrency" | "job.concurrency" => Ok(Self::JobConcurrency),
+                "jobs.<job_id>.strategy" | "job.strategy" => Ok(Self::JobStrategy),
+                "jobs.<job_id>.with.<with_id>" | "job.with" => Ok(Self::JobWith),
+                "jobs.<job_id>.secrets.<secrets_id>" | "job.secrets" => Ok(Self::JobSecrets),
+                "jobs.<job_id>.steps.if" | "steps.<id>.if" | "step.if" => Ok(Self::StepIf),
+                "jobs.<job_id>.steps.name" | "steps.<id>.name" | "step.name" => Ok(Self::StepName),
+                "jobs.<job_id>.steps.run" | "steps.<id>.run" | "step.run" => Ok(Self::StepRun),
+                "jobs.<job_id>.steps.env" | "steps.<id>.env" | "step.env" => Ok(Self::StepEnv),
+                "jobs.<job_id>.steps.with" | "steps.<id>.with" | "step.with" => Ok(Self::StepWith),
+                other => Err(PyValueError::new_err(format!(
+                    "Unknown expression placement '{other}'"
+                ))),
+            }
+        }
+    }
+    impl Placement {
+        fn allowed(&self) -> Allowed {
+            match self {
+                Self::WorkflowEnv => ALLOWED_WORKFLOW_ENV,
+                Self::WorkflowRunName => ALLOWED_WORKFLOW_RUN_NAME,
+                Self::WorkflowConcurrency => ALLOWED_WORKFLOW_CONCURRENCY,
+                Self::JobIf => ALLOWED_JOB_IF,
+                Self::JobName => ALLOWED_JOB_NAME,
+                Self::JobRunsOn => ALLOWED_JOB_RUNS_ON,
+                Self::JobEnv => ALLOWED_JOB_ENV,
+                Self::JobEnvironment => ALLOWED_JOB_ENVIRONMENT,
+                Self::JobConcurrency => ALLOWED_JOB_CONCURRENCY,
+                Self::JobStrategy => ALLOWED_JOB_STRATEGY,
+                Self::JobWith => ALLOWED_JOB_WITH,
+                Self::JobSecrets => ALLOWED_JOB_SECRETS,
+                Self::StepIf => ALLOWED_STEP_IF,
+                Self::StepName => ALLOWED_STEP_NAME,
+                Self::StepRun => ALLOWED_STEP_RUN,
+                Self::StepEnv => ALLOWED_STEP_ENV,
+                Self::StepWith => ALLOWED_STEP_WITH,
+            }
+        }
+    }
+
+    /// Check whether `expr` would be accepted by GitHub at `placement` (one of the canonical
+    /// workflow keys listed on `Placement`, e.g. `"jobs.<job_id>.if"` or the shorthand
+    /// `"job.if"`). Raw (non-`*Expression`) values always pass, since they reference no context
+    /// at all. On a violation, the error names both the offending context/function root(s) and
+    /// the placement that rejected them, the same diagnostic the matching builder would raise.
+    #[pyfunction]
+    fn validate_placement(expr: &Bound<'_, PyAny>, placement: &str) -> PyResult<()> {
+        let allowed = Placement::from_str(placement)?.allowed();
+        if let Ok(e) = expr.extract::<BooleanExpression>() {
+            e.validate_allowed(allowed)
+        } else if let Ok(e) = expr.extract::<NumberExpression>() {
+            e.validate_allowed(allowed)
+        } else if let Ok(e) = expr.extract::<StringExpression>() {
+            e.validate_allowed(allowed)
+        } else if let Ok(e) = expr.extract::<ArrayExpression>() {
+            e.validate_allowed(allowed)
+        } else if let Ok(e) = expr.extract::<ObjectExpression>() {
+            e.validate_allowed(allowed)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn yaml_is_expression(value: &Yaml) -> bool {
+        matches!(value, Yaml::Real(s) if s.contains("${{") && s.contains("}}"))
+    }
+
+    fn yaml_kind_name(value: &Yaml) -> &'static str {
+        match value {
+            Yaml::Boolean(_) => "a boolean",
+            Yaml::Integer(_) | Yaml::Real(_) => "a number",
+            Yaml::String(_) => "a string",
+            Yaml::Array(_) => "an array",
+            Yaml::Hash(_) => "a hash",
+            Yaml::Null => "null",
+            Yaml::Alias(_) | Yaml::BadValue => "an unsupported value",
+        }
+    }
+
+    fn workflow_input_type_matches(value: &Yaml, input_type: &WorkflowInputType) -> bool {
+        matches!(
+            (value, input_type),
+            (Yaml::Boolean(_), WorkflowInputType::Boolean { .. })
+                | (
+                    Yaml::Integer(_) | Yaml::Real(_),
+                    WorkflowInputType::Number { .. }
+                )
+                | (Yaml::String(_), WorkflowInputType::String { .. })
+        )
+    }
+
+    /// Cross-validate a caller `Job` that invokes a reusable workflow (its `uses` field) against
+    /// the `callee` `Workflow` it names: every key in `with`/`secrets` must be an input/secret the
+    /// callee actually declares under `on.workflow_call`, every `required` input/secret the callee
+    /// declares must be supplied, and every `with` value whose YAML shape is a literal (not a
+    /// `${{ ... }}` expression, whose runtime type can't be known statically) must match the
+    /// callee's declared input type. Raises a RuntimeError describing the first mismatch found.
+    #[pyfunction]
+    fn validate_workflow_call(job: &Job, callee: &Workflow) -> PyResult<()> {
+        let Some(call) = &callee.on.workflow_call else {
+            return Err(PyRuntimeError::new_err(
+                "Job calls a reusable workflow, but the callee does not declare `on.workflow_call`",
+            ));
+        };
+
+        if let Some(with) = &job.with {
+            for (key, value) in with.iter() {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let Some((_, input)) = call.inputs.iter().find(|(name, _)| name.as_str() == key)
+                else {
+                    return Err(PyRuntimeError::new_err(format!(
+                        "Job passes `with.{key}`, but the callee workflow declares no such input"
+                    )));
+                };
+                if !yaml_is_expression(value)
+                    && !workflow_input_type_matches(value, &input.input_type)
+                {
+                    return Err(PyRuntimeError::new_err(format!(
+                        "Job passes `with.{key}` as {}, but the callee workflow declares it as {}",
+                        yaml_kind_name(value),
+                        input.input_type.get_type().as_str().unwrap_or("?"),
+                    )));
+                }
+            }
+        }
+        for (name, input) in call.inputs.iter() {
+            if input.required == Some(true) && !with_has_key(job.with.as_ref(), name) {
+                return Err(PyRuntimeError::new_err(format!(
+                    "The callee workflow requires input '{name}', but the job does not pass it via `with`"
+                )));
+            }
+        }
+
+        match job.secrets.as_ref().map(|secrets| &secrets.options) {
+            None => {
+                for (name, secret) in call.secrets.iter() {
+                    if secret.required == Some(true) {
+                        return Err(PyRuntimeError::new_err(format!(
+                            "The callee workflow requires secret '{name}', but the job does not pass any `secrets`"
+                        )));
+                    }
+                }
+            }
+            Some(JobSecretsOptions::Inherit) => {}
+            Some(JobSecretsOptions::Secrets(values)) => {
+                for key in values.keys() {
+                    if !call.secrets.iter().any(|(name, _)| name.as_str() == key.as_str()) {
+                        return Err(PyRuntimeError::new_err(format!(
+                            "Job passes `secrets.{key}`, but the callee workflow declares no such secret"
+                        )));
+                    }
+                }
+                for (name, secret) in call.secrets.iter() {
+                    if secret.required == Some(true) && !values.contains_key(name) {
+                        return Err(PyRuntimeError::new_err(format!(
+                            "The callee workflow requires secret '{name}', but the job does not pass it via `secrets`"
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn with_has_key(with: Option<&Hash>, key: &str) -> bool {
+        with.is_some_and(|with| with.iter().any(|(k, _)| k.as_str() == Some(key)))
+    }
+
+    fn validate_string_like(value: &StringLike, allowed: Allowed) -> PyResult<()> {
+        validate_string_like_for_untrusted_input(value, allowed, TaintSeverity::Warn)
+    }
+
+    /// Like `validate_string_like`, but for a run-style field (currently just
+    /// `ALLOWED_STEP_RUN`) also runs the script-injection check, at the caller-chosen
+    /// `untrusted_severity`. For any other `allowed` target the extra check is a no-op, since
+    /// only a field that gets interpolated into a shell command is an injection vector.
+    fn validate_string_like_for_untrusted_input(
+        value: &StringLike,
+        allowed: Allowed,
+        untrusted_severity: TaintSeverity,
+    ) -> PyResult<()> {
+        if let Either::A(expr) = value {
+            expr.validate_allowed(allowed)?;
+            if allowed.label() == ALLOWED_STEP_RUN.label() {
+                expr.validate_untrusted_input(untrusted_severity)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_bool_like(value: &BoolLike, allowed: Allowed) -> PyResult<()> {
+        if let Either::A(expr) = value {
+            expr.validate_allowed(allowed)?;
+        }
+        Ok(())
+    }
+
+    fn validate_int_like(value: &IntLike, allowed: Allowed) -> PyResult<()> {
+        if let Either::A(expr) = value {
+            expr.validate_allowed(allowed)?;
+        }
+        Ok(())
+    }
+
+    fn validate_condition(
+        value: &Either<BooleanExpression, String>,
+        allowed: Allowed,
+    ) -> PyResult<()> {
+        if let Either::A(expr) = value {
+            expr.validate_allowed(allowed)?;
+        }
+        Ok(())
+    }
+
+    fn validate_string_map(values: &PyMap<String, StringLike>, allowed: Allowed) -> PyResult<()> {
+        for (_, value) in values.iter() {
+            validate_string_like(value, allowed)?;
+        }
+        Ok(())
+    }
+
+    fn validate_string_vec(values: &[StringLike], allowed: Allowed) -> PyResult<()> {
+        for value in values {
+            validate_string_like(value, allowed)?;
+        }
+        Ok(())
+    }
+
+    fn validate_one_or_vec(values: &OneOrVec<StringLike>, allowed: Allowed) -> PyResult<()> {
+        for value in values {
+            validate_string_like(value, allowed)?;
+        }
+        Ok(())
+    }
+
+    fn validate_runs_on(runs_on: &RunsOn) -> PyResult<()> {
+        match runs_on {
+            RunsOn::String(value) => validate_string_like(value, ALLOWED_JOB_RUNS_ON),
+            RunsOn::Array(values) => validate_string_vec(values, ALLOWED_JOB_RUNS_ON),
+            RunsOn::Spec(spec) => match &spec.options {
+                RunsOnSpecOptions::Group(group) => validate_string_like(group, ALLOWED_JOB_RUNS_ON),
+                RunsOnSpecOptions::Labels(labels) => validate_one_or_vec(labels, ALLOWED_JOB_RUNS_ON),
+                RunsOnSpecOptions::GroupAndLabels(group, labels) => {
+                    validate_string_like(group, ALLOWED_JOB_RUNS_ON)?;
+                    validate_one_or_vec(labels, ALLOWED_JOB_RUNS_ON)
+                }
+            },
+        }
+    }
+
+    fn validate_with_opts(opts: &Bound<'_, PyDict>, allowed: Allowed) -> PyResult<()> {
+        for (_, value) in opts.iter() {
+            if let Ok(expr) = value.extract::<BooleanExpression>() {
+                expr.validate_allowed(allowed)?;
+            } else if let Ok(expr) = value.extract::<StringExpression>() {
+                expr.validate_allowed(allowed)?;
+            } else if let Ok(expr) = value.extract::<NumberExpression>() {
+                expr.validate_allowed(allowed)?;
+            } else if let Ok(expr) = value.extract::<ArrayExpression>() {
+                expr.validate_allowed(allowed)?;
+            } else if let Ok(expr) = value.extract::<ObjectExpression>() {
+                expr.validate_allowed(allowed)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_step_options(
+        name: Option<&StringLike>,
+        condition: Option<&Either<BooleanExpression, String>>,
+        working_directory: Option<&StringLike>,
+        env: Option<&PyMap<String, StringLike>>,
+        continue_on_error: Option<&BoolLike>,
+        timeout_minutes: Option<&IntLike>,
+    ) -> PyResult<()> {
+        if let Some(name) = name {
+            validate_string_like(name, ALLOWED_STEP_NAME)?;
+        }
+        if let Some(condition) = condition {
+            validate_condition(condition, ALLOWED_STEP_IF)?;
+        }
+        if let Some(working_directory) = working_directory {
+            validate_string_like(working_directory, ALLOWED_STEP_WORKING_DIRECTORY)?;
+        }
+        if let Some(env) = env {
+            validate_string_map(env, ALLOWED_STEP_ENV)?;
+        }
+        if let Some(continue_on_error) = continue_on_error {
+            validate_bool_like(continue_on_error, ALLOWED_STEP_CONTINUE_ON_ERROR)?;
+        }
+        if let Some(timeout_minutes) = timeout_minutes {
+            validate_int_like(timeout_minutes, ALLOWED_STEP_TIMEOUT_MINUTES)?;
+        }
+        Ok(())
+    }
+
+    fn validate_container_for_job(container: &Container) -> PyResult<()> {
+        validate_string_like(&container.image, ALLOWED_JOB_CONTAINER_IMAGE)?;
+        if let Some(options) = &container.options {
+            validate_string_like(options, ALLOWED_JOB_CONTAINER)?;
+        }
+        if let Some(volumes) = &container.volumes {
+            validate_string_vec(volumes, ALLOWED_JOB_CONTAINER)?;
+        }
+        if let Some(ports) = &container.ports {
+            for port in ports {
+                validate_int_like(port, ALLOWED_JOB_CONTAINER)?;
+            }
+        }
+        if let Some(credentials) = &container.credentials {
+            validate_string_like(&credentials.username, ALLOWED_JOB_CONTAINER_CREDENTIALS)?;
+            validate_string_like(&credentials.password, ALLOWED_JOB_CONTAINER_CREDENTIALS)?;
+        }
+        if let Some(env) = &container.env {
+            validate_string_map(env, ALLOWED_JOB_CONTAINER_ENV)?;
+        }
+        Ok(())
+    }
+
+    fn validate_container_for_service(container: &Container) -> PyResult<()> {
+        validate_string_like(&container.image, ALLOWED_JOB_SERVICES)?;
+        if let Some(options) = &container.options {
+            validate_string_like(options, ALLOWED_JOB_SERVICES)?;
+        }
+        if let Some(volumes) = &container.volumes {
+            validate_string_vec(volumes, ALLOWED_JOB_SERVICES)?;
+        }
+        if let Some(ports) = &container.ports {
+            for port in ports {
+                validate_int_like(port, ALLOWED_JOB_SERVICES)?;
+            }
+        }
+        if let Some(credentials) = &container.credentials {
+            validate_string_like(&credentials.username, ALLOWED_JOB_SERVICES_CREDENTIALS)?;
+            validate_string_like(&credentials.password, ALLOWED_JOB_SERVICES_CREDENTIALS)?;
+        }
+        if let Some(env) = &container.env {
+            validate_string_map(env, ALLOWED_JOB_SERVICES_ENV)?;
+        }
+        Ok(())
+    }
+
+    fn validate_concurrency(concurrency: &Concurrency, allowed: Allowed) -> PyResult<()> {
+        validate_string_like(&concurrency.group, allowed)?;
+        if let Some(cancel_in_progress) = &concurrency.cancel_in_progress {
+            validate_bool_like(cancel_in_progress, allowed)?;
+        }
+        Ok(())
+    }
+
+    fn validate_environment(environment: &Environment) -> PyResult<()> {
+        validate_string_like(&environment.name, ALLOWED_JOB_ENVIRONMENT)?;
+        if let Some(url) = &environment.url {
+            validate_string_like(url, ALLOWED_JOB_ENVIRONMENT_URL)?;
+        }
+        Ok(())
+    }
+    impl TryYamlable for Bound<'_, PyAny> {
+        fn try_as_yaml(&self) -> PyResult<Yaml> {
+            if self.is_none() {
+                Ok(Yaml::Null)
+            } else if let Ok(e) = self.extract::<StringExpression>() {
+                Ok((&e).as_yaml())
+            } else if let Ok(e) = self.extract::<BooleanExpression>() {
+                Ok((&e).as_yaml())
+            } else if let Ok(e) = self.extract::<NumberExpression>() {
+                Ok((&e).as_yaml())
+            } else if self.is_instance_of::<PyBool>() {
+                Ok(self.extract::<bool>()?.as_yaml())
+            } else if self.is_instance_of::<PyInt>() {
+                Ok(self.extract::<i64>()?.as_yaml())
+            } else if self.is_instance_of::<PyFloat>() {
+                Ok(self.extract::<f64>()?.as_yaml())
+            } else if self.is_instance_of::<PyString>() {
+                Ok(self.extract::<String>()?.as_yaml())
+            } else if let Ok(list) = self.cast::<PyList>() {
+                Ok(Yaml::Array(list.try_as_array()?))
+            } else if let Ok(dict) = self.cast::<PyDict>() {
+                Ok(Yaml::Hash(dict.try_as_hash()?))
+            } else {
+                Err(PyValueError::new_err("Invalid value"))
+            }
+        }
+    }
+
+    impl TryHash for Bound<'_, PyDict> {
+        fn try_as_hash(&self) -> PyResult<Hash> {
+            let mut dict_internals = Hash::new();
+            for (key, entry) in self.iter() {
+                if let Ok(key) = key.extract::<String>() {
+                    dict_internals.insert_yaml(key, entry.try_as_yaml()?);
+                } else {
+                    return Err(PyValueError::new_err("Invalid key"));
+                }
+            }
+            Ok(dict_internals)
+        }
+    }
+
+    impl TryArray for Bound<'_, PyList> {
+        fn try_as_array(&self) -> PyResult<Vec<Yaml>> {
+            let mut list_internals = Vec::new();
+            for entry in self.iter() {
+                list_internals.push(entry.try_as_yaml()?);
+            }
+            Ok(list_internals)
+        }
+    }
+
+    #[derive(Clone)]
+    struct WithArgs {
+        options: Option<Hash>,
+        args: Option<StringLike>,
+        entrypoint: Option<StringLike>,
+    }
+
+    impl Yamlable for WithArgs {
+        fn as_yaml(&self) -> Yaml {
+            let mut entries = self.options.clone().unwrap_or_default();
+            entries.insert_yaml_opt("args", &self.args);
+            entries.insert_yaml_opt("entrypoint", &self.entrypoint);
+            Yaml::Hash(entries)
+        }
+    }
+
+    #[derive(Clone)]
+    enum StepAction {
+        Run(StringLike),
+        Action {
+            uses: String,
+            pin_comment: Option<String>,
+            with: Option<WithArgs>,
+        },
+    }
+    impl StepAction {
+        fn uses_yaml(&self) -> Option<Yaml> {
+            match self {
+                StepAction::Run(_) => None,
+                StepAction::Action {
+                    uses,
+                    pin_comment: Some(comment),
+                    ..
+                } => Some(Yaml::Real(format!("{uses} # {comment}"))),
+                StepAction::Action { uses, .. } => Some(uses.as_yaml()),
+            }
+        }
+        fn with(&self) -> Option<WithArgs> {
+            match self {
+                StepAction::Run(_) => None,
+                StepAction::Action { with, .. } => with.clone(),
+            }
+        }
+        fn run(&self) -> Option<&StringLike> {
+            match self {
+                StepAction::Run(script) => Some(script),
+                StepAction::Action { .. } => None,
+            }
+        }
+    }
+
+    #[pyclass(subclass)]
+    #[derive(Clone)]
+    struct Step {
+        name: Option<StringLike>,
+        step_action: StepAction,
+        options: StepOptions,
+        recommended_permissions: Option<Permissions>,
+    }
+
+    #[derive(Clone)]
+    struct StepOptions {
+        condition: Option<Either<BooleanExpression, String>>,
+        working_directory: Option<StringLike>,
+        shell: Option<String>,
+        id: Option<String>,
+        env: Option<PyMap<String, StringLike>>,
+        continue_on_error: Option<BoolLike>,
+        timeout_minutes: Option<IntLike>,
+    }
+
+    #[pymethods]
+    impl Step {
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        /// Evaluate every `${{ ... }}` expression in this step's rendered YAML against a concrete
+        /// `context` dict (the same shape `evaluate.evaluate` takes, keyed by root like `github`,
+        /// `inputs`, `matrix`, `env`, `needs`, ...), returning the fully resolved YAML as a string
+        /// instead of the templated form. Useful for asserting on the materialized output for a
+        /// specific event payload in tests.
+        fn render_with_context(&self, context: &Bound<'_, PyDict>) -> PyResult<String> {
+            render_yaml_with_context(&self.as_yaml(), context)?.as_yaml_string()
+        }
+
+        /// Parse a single entry of a job's ``steps:`` list from an existing workflow file.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            step_from_hash(expect_hash(&parse_yaml_document(yaml)?, "step")?.clone())
+        }
+    }
+    /// A YAML scalar that is, in its entirety, a single `${{ ... }}` expression (trimming
+    /// whitespace), mirroring `expressions::is_expression`'s granularity: partial/inline
+    /// expressions embedded inside a larger string are left untouched.
+    fn strip_expr_delimiters(scalar: &str) -> Option<&str> {
+        let trimmed = scalar.trim();
+        trimmed
+            .strip_prefix("${{")
+            .and_then(|s| s.strip_suffix("}}"))
+            .map(str::trim)
+    }
+
+    /// Walk a rendered `Yaml` tree, evaluating every scalar that is a whole `${{ ... }}`
+    /// expression against `context` and substituting its concrete value, leaving every other
+    /// scalar (and the tree shape) untouched.
+    fn render_yaml_with_context(yaml: &Yaml, context: &Bound<'_, PyDict>) -> PyResult<Yaml> {
+        Ok(match yaml {
+            Yaml::Real(s) => match strip_expr_delimiters(s) {
+                Some(inner) => value_to_yaml(&evaluate_value(inner, context)?),
+                None => yaml.clone(),
+            },
+            Yaml::Array(items) => Yaml::Array(
+                items
+                    .iter()
+                    .map(|item| render_yaml_with_context(item, context))
+                    .collect::<PyResult<_>>()?,
+            ),
+            Yaml::Hash(hash) => {
+                let mut out = Hash::new();
+                for (k, v) in hash {
+                    out.insert(
+                        render_yaml_with_context(k, context)?,
+                        render_yaml_with_context(v, context)?,
+                    );
+                }
+                Yaml::Hash(out)
+            }
+            other => other.clone(),
+        })
+    }
+
+    /// Read `text` as a single YAML document, the inverse entry point to `as_yaml_string` used by
+    /// every `from_yaml` constructor below (e.g. `Job.from_yaml`, `Workflow.from_yaml`) to parse an
+    /// existing workflow file back into typed objects.
+    fn parse_yaml_document(text: &str) -> PyResult<Yaml> {
+        let mut docs =
+            YamlLoader::load_from_str(text).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        match docs.len() {
+            1 => Ok(docs.remove(0)),
+            0 => Ok(Yaml::Hash(Hash::new())),
+            _ => Err(PyValueError::new_err(
+                "Expected a single YAML document, found multiple",
+            )),
+        }
+    }
+
+    fn expect_hash<'a>(yaml: &'a Yaml, what: &str) -> PyResult<&'a Hash> {
+        yaml.as_hash()
+            .ok_or_else(|| PyValueError::new_err(format!("Expected '{what}' to be a mapping")))
+    }
+
+    /// Pull a field out of a parsed hash by its (kebab-case) YAML key, removing it so the caller
+    /// can detect leftover, unmodeled keys once every known field has been extracted.
+    fn hash_take(hash: &mut Hash, key: &str) -> Option<Yaml> {
+        hash.remove(&Yaml::String(key.to_string()))
+    }
+
+    /// After every field a `from_yaml` constructor understands has been removed via `hash_take`,
+    /// error out naming whatever is left, so users can tell which keys this crate doesn't yet
+    /// model instead of having them silently dropped.
+    fn reject_unknown_keys(hash: &Hash, what: &str) -> PyResult<()> {
+        if hash.is_empty() {
+            return Ok(());
+        }
+        let keys: Vec<String> = hash
+            .keys()
+            .map(|k| k.as_str().map(str::to_string).unwrap_or_else(|| format!("{k:?}")))
+            .collect();
+        Err(PyValueError::new_err(format!(
+            "Unrecognized key(s) in '{what}' that yamloom does not yet model: {}",
+            keys.join(", ")
+        )))
+    }
+
+    fn yaml_scalar_to_string(yaml: &Yaml, what: &str) -> PyResult<String> {
+        match yaml {
+            Yaml::String(s) | Yaml::Real(s) => Ok(s.clone()),
+            Yaml::Integer(i) => Ok(i.to_string()),
+            Yaml::Boolean(b) => Ok(b.to_string()),
+            _ => Err(PyValueError::new_err(format!(
+                "Expected '{what}' to be a scalar"
+            ))),
+        }
+    }
+
+    /// Parse a YAML scalar back into a `StringLike`: a whole `${{ ... }}` expression becomes a
+    /// `StringExpression` (via `expressions::parse_scalar`), anything else is kept as a literal
+    /// string.
+    fn parse_string_like(yaml: &Yaml, what: &str) -> PyResult<StringLike> {
+        let s = yaml_scalar_to_string(yaml, what)?;
+        Ok(match parse_scalar(&s) {
+            Some(expr) => Either::A(expr),
+            None => Either::B(s),
+        })
+    }
+
+    /// Parse a YAML node that GitHub accepts as either a bare scalar or a list of scalars (e.g.
+    /// `runs-on.labels`) into a `OneOrVec`, matching the crate's Python-side `OneOrVec` coercion.
+    fn parse_one_or_vec_string_like(yaml: &Yaml, what: &str) -> PyResult<OneOrVec<StringLike>> {
+        match yaml.as_vec() {
+            Some(arr) => Ok(OneOrVec::from(
+                arr.iter()
+                    .map(|y| parse_string_like(y, what))
+                    .collect::<PyResult<Vec<_>>>()?,
+            )),
+            None => Ok(OneOrVec::from(vec![parse_string_like(yaml, what)?])),
+        }
+    }
+
+    fn parse_bool_like(yaml: &Yaml, what: &str) -> PyResult<BoolLike> {
+        if let Yaml::Boolean(b) = yaml {
+            return Ok(Either::B(*b));
+        }
+        let s = yaml_scalar_to_string(yaml, what)?;
+        match parse_scalar(&s) {
+            Some(expr) => Ok(Either::A(expr.as_bool())),
+            None => s.parse::<bool>().map(Either::B).map_err(|_| {
+                PyValueError::new_err(format!(
+                    "Expected '{what}' to be a boolean or a '${{{{ }}}}' expression"
+                ))
+            }),
+        }
+    }
+
+    fn parse_int_like(yaml: &Yaml, what: &str) -> PyResult<IntLike> {
+        if let Yaml::Integer(i) = yaml {
+            return Ok(Either::B(*i));
+        }
+        let s = yaml_scalar_to_string(yaml, what)?;
+        match parse_scalar(&s) {
+            Some(expr) => Ok(Either::A(expr.as_num())),
+            None => s.parse::<i64>().map(Either::B).map_err(|_| {
+                PyValueError::new_err(format!(
+                    "Expected '{what}' to be an integer or a '${{{{ }}}}' expression"
+                ))
+            }),
+        }
+    }
+
+    /// Parse a job's or step's ``if:`` condition, whose type (`Either<BooleanExpression, String>`)
+    /// keeps bare (un-delimited) expressions like `success()` as a plain string rather than
+    /// requiring the `${{ }}` wrapper GitHub only strictly requires elsewhere.
+    fn parse_condition(yaml: &Yaml, what: &str) -> PyResult<Either<BooleanExpression, String>> {
+        let s = yaml_scalar_to_string(yaml, what)?;
+        Ok(match parse_scalar(&s) {
+            Some(expr) => Either::A(expr.as_bool()),
+            None => Either::B(s),
+        })
+    }
+
+    fn parse_string_map(yaml: &Yaml, what: &str) -> PyResult<PyMap<String, StringLike>> {
+        expect_hash(yaml, what)?
+            .iter()
+            .map(|(k, v)| {
+                let key = yaml_scalar_to_string(k, what)?;
+                let value = parse_string_like(v, what)?;
+                Ok((key, value))
+            })
+            .collect::<PyResult<Vec<_>>>()
+            .map(|pairs| pairs.into_iter().collect())
+    }
+
+    /// Like `expect_hash`, but treats a missing or `null` trigger (`on: { push: }`) as an empty
+    /// mapping rather than an error, matching GitHub's "use the default config" shorthand.
+    fn expect_hash_or_empty(yaml: &Yaml, what: &str) -> PyResult<Hash> {
+        match yaml {
+            Yaml::BadValue | Yaml::Null => Ok(Hash::new()),
+            Yaml::Hash(hash) => Ok(hash.clone()),
+            _ => Err(PyValueError::new_err(format!(
+                "Expected '{what}' to be a mapping"
+            ))),
+        }
+    }
+
+    fn yaml_as_bool(yaml: &Yaml, what: &str) -> PyResult<bool> {
+        yaml.as_bool()
+            .ok_or_else(|| PyValueError::new_err(format!("Expected '{what}' to be a boolean")))
+    }
+
+    fn parse_string_vec(yaml: &Yaml, what: &str) -> PyResult<Vec<String>> {
+        yaml.as_vec()
+            .ok_or_else(|| PyValueError::new_err(format!("Expected '{what}' to be a list")))?
+            .iter()
+            .map(|y| yaml_scalar_to_string(y, what))
+            .collect()
+    }
+
+    /// Pull an optional list-of-strings field (e.g. `branches`, `paths-ignore`) out of `hash` by
+    /// its kebab-case key, the filter-field counterpart to `hash_take` + `parse_string_vec`.
+    fn take_string_vec(hash: &mut Hash, key: &str, what: &str) -> PyResult<Option<Vec<String>>> {
+        hash_take(hash, key)
+            .map(|yaml| parse_string_vec(&yaml, &format!("{what}.{key}")))
+            .transpose()
+    }
+
+    /// Reject an include filter (`branches`, `paths`, ...) and its matching `-ignore`
+    /// counterpart both being set on the same event, a combination GitHub Actions refuses to
+    /// run. Called from every such pair's constructor and `_from_hash` parser so the invariant
+    /// holds regardless of which path built the event; fields are never exposed to Python after
+    /// construction, so there's no separate path that could re-break it later.
+    fn validate_filter_conflict(
+        include: Option<&Vec<String>>,
+        ignore: Option<&Vec<String>>,
+        what: &str,
+        include_name: &str,
+        ignore_name: &str,
+    ) -> PyResult<()> {
+        if include.is_some() && ignore.is_some() {
+            Err(FilterConflictError::new_err(format!(
+                "'{what}' cannot specify both '{include_name}' and '{ignore_name}'"
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// One token of a compiled `FilterPattern`. `Star`, `DoubleStar`, and `OneOrMore` never
+    /// match a single character themselves (see `pattern_token_matches_char`); they're handled
+    /// structurally by `pattern_tokens_match` instead.
+    #[derive(Clone)]
+    enum PatternToken {
+        Literal(char),
+        AnyChar,
+        Star,
+        DoubleStar,
+        Class(Vec<(char, char)>),
+        OneOrMore(Box<PatternToken>),
+    }
+
+    /// A single compiled entry of a `branches`/`branches-ignore`/`paths`/`paths-ignore` list,
+    /// implementing GitHub's filter-pattern syntax: `*` matches zero or more characters except
+    /// `/`, `**` matches zero or more characters including `/`, `?` matches exactly one non-`/`
+    /// character, `+` matches one or more of the immediately preceding character or class,
+    /// `[a-z]`/`[0-9]` are literal character-range classes, `\` escapes the following character,
+    /// and a leading `!` negates the whole pattern.
+    #[derive(Clone)]
+    struct FilterPattern {
+        negated: bool,
+        tokens: Vec<PatternToken>,
+    }
+
+    fn compile_filter_pattern(pattern: &str) -> PyResult<FilterPattern> {
+        let (negated, body) = match pattern.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let chars: Vec<char> = body.chars().collect();
+        let mut tokens: Vec<PatternToken> = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '\\' => {
+                    let escaped = *chars.get(i + 1).ok_or_else(|| {
+                        PyValueError::new_err(format!(
+                            "Pattern '{pattern}' ends with a dangling '\\' escape"
+                        ))
+                    })?;
+                    tokens.push(PatternToken::Literal(escaped));
+                    i += 2;
+                }
+                '*' => {
+                    if chars.get(i + 1) == Some(&'*') {
+                        tokens.push(PatternToken::DoubleStar);
+                        i += 2;
+                    } else {
+                        tokens.push(PatternToken::Star);
+                        i += 1;
+                    }
+                }
+                '?' => {
+                    tokens.push(PatternToken::AnyChar);
+                    i += 1;
+                }
+                '[' => {
+                    let end = chars[i + 1..]
+                        .iter()
+                        .position(|&c| c == ']')
+                        .map(|p| i + 1 + p)
+                        .ok_or_else(|| {
+                            PyValueError::new_err(format!(
+                                "Pattern '{pattern}' has an unterminated '[' character class"
+                            ))
+                        })?;
+                    let class_chars = &chars[i + 1..end];
+                    let mut ranges = Vec::new();
+                    let mut j = 0;
+                    while j < class_chars.len() {
+                        if j + 2 < class_chars.len() && class_chars[j + 1] == '-' {
+                            ranges.push((class_chars[j], class_chars[j + 2]));
+                            j += 3;
+                        } else {
+                            ranges.push((class_chars[j], class_chars[j]));
+                            j += 1;
+                        }
+                    }
+                    tokens.push(PatternToken::Class(ranges));
+                    i = end + 1;
+                }
+                '+' => {
+                    let prev = tokens.pop().ok_or_else(|| {
+                        PyValueError::new_err(format!(
+                            "Pattern '{pattern}' has a '+' with no preceding character to repeat"
+                        ))
+                    })?;
+                    if matches!(
+                        prev,
+                        PatternToken::Star | PatternToken::DoubleStar | PatternToken::OneOrMore(_)
+                    ) {
+                        return Err(PyValueError::new_err(format!(
+                            "Pattern '{pattern}' applies '+' to a wildcard, which GitHub does not support"
+                        )));
+                    }
+                    tokens.push(PatternToken::OneOrMore(Box::new(prev)));
+                    i += 1;
+                }
+                c => {
+                    tokens.push(PatternToken::Literal(c));
+                    i += 1;
+                }
+            }
+        }
+        Ok(FilterPattern { negated, tokens })
+    }
+
+    fn pattern_token_matches_char(token: &PatternToken, c: char) -> bool {
+        match token {
+            PatternToken::Literal(l) => *l == c,
+            PatternToken::AnyChar => c != '/',
+            PatternToken::Class(ranges) => ranges.iter().any(|&(lo, hi)| c >= lo && c <= hi),
+            PatternToken::Star | PatternToken::DoubleStar | PatternToken::OneOrMore(_) => false,
+        }
+    }
+
+    /// Anchored match of a compiled token stream against the whole of `value`, the equivalent of
+    /// compiling each pattern to a `^...$`-anchored regex (`*` -> `[^/]*`, `**` -> `.*`, etc.) and
+    /// testing it once; implemented as direct backtracking instead since this crate doesn't
+    /// otherwise depend on a regex engine.
+    fn pattern_tokens_match(tokens: &[PatternToken], value: &[char]) -> bool {
+        let Some((token, rest)) = tokens.split_first() else {
+            return value.is_empty();
+        };
+        match token {
+            PatternToken::Star => {
+                for i in 0..=value.len() {
+                    if value[..i].contains(&'/') {
+                        break;
+                    }
+                    if pattern_tokens_match(rest, &value[i..]) {
+                        return true;
+                    }
+                }
+                false
+            }
+            PatternToken::DoubleStar => {
+                (0..=value.len()).any(|i| pattern_tokens_match(rest, &value[i..]))
+            }
+            PatternToken::OneOrMore(inner) => {
+                let mut count = 0;
+                while count < value.len() && pattern_token_matches_char(inner, value[count]) {
+                    count += 1;
+                }
+                (1..=count).rev().any(|i| pattern_tokens_match(rest, &value[i..]))
+            }
+            _ => match value.split_first() {
+                Some((&c, remaining)) => {
+                    pattern_token_matches_char(token, c) && pattern_tokens_match(rest, remaining)
+                }
+                None => false,
+            },
+        }
+    }
+
+    fn filter_pattern_matches(pattern: &FilterPattern, value: &str) -> bool {
+        let chars: Vec<char> = value.chars().collect();
+        pattern_tokens_match(&pattern.tokens, &chars)
+    }
+
+    fn compile_filter_patterns(patterns: &[String]) -> PyResult<Vec<FilterPattern>> {
+        patterns.iter().map(|p| compile_filter_pattern(p)).collect()
+    }
+
+    /// Evaluate a whole filter list (`branches`, `paths-ignore`, ...) against a candidate value,
+    /// applying GitHub's precedence rule: a leading `!` negates a pattern, and at least one
+    /// non-negated pattern must exist for negation to take effect, so a list made up entirely of
+    /// negated patterns never matches anything. Otherwise patterns are tested in order and each
+    /// match overwrites the running result with whether that pattern was negated, so a later
+    /// pattern always wins over an earlier one.
+    fn filter_list_matches(patterns: &[FilterPattern], value: &str) -> bool {
+        if patterns.iter().all(|p| p.negated) {
+            return false;
+        }
+        let mut matched = false;
+        for pattern in patterns {
+            if filter_pattern_matches(pattern, value) {
+                matched = !pattern.negated;
+            }
+        }
+        matched
+    }
+
+    /// Test whether `value` (a ref or changed-file path) would trigger an event given its
+    /// `include`/`-ignore` filter pair, shared by every `matches_ref`/`matches_tag`/
+    /// `matches_path` method. `include` and `ignore` can never both be set
+    /// (`validate_filter_conflict` rejects that at construction time), so at most one of the two
+    /// branches below ever runs; neither being set means the filter doesn't restrict anything.
+    fn matches_filter(
+        value: &str,
+        include: &Option<Vec<String>>,
+        ignore: &Option<Vec<String>>,
+    ) -> PyResult<bool> {
+        if let Some(patterns) = include {
+            Ok(filter_list_matches(&compile_filter_patterns(patterns)?, value))
+        } else if let Some(patterns) = ignore {
+            Ok(!filter_list_matches(&compile_filter_patterns(patterns)?, value))
+        } else {
+            Ok(true)
+        }
+    }
+
+    #[cfg(test)]
+    mod filter_pattern_tests {
+        use super::*;
+
+        fn matches(pattern: &str, value: &str) -> bool {
+            filter_pattern_matches(&compile_filter_pattern(pattern).unwrap(), value)
+        }
+
+        #[test]
+        fn star_matches_within_a_single_path_segment() {
+            assert!(matches("feature/*", "feature/foo"));
+            assert!(!matches("feature/*", "feature/foo/bar"));
+        }
+
+        #[test]
+        fn double_star_matches_across_path_segments() {
+            assert!(matches("feature/**", "feature/foo/bar"));
+            assert!(matches("**", "anything/at/all"));
+        }
+
+        #[test]
+        fn question_mark_matches_exactly_one_non_slash_character() {
+            assert!(matches("releases/v?", "releases/v1"));
+            assert!(!matches("releases/v?", "releases/v10"));
+            assert!(!matches("releases/v?", "releases/v/"));
+        }
+
+        #[test]
+        fn character_class_matches_a_literal_range() {
+            assert!(matches("releases/v[0-9]", "releases/v3"));
+            assert!(!matches("releases/v[0-9]", "releases/va"));
+        }
+
+        #[test]
+        fn filter_list_lets_a_later_pattern_override_an_earlier_one() {
+            let patterns = compile_filter_patterns(&[
+                "releases/**".to_string(),
+                "!releases/**-alpha".to_string(),
+            ])
+            .unwrap();
+            assert!(filter_list_matches(&patterns, "releases/1.0"));
+            assert!(!filter_list_matches(&patterns, "releases/1.0-alpha"));
+        }
+
+        #[test]
+        fn negated_only_list_never_matches() {
+            // Per GitHub's own spec: a leading '!' negates a pattern, but at least one
+            // non-negated pattern must exist for negation to take effect. A branches/paths list
+            // made up entirely of '!'-prefixed patterns therefore never matches anything (this is
+            // a distinct mechanism from the separate branches-ignore/paths-ignore fields, which
+            // are implemented by matches_filter's `ignore` branch, not by this function).
+            let patterns = compile_filter_patterns(&["!mona/octocat".to_string()]).unwrap();
+            assert!(!filter_list_matches(&patterns, "main"));
+            assert!(!filter_list_matches(&patterns, "mona/octocat"));
+        }
+    }
+
+    /// One GitHub "activity type" variant of a single event (e.g. `opened`/`closed` for
+    /// `pull_request`). Each event that filters on activity type gets its own small enum
+    /// implementing this trait, which is all `ActivityTypes` needs to parse and emit a `types:`
+    /// array generically.
+    trait ActivityKind: Copy + Eq + std::hash::Hash + 'static {
+        /// Every variant, in the order GitHub documents them and the order they're emitted in.
+        const ALL: &'static [Self];
+        fn as_str(self) -> &'static str;
+    }
+
+    /// The validated, ordered set behind an event's `types:` array, generic over that event's own
+    /// `ActivityKind` enum. Replaces one bespoke `bool`-per-field struct, constructor, and
+    /// `MaybeYamlable` impl per event with a single shared representation.
+    #[derive(Clone)]
+    struct ActivityTypes<K: ActivityKind>(HashSet<K>);
+
+    impl<K: ActivityKind> ActivityTypes<K> {
+        fn new() -> Self {
+            Self(HashSet::new())
+        }
+
+        /// Build a set from an event's keyword-argument booleans, e.g.
+        /// `ActivityTypes::from_flags([(Kind::Opened, opened), (Kind::Closed, closed)])`, keeping
+        /// the Python constructor's per-type boolean ergonomics while storing only the enum set.
+        fn from_flags(flags: impl IntoIterator<Item = (K, bool)>) -> Self {
+            Self(
+                flags
+                    .into_iter()
+                    .filter_map(|(kind, set)| set.then_some(kind))
+                    .collect(),
+            )
+        }
+
+        fn contains(&self, kind: K) -> bool {
+            self.0.contains(&kind)
+        }
+
+        fn as_yaml(&self) -> Option<Yaml> {
+            if self.0.is_empty() {
+                return None;
+            }
+            let mut arr = Array::new();
+            for kind in K::ALL {
+                arr.push_yaml_cond(kind.as_str(), self.contains(*kind));
+            }
+            Some(Yaml::Array(arr))
+        }
+
+        /// Parse and validate a `types:` array against `K::ALL`, the generic replacement for the
+        /// old per-event `parse_activity_types` calls: a missing `types` key still means "every
+        /// activity type", an entry outside `K::ALL` or repeated in the list is still an error.
+        fn parse(hash: &mut Hash, what: &str) -> PyResult<Self> {
+            let Some(yaml) = hash_take(hash, "types") else {
+                return Ok(Self::new());
+            };
+            let mut seen = HashSet::new();
+            let mut set = HashSet::new();
+            for s in parse_string_vec(&yaml, &format!("{what}.types"))? {
+                let kind = K::ALL.iter().find(|k| k.as_str() == s).copied().ok_or_else(|| {
+                    PyValueError::new_err(format!(
+                        "Unknown activity type '{s}' for '{what}'; expected one of: {}",
+                        K::ALL
+                            .iter()
+                            .map(|k| k.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                })?;
+                if !seen.insert(s.clone()) {
+                    return Err(PyValueError::new_err(format!(
+                        "Duplicate activity type '{s}' for '{what}'"
+                    )));
+                }
+                set.insert(kind);
+            }
+            Ok(Self(set))
+        }
+    }
+
+    /// An event whose YAML shape is (optionally) some fixed, non-activity-type fields plus a
+    /// `types:` array. Implementing this instead of `MaybeYamlable` directly gets the event the
+    /// blanket impl below for free.
+    trait ActivityEvent {
+        type Kind: ActivityKind;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind>;
+        /// Non-activity-type fields this event also emits (filter lists, `workflows`, ...).
+        /// Most events have none and can leave this at its default no-op.
+        fn extra_yaml(&self, _out: &mut Hash) {}
+    }
+
+    impl<T: ActivityEvent> MaybeYamlable for &T {
+        fn maybe_as_yaml(&self) -> Option<Yaml> {
+            let mut out = Hash::new();
+            self.extra_yaml(&mut out);
+            if let Some(types_yaml) = self.activity_types().as_yaml() {
+                out.insert_yaml("types", types_yaml);
+            }
+            if out.is_empty() {
+                None
+            } else {
+                Some(Yaml::Hash(out))
+            }
+        }
+    }
+
+    /// The `types:`-array half of an event's `diff()`: activity types `other` turned on or off
+    /// relative to `self`, for reconciling a hand-edited `on:` block against a freshly generated
+    /// one instead of blindly overwriting it.
+    fn diff_activity_types<K: ActivityKind>(
+        py: Python<'_>,
+        types: &ActivityTypes<K>,
+        other: &ActivityTypes<K>,
+    ) -> PyResult<Py<PyDict>> {
+        let dict = PyDict::new(py);
+        let enabled: Vec<&str> = K::ALL
+            .iter()
+            .filter(|k| !types.contains(**k) && other.contains(**k))
+            .map(|k| k.as_str())
+            .collect();
+        let disabled: Vec<&str> = K::ALL
+            .iter()
+            .filter(|k| types.contains(**k) && !other.contains(**k))
+            .map(|k| k.as_str())
+            .collect();
+        dict.set_item("enabled", enabled)?;
+        dict.set_item("disabled", disabled)?;
+        Ok(dict.unbind())
+    }
+
+    /// The `types:`-array half of an event's `merge()`: the union of both sides' enabled
+    /// activity types.
+    fn merge_activity_types<K: ActivityKind>(
+        types: &ActivityTypes<K>,
+        other: &ActivityTypes<K>,
+    ) -> ActivityTypes<K> {
+        ActivityTypes(types.0.union(&other.0).copied().collect())
+    }
+
+    /// The filter-list half of an event's `diff()`: entries present in `b` but not `a` (added)
+    /// and entries present in `a` but not `b` (removed), each preserving the order of the list
+    /// they came from.
+    fn diff_filter_list(
+        a: &Option<Vec<String>>,
+        b: &Option<Vec<String>>,
+    ) -> (Vec<String>, Vec<String>) {
+        let a_set: HashSet<&str> = a.iter().flatten().map(String::as_str).collect();
+        let b_set: HashSet<&str> = b.iter().flatten().map(String::as_str).collect();
+        let added = b
+            .iter()
+            .flatten()
+            .filter(|v| !a_set.contains(v.as_str()))
+            .cloned()
+            .collect();
+        let removed = a
+            .iter()
+            .flatten()
+            .filter(|v| !b_set.contains(v.as_str()))
+            .cloned()
+            .collect();
+        (added, removed)
+    }
+
+    /// The filter-list half of an event's `merge()`: the union of both lists, deduped while
+    /// preserving insertion order (`a`'s entries first).
+    fn merge_filter_list(a: &Option<Vec<String>>, b: &Option<Vec<String>>) -> Option<Vec<String>> {
+        let mut seen = HashSet::new();
+        let mut merged = Vec::new();
+        for v in a.iter().flatten().chain(b.iter().flatten()) {
+            if seen.insert(v.clone()) {
+                merged.push(v.clone());
+            }
+        }
+        (!merged.is_empty()).then_some(merged)
+    }
+
+    impl Yamlable for Step {
+        fn as_yaml(&self) -> Yaml {
+            let mut entries = Hash::new();
+            entries.insert_yaml_opt("name", &self.name);
+            entries.insert_yaml_opt("if", &self.options.condition);
+            entries.insert_yaml_opt("uses", self.step_action.uses_yaml());
+            entries.insert_yaml_opt("with", self.step_action.with());
+            entries.insert_yaml_opt("run", self.step_action.run());
+            entries.insert_yaml_opt("working-directory", &self.options.working_directory);
+            entries.insert_yaml_opt("shell", &self.options.shell);
+            entries.insert_yaml_opt("id", &self.options.id);
+            entries.insert_yaml_opt("env", &self.options.env);
+            entries.insert_yaml_opt("continue-on-error", &self.options.continue_on_error);
+            entries.insert_yaml_opt("timeout-minutes", &self.options.timeout_minutes);
+            Yaml::Hash(entries)
+        }
+    }
+    fn collect_script_lines(script: Vec<StringLike>) -> StringLike {
+        let lines = script
+            .into_iter()
+            .map(|line| match line {
+                Either::A(expr) => expr.as_expression_string(),
+                Either::B(raw) => raw,
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        Either::B(lines)
+    }
+
+    /// Split the ``uses:`` scalar back into the bare ref and (if present) its pin comment,
+    /// undoing the `" # <comment>"` suffix `StepAction::uses_yaml` appends when `pin_comment` is
+    /// set.
+    fn split_uses_comment(s: &str) -> (String, Option<String>) {
+        match s.split_once(" # ") {
+            Some((uses, comment)) => (uses.to_string(), Some(comment.to_string())),
+            None => (s.to_string(), None),
+        }
+    }
+
+    fn parse_with_args(hash: &Hash) -> PyResult<WithArgs> {
+        let mut hash = hash.clone();
+        let args = hash_take(&mut hash, "args")
+            .map(|y| parse_string_like(&y, "with.args"))
+            .transpose()?;
+        let entrypoint = hash_take(&mut hash, "entrypoint")
+            .map(|y| parse_string_like(&y, "with.entrypoint"))
+            .transpose()?;
+        Ok(WithArgs {
+            options: if hash.is_empty() { None } else { Some(hash) },
+            args,
+            entrypoint,
+        })
+    }
+
+    /// Parse a single ``steps:`` entry from an existing workflow file, reconstructing the
+    /// `uses`/`run` exclusivity. `recommended_permissions` is always `None`, since it is
+    /// yamloom-specific metadata with no representation in rendered YAML.
+    fn step_from_hash(mut hash: Hash) -> PyResult<Step> {
+        let name = hash_take(&mut hash, "name")
+            .map(|y| parse_string_like(&y, "step.name"))
+            .transpose()?;
+        let condition = hash_take(&mut hash, "if")
+            .map(|y| parse_condition(&y, "step.if"))
+            .transpose()?;
+        let working_directory = hash_take(&mut hash, "working-directory")
+            .map(|y| parse_string_like(&y, "step.working-directory"))
+            .transpose()?;
+        let shell = hash_take(&mut hash, "shell")
+            .map(|y| yaml_scalar_to_string(&y, "step.shell"))
+            .transpose()?;
+        let id = hash_take(&mut hash, "id")
+            .map(|y| yaml_scalar_to_string(&y, "step.id"))
+            .transpose()?;
+        let env = hash_take(&mut hash, "env")
+            .map(|y| parse_string_map(&y, "step.env"))
+            .transpose()?;
+        let continue_on_error = hash_take(&mut hash, "continue-on-error")
+            .map(|y| parse_bool_like(&y, "step.continue-on-error"))
+            .transpose()?;
+        let timeout_minutes = hash_take(&mut hash, "timeout-minutes")
+            .map(|y| parse_int_like(&y, "step.timeout-minutes"))
+            .transpose()?;
+        let uses = hash_take(&mut hash, "uses");
+        let run = hash_take(&mut hash, "run");
+        let with = hash_take(&mut hash, "with");
+        let step_action = match (uses, run) {
+            (Some(_), Some(_)) => {
+                return Err(PyValueError::new_err(
+                    "Step cannot have both 'uses' and 'run'",
+                ));
+            }
+            (Some(uses), None) => {
+                let uses_str = yaml_scalar_to_string(&uses, "step.uses")?;
+                let (uses, pin_comment) = split_uses_comment(&uses_str);
+                let with = with
+                    .map(|y| parse_with_args(expect_hash(&y, "step.with")?))
+                    .transpose()?;
+                StepAction::Action {
+                    uses,
+                    pin_comment,
+                    with,
+                }
+            }
+            (None, Some(run)) => {
+                if with.is_some() {
+                    return Err(PyValueError::new_err(
+                        "Step with 'run' cannot have a 'with' key",
+                    ));
+                }
+                StepAction::Run(parse_string_like(&run, "step.run")?)
+            }
+            (None, None) => {
+                return Err(PyValueError::new_err(
+                    "Step must have either 'uses' or 'run'",
+                ));
+            }
+        };
+        reject_unknown_keys(&hash, "step")?;
+        Ok(Step {
+            name,
+            step_action,
+            options: StepOptions {
+                condition,
+                working_directory,
+                shell,
+                id,
+                env,
+                continue_on_error,
+                timeout_minutes,
+            },
+            recommended_permissions: None,
+        })
+    }
+
+    /// Generate a `Step` from a list of shell commands.
+    ///
+    /// Parameters
+    /// ----------
+    /// *script
+    ///     A list of shell commands to run in sequence. These will be concatenated with newlines
+    ///     and passed as the ``run`` key of the generated step. Note that this must not exceed
+    ///     21,000 characters in total.
+    /// name
+    ///     The name of the step to display on GitHub.
+    /// condition
+    ///     A boolean expression which must be met for the step to run. Note that this represents the ``if`` key in the actual YAML file.
+    /// working_directory
+    ///     Specifies the directory in which the script is run.
+    /// shell
+    ///     Used to override the default shell settings of the runner's OS (or `Job`/`Workflow` defaults).
+    /// id
+    ///     A unique identifier for the step which can be referenced in expressions.
+    /// env
+    ///     Used to specify environment variables for the step.
+    /// continue_on_error
+    ///     Prevents the job from failing if this step fails.
+    /// timeout_minutes
+    ///     The maximum number of minutes to let the step run before GitHub automatically cancels it (defaults to 360 if not specified).
+    /// on_untrusted_input
+    ///     What to do if a script line interpolates an attacker-controllable field (e.g.
+    ///     ``github.event.issue.title``) directly, the classic script-injection vector: either
+    ///     ``"warn"`` (print a warning and build anyway, the default) or ``"error"`` (raise).
+    ///     Either way, the recommended fix is to bind the value to an ``env:`` entry instead.
+    ///
+    #[pyfunction]
+    #[pyo3(
+        signature = (*script, name = None, condition = None, working_directory = None, shell = None, id = None, env = None, continue_on_error = None, timeout_minutes= None, on_untrusted_input = None)
+    )]
+    fn script(
+        script: &Bound<'_, PyTuple>,
+        name: Option<StringLike>,
+        condition: Option<Either<BooleanExpression, String>>,
+        working_directory: Option<StringLike>,
+        shell: Option<String>,
+        id: Option<String>,
+        env: Option<PyMap<String, StringLike>>,
+        continue_on_error: Option<BoolLike>,
+        timeout_minutes: Option<IntLike>,
+        on_untrusted_input: Option<String>,
+    ) -> PyResult<Step> {
+        let untrusted_severity = on_untrusted_input
+            .map(|s| TaintSeverity::from_str(&s))
+            .transpose()?
+            .unwrap_or(TaintSeverity::Warn);
+        let script = script
+            .iter()
+            .map(|item| item.extract::<StringLike>())
+            .collect::<PyResult<Vec<StringLike>>>()?;
+        for line in &script {
+            validate_string_like_for_untrusted_input(line, ALLOWED_STEP_RUN, untrusted_severity)?;
+        }
+        validate_step_options(
+            name.as_ref(),
+            condition.as_ref(),
+            working_directory.as_ref(),
+            env.as_ref(),
+            continue_on_error.as_ref(),
+            timeout_minutes.as_ref(),
+        )?;
+        let script = collect_script_lines(script);
+        Ok(Step {
+            name,
+            step_action: StepAction::Run(script),
+            options: StepOptions {
+                condition,
+                working_directory,
+                shell,
+                id,
+                env,
+                continue_on_error,
+                timeout_minutes,
+            },
+            recommended_permissions: None,
+        })
+    }
+
+    /// How strictly `make_action` (and the builders atop it) enforce that an action reference is
+    /// pinned to an immutable commit, guarding against a third-party action silently changing
+    /// behavior under a mutable tag or branch (the classic supply-chain attack on GitHub Actions).
+    #[derive(Clone, Copy)]
+    enum ActionPin {
+        /// Any `ref` is accepted, including mutable tags (`v4`) and branches (`main`).
+        Unpinned,
+        /// The `ref` must be a full 40-character commit SHA.
+        RequireSha,
+    }
+    impl FromStr for ActionPin {
+        type Err = PyErr;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.to_lowercase().as_str() {
+                "unpinned" => Ok(Self::Unpinned),
+                "require-sha" => Ok(Self::RequireSha),
+                _ => Err(PyValueError::new_err(
+                    "Invalid action pin policy, expected 'unpinned' or 'require-sha'",
+                )),
+            }
+        }
+    }
 
-    fn validate_string_like(value: &StringLike, allowed: Allowed) -> PyResult<()> {
-        if let Either::A(expr) = value {
-            expr.validate_allowed(allowed)?;
+    fn is_commit_sha(s: &str) -> bool {
+        s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    fn validate_action_pin(r#ref: Option<&str>, policy: ActionPin) -> PyResult<()> {
+        let ActionPin::RequireSha = policy else {
+            return Ok(());
+        };
+        match r#ref {
+            Some(r) if is_commit_sha(r) => Ok(()),
+            Some(r) => Err(PyRuntimeError::new_err(format!(
+                "Action ref '{r}' is not pinned to a full 40-character commit SHA; mutable refs like tags and branches are rejected under the 'require-sha' pin policy"
+            ))),
+            None => Err(PyRuntimeError::new_err(
+                "Action has no ref, but the 'require-sha' pin policy requires one pinned to a full 40-character commit SHA",
+            )),
         }
-        Ok(())
     }
 
-    fn validate_bool_like(value: &BoolLike, allowed: Allowed) -> PyResult<()> {
-        if let Either::A(expr) = value {
-            expr.validate_allowed(allowed)?;
+    fn make_action(
+        name: Option<StringLike>,
+        action: &str,
+        r#ref: Option<String>,
+        with_opts: Option<Hash>,
+        args: Option<StringLike>,
+        entrypoint: Option<StringLike>,
+        condition: Option<Either<BooleanExpression, String>>,
+        id: Option<String>,
+        env: Option<PyMap<String, StringLike>>,
+        continue_on_error: Option<BoolLike>,
+        timeout_minutes: Option<IntLike>,
+        recommended_permissions: Option<Permissions>,
+        pin_policy: Option<String>,
+        pin_comment: Option<String>,
+    ) -> PyResult<Step> {
+        validate_step_options(
+            name.as_ref(),
+            condition.as_ref(),
+            None,
+            env.as_ref(),
+            continue_on_error.as_ref(),
+            timeout_minutes.as_ref(),
+        )?;
+        if let Some(args) = &args {
+            validate_string_like(args, ALLOWED_STEP_WITH)?;
         }
-        Ok(())
+        if let Some(entrypoint) = &entrypoint {
+            validate_string_like(entrypoint, ALLOWED_STEP_WITH)?;
+        }
+        let pin_policy = pin_policy
+            .map(|s| ActionPin::from_str(&s))
+            .transpose()?
+            .unwrap_or(ActionPin::Unpinned);
+        validate_action_pin(r#ref.as_deref(), pin_policy)?;
+        let with_args = if with_opts.is_some() || args.is_some() || entrypoint.is_some() {
+            Some(WithArgs {
+                options: with_opts,
+                args,
+                entrypoint,
+            })
+        } else {
+            None
+        };
+        Ok(Step {
+            name,
+            step_action: StepAction::Action {
+                uses: format!(
+                    "{}{}",
+                    action,
+                    r#ref.map(|s| format!("@{s}")).unwrap_or_default()
+                ),
+                pin_comment,
+                with: with_args,
+            },
+            options: StepOptions {
+                condition,
+                working_directory: None,
+                shell: None,
+                id,
+                env,
+                continue_on_error,
+                timeout_minutes,
+            },
+            recommended_permissions,
+        })
     }
 
-    fn validate_int_like(value: &IntLike, allowed: Allowed) -> PyResult<()> {
-        if let Either::A(expr) = value {
-            expr.validate_allowed(allowed)?;
+    /// Generate a `Step` from a reusable unit of code called an action.
+    ///
+    /// Parameters
+    /// ----------
+    /// name
+    ///     The name of the step to display on GitHub.
+    /// action
+    ///     The location of the action's public GitHub repository (a string of the form {owner}/{repo}).
+    /// ref
+    ///     The branch, ref, or SHA of the action's repository to use. This is used to specify a specific version of an action.
+    /// with_opts
+    ///     A map of input parameters for the action. These are passed as the ``with`` key of the generated step.
+    /// args
+    ///     The inputs for a Docker container which are passed to the container's entrypoint. This
+    ///     is a subkey of the ``with`` key of the generated step.
+    /// entrypoint
+    ///     Overrides the Docker ENTRYPOINT in the action's Dockerfile or sets one if it was not
+    ///     specified. Accepts a single string defining the executable to run (note that this is
+    ///     different from Docker's ENTRYPOINT instruction which has both a shell and exec form).
+    ///     This is a subkey of the ``with`` key of the generated step.
+    /// condition
+    ///     A boolean expression which must be met for the step to run. Note that this represents the ``if`` key in the actual YAML file.
+    /// id
+    ///     A unique identifier for the step which can be referenced in expressions.
+    /// env
+    ///     Used to specify environment variables for the step.
+    /// continue_on_error
+    ///     Prevents the job from failing if this step fails.
+    /// timeout_minutes
+    ///     The maximum number of minutes to let the step run before GitHub automatically cancels it (defaults to 360 if not specified).
+    /// recommended_permissions
+    ///     Recommended permissions required to run this action.
+    /// pin_policy
+    ///     Either ``"unpinned"`` (the default, any ref is accepted) or ``"require-sha"`` to reject
+    ///     any ``ref`` that isn't a full 40-character commit SHA, so a mutable tag or branch (e.g.
+    ///     ``v4``, ``main``) can't let a third-party action silently change underneath the
+    ///     workflow. Use `live.resolve_action_ref` to look up the commit SHA for a human-friendly
+    ///     tag before pinning.
+    /// pin_comment
+    ///     An optional human-friendly label (e.g. the tag a SHA was resolved from) rendered as a
+    ///     trailing YAML comment after the ``uses`` line, so a SHA-pinned action stays readable.
+    ///
+    #[pyfunction]
+    #[pyo3(signature = (name, action, *, r#ref = None, with_opts = None, args = None, entrypoint = None, condition = None, id = None, env = None, continue_on_error = None, timeout_minutes = None, recommended_permissions = None, pin_policy = None, pin_comment = None))]
+    fn action(
+        name: Option<StringLike>,
+        action: &str,
+        r#ref: Option<String>,
+        with_opts: Option<Bound<PyDict>>,
+        args: Option<StringLike>,
+        entrypoint: Option<StringLike>,
+        condition: Option<Either<BooleanExpression, String>>,
+        id: Option<String>,
+        env: Option<PyMap<String, StringLike>>,
+        continue_on_error: Option<BoolLike>,
+        timeout_minutes: Option<IntLike>,
+        recommended_permissions: Option<Permissions>,
+        pin_policy: Option<String>,
+        pin_comment: Option<String>,
+    ) -> PyResult<Step> {
+        if let Some(with_opts) = &with_opts {
+            validate_with_opts(with_opts, ALLOWED_STEP_WITH)?;
         }
-        Ok(())
+        make_action(
+            name,
+            action,
+            r#ref,
+            with_opts.map(|d| d.try_as_hash()).transpose()?,
+            args,
+            entrypoint,
+            condition,
+            id,
+            env,
+            continue_on_error,
+            timeout_minutes,
+            recommended_permissions,
+            pin_policy,
+            pin_comment,
+        )
     }
 
-    fn validate_condition(
-        value: &Either<BooleanExpression, String>,
-        allowed: Allowed,
-    ) -> PyResult<()> {
-        if let Either::A(expr) = value {
-            expr.validate_allowed(allowed)?;
+    #[pyclass(extends=Step, subclass)]
+    struct ActionStep;
+    #[pymethods]
+    impl ActionStep {
+        #[new]
+        #[pyo3(signature = (name, action, *, r#ref = None, with_opts = None, args = None, entrypoint = None, condition = None, id = None, env = None, continue_on_error = None, timeout_minutes = None, recommended_permissions = None, pin_policy = None, pin_comment = None))]
+        fn new(
+            name: Option<StringLike>,
+            action: &str,
+            r#ref: Option<String>,
+            with_opts: Option<Bound<PyDict>>,
+            args: Option<StringLike>,
+            entrypoint: Option<StringLike>,
+            condition: Option<Either<BooleanExpression, String>>,
+            id: Option<String>,
+            env: Option<PyMap<String, StringLike>>,
+            continue_on_error: Option<BoolLike>,
+            timeout_minutes: Option<IntLike>,
+            recommended_permissions: Option<Permissions>,
+            pin_policy: Option<String>,
+            pin_comment: Option<String>,
+        ) -> PyResult<(Self, Step)> {
+            if let Some(with_opts) = &with_opts {
+                validate_with_opts(with_opts, ALLOWED_STEP_WITH)?;
+            }
+            let step = make_action(
+                name,
+                action,
+                r#ref,
+                with_opts.map(|d| d.try_as_hash()).transpose()?,
+                args,
+                entrypoint,
+                condition,
+                id,
+                env,
+                continue_on_error,
+                timeout_minutes,
+                recommended_permissions,
+                pin_policy,
+                pin_comment,
+            )?;
+            Ok((ActionStep, step))
         }
-        Ok(())
     }
 
-    fn validate_string_map(values: &PyMap<String, StringLike>, allowed: Allowed) -> PyResult<()> {
-        for (_, value) in values.iter() {
-            validate_string_like(value, allowed)?;
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum ReadWriteNonePermission {
+        Read,
+        Write,
+        None,
+    }
+    impl FromStr for ReadWriteNonePermission {
+        type Err = PyErr;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.to_lowercase().as_str() {
+                "read" => Ok(Self::Read),
+                "write" => Ok(Self::Write),
+                "none" => Ok(Self::None),
+                _ => Err(PyValueError::new_err("Invalid permission")),
+            }
+        }
+    }
+    impl Yamlable for &ReadWriteNonePermission {
+        fn as_yaml(&self) -> Yaml {
+            match self {
+                ReadWriteNonePermission::Read => "read",
+                ReadWriteNonePermission::Write => "write",
+                ReadWriteNonePermission::None => "none",
+            }
+            .as_yaml()
+        }
+    }
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum WriteNonePermission {
+        Write,
+        None,
+    }
+    impl FromStr for WriteNonePermission {
+        type Err = PyErr;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.to_lowercase().as_str() {
+                "write" => Ok(Self::Write),
+                "none" => Ok(Self::None),
+                _ => Err(PyValueError::new_err("Invalid permission")),
+            }
+        }
+    }
+    impl Yamlable for &WriteNonePermission {
+        fn as_yaml(&self) -> Yaml {
+            match self {
+                WriteNonePermission::Write => "write",
+                WriteNonePermission::None => "none",
+            }
+            .as_yaml()
+        }
+    }
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum ReadNonePermission {
+        Read,
+        None,
+    }
+    impl FromStr for ReadNonePermission {
+        type Err = PyErr;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.to_lowercase().as_str() {
+                "read" => Ok(Self::Read),
+                "none" => Ok(Self::None),
+                _ => Err(PyValueError::new_err("Invalid permission")),
+            }
+        }
+    }
+    impl Yamlable for &ReadNonePermission {
+        fn as_yaml(&self) -> Yaml {
+            match self {
+                ReadNonePermission::Read => "read",
+                ReadNonePermission::None => "none",
+            }
+            .as_yaml()
         }
-        Ok(())
     }
 
-    fn validate_string_vec(values: &[StringLike], allowed: Allowed) -> PyResult<()> {
-        for value in values {
-            validate_string_like(value, allowed)?;
+    #[derive(Clone)]
+    struct IndividualPermissions {
+        actions: Option<ReadWriteNonePermission>,
+        artifact_metadata: Option<ReadWriteNonePermission>,
+        attestations: Option<ReadWriteNonePermission>,
+        checks: Option<ReadWriteNonePermission>,
+        contents: Option<ReadWriteNonePermission>,
+        deployments: Option<ReadWriteNonePermission>,
+        id_token: Option<WriteNonePermission>,
+        issues: Option<ReadWriteNonePermission>,
+        models: Option<ReadNonePermission>,
+        discussions: Option<ReadWriteNonePermission>,
+        packages: Option<ReadWriteNonePermission>,
+        pages: Option<ReadWriteNonePermission>,
+        pull_requests: Option<ReadWriteNonePermission>,
+        security_events: Option<ReadWriteNonePermission>,
+        statuses: Option<ReadWriteNonePermission>,
+        /// Documents the concrete package names this scope's ``packages`` level is intended to
+        /// cover. GitHub's own schema has no notion of this, so it is purely informational,
+        /// rendered as an ``x-yamloom-allow`` extension field alongside the standard ``packages``
+        /// key. `None` means unscoped (the level applies to every package).
+        packages_allow: Option<Vec<String>>,
+        /// Same as `packages_allow`, but for the ``contents`` scope (e.g. specific repo paths).
+        contents_allow: Option<Vec<String>>,
+    }
+    impl IndividualPermissions {
+        fn is_empty(&self) -> bool {
+            self.actions.is_none()
+                && self.artifact_metadata.is_none()
+                && self.attestations.is_none()
+                && self.checks.is_none()
+                && self.contents.is_none()
+                && self.deployments.is_none()
+                && self.id_token.is_none()
+                && self.issues.is_none()
+                && self.models.is_none()
+                && self.discussions.is_none()
+                && self.packages.is_none()
+                && self.pages.is_none()
+                && self.pull_requests.is_none()
+                && self.security_events.is_none()
+                && self.statuses.is_none()
+                && self.packages_allow.is_none()
+                && self.contents_allow.is_none()
         }
-        Ok(())
     }
-
-    fn validate_runs_on(runs_on: &RunsOn) -> PyResult<()> {
-        match runs_on {
-            RunsOn::String(value) => validate_string_like(value, ALLOWED_JOB_RUNS_ON),
-            RunsOn::Array(values) => validate_string_vec(values, ALLOWED_JOB_RUNS_ON),
-            RunsOn::Spec(spec) => match &spec.options {
-                RunsOnSpecOptions::Group(group) => validate_string_like(group, ALLOWED_JOB_RUNS_ON),
-                RunsOnSpecOptions::Labels(labels) => {
-                    validate_string_like(labels, ALLOWED_JOB_RUNS_ON)
+    #[derive(Clone)]
+    enum PermissionsOptions {
+        Individual(IndividualPermissions),
+        ReadAll,
+        WriteAll,
+        None,
+    }
+    #[pyclass]
+    #[derive(Clone)]
+    struct Permissions {
+        options: PermissionsOptions,
+    }
+    #[pymethods]
+    impl Permissions {
+        /// strict
+        ///     If True, reject a redundant spelled-out shorthand: every scope explicitly set to
+        ///     exactly what `read_all`/`write_all` would already produce; the error names the
+        ///     shorthand to use. A value a scope does not accept (e.g. `read` for `id_token`) is
+        ///     rejected whether or not `strict` is set, with an error naming the scope and its
+        ///     allowed values.
+        #[new]
+        #[pyo3(signature= (actions=None, artifact_metadata=None, attestations=None, checks=None, contents=None, deployments=None, id_token=None, issues=None, models=None, discussions=None, packages=None, pages=None, pull_requests=None, security_events=None, statuses=None, packages_allow=None, contents_allow=None, strict=false))]
+        #[allow(clippy::too_many_arguments)]
+        fn new(
+            actions: Option<String>,
+            artifact_metadata: Option<String>,
+            attestations: Option<String>,
+            checks: Option<String>,
+            contents: Option<String>,
+            deployments: Option<String>,
+            id_token: Option<String>,
+            issues: Option<String>,
+            models: Option<String>,
+            discussions: Option<String>,
+            packages: Option<String>,
+            pages: Option<String>,
+            pull_requests: Option<String>,
+            security_events: Option<String>,
+            statuses: Option<String>,
+            packages_allow: Option<Vec<String>>,
+            contents_allow: Option<Vec<String>>,
+            strict: bool,
+        ) -> PyResult<Self> {
+            let indiv = IndividualPermissions {
+                actions: parse_rw_scope("actions", actions)?,
+                artifact_metadata: parse_rw_scope("artifact_metadata", artifact_metadata)?,
+                attestations: parse_rw_scope("attestations", attestations)?,
+                checks: parse_rw_scope("checks", checks)?,
+                contents: parse_rw_scope("contents", contents)?,
+                deployments: parse_rw_scope("deployments", deployments)?,
+                id_token: parse_write_scope("id_token", id_token)?,
+                issues: parse_rw_scope("issues", issues)?,
+                models: parse_read_scope("models", models)?,
+                discussions: parse_rw_scope("discussions", discussions)?,
+                packages: parse_rw_scope("packages", packages)?,
+                pages: parse_rw_scope("pages", pages)?,
+                pull_requests: parse_rw_scope("pull_requests", pull_requests)?,
+                security_events: parse_rw_scope("security_events", security_events)?,
+                statuses: parse_rw_scope("statuses", statuses)?,
+                packages_allow,
+                contents_allow,
+            };
+            if strict {
+                if individual_eq(&indiv, &individual_from_permissions(&Permissions::read_all())) {
+                    return Err(PyValueError::new_err(
+                        "Every scope is explicitly set to exactly what 'read_all' would produce; \
+                         use Permissions.read_all() instead of spelling it out scope-by-scope",
+                    ));
                 }
-                RunsOnSpecOptions::GroupAndLabels(group, labels) => {
-                    validate_string_like(group, ALLOWED_JOB_RUNS_ON)?;
-                    validate_string_like(labels, ALLOWED_JOB_RUNS_ON)
+                if individual_eq(&indiv, &individual_from_permissions(&Permissions::write_all())) {
+                    return Err(PyValueError::new_err(
+                        "Every scope is explicitly set to exactly what 'write_all' would produce; \
+                         use Permissions.write_all() instead of spelling it out scope-by-scope",
+                    ));
                 }
-            },
+            }
+            Ok(Self {
+                options: PermissionsOptions::Individual(indiv),
+            })
         }
-    }
-
-    fn validate_with_opts(opts: &Bound<'_, PyDict>, allowed: Allowed) -> PyResult<()> {
-        for (_, value) in opts.iter() {
-            if let Ok(expr) = value.extract::<BooleanExpression>() {
-                expr.validate_allowed(allowed)?;
-            } else if let Ok(expr) = value.extract::<StringExpression>() {
-                expr.validate_allowed(allowed)?;
-            } else if let Ok(expr) = value.extract::<NumberExpression>() {
-                expr.validate_allowed(allowed)?;
-            } else if let Ok(expr) = value.extract::<ArrayExpression>() {
-                expr.validate_allowed(allowed)?;
-            } else if let Ok(expr) = value.extract::<ObjectExpression>() {
-                expr.validate_allowed(allowed)?;
+        #[staticmethod]
+        fn none() -> Self {
+            Self {
+                options: PermissionsOptions::None,
             }
         }
-        Ok(())
-    }
-
-    fn validate_step_options(
-        name: Option<&StringLike>,
-        condition: Option<&Either<BooleanExpression, String>>,
-        working_directory: Option<&StringLike>,
-        env: Option<&PyMap<String, StringLike>>,
-        continue_on_error: Option<&BoolLike>,
-        timeout_minutes: Option<&IntLike>,
-    ) -> PyResult<()> {
-        if let Some(name) = name {
-            validate_string_like(name, ALLOWED_STEP_NAME)?;
+        #[staticmethod]
+        fn read_all() -> Self {
+            Self {
+                options: PermissionsOptions::ReadAll,
+            }
         }
-        if let Some(condition) = condition {
-            validate_condition(condition, ALLOWED_STEP_IF)?;
+        #[staticmethod]
+        fn write_all() -> Self {
+            Self {
+                options: PermissionsOptions::WriteAll,
+            }
         }
-        if let Some(working_directory) = working_directory {
-            validate_string_like(working_directory, ALLOWED_STEP_WORKING_DIRECTORY)?;
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
         }
-        if let Some(env) = env {
-            validate_string_map(env, ALLOWED_STEP_ENV)?;
+
+        /// Check whether this `Permissions` grants at least as much access as `required` on every
+        /// scope, using the same `Read`/`Write`/`None` ordering as `merge_permissions`. Useful for
+        /// asserting that a job's declared `permissions:` actually covers the union of
+        /// `recommended_permissions` from all of its `ActionStep`s.
+        fn satisfies(&self, required: &Permissions) -> bool {
+            individual_satisfies(
+                &individual_from_permissions(self),
+                &individual_from_permissions(required),
+            )
         }
-        if let Some(continue_on_error) = continue_on_error {
-            validate_bool_like(continue_on_error, ALLOWED_STEP_CONTINUE_ON_ERROR)?;
+
+        /// Look up the granted level (``"read"``, ``"write"``, or ``"none"``) of a single
+        /// permission scope by its attribute name (e.g. ``"contents"``, ``"id_token"``), the same
+        /// names accepted by `Permissions.__new__`.
+        fn query(&self, scope: &str) -> PyResult<String> {
+            individual_from_permissions(self).scope_label(scope)
         }
-        if let Some(timeout_minutes) = timeout_minutes {
-            validate_int_like(timeout_minutes, ALLOWED_STEP_TIMEOUT_MINUTES)?;
+
+        /// Return the names of every scope where this `Permissions` grants strictly more access
+        /// than `other`, so an over-broad declaration (e.g. `write-all` where only `contents: read`
+        /// is needed) can be flagged.
+        fn diff(&self, other: &Permissions) -> Vec<String> {
+            diff_individual(
+                &individual_from_permissions(self),
+                &individual_from_permissions(other),
+            )
+        }
+
+        /// Parse a ``permissions:`` value from an existing workflow file: either the
+        /// ``"read-all"``/``"write-all"`` shorthand, an empty mapping (``none``), or a mapping of
+        /// individual scope names to ``"read"``/``"write"``/``"none"``, including the
+        /// ``x-yamloom-allow`` extension this crate itself emits for ``packages_allow``/
+        /// ``contents_allow``.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            permissions_from_yaml(&parse_yaml_document(yaml)?)
+        }
+    }
+    /// Parse a ``permissions:`` node already extracted from a larger document (e.g. a job's
+    /// `permissions` key), shared between `Permissions::from_yaml` and `Job::from_yaml`.
+    fn permissions_from_yaml(yaml: &Yaml) -> PyResult<Permissions> {
+        match yaml_scalar_to_string(yaml, "permissions") {
+            Ok(s) if s == "read-all" => return Ok(Permissions::read_all()),
+            Ok(s) if s == "write-all" => return Ok(Permissions::write_all()),
+            _ => {}
+        }
+        let mut hash = expect_hash(yaml, "permissions")?.clone();
+        if hash.is_empty() {
+            return Ok(Permissions::none());
+        }
+        let (packages_allow, contents_allow) = hash_take(&mut hash, "x-yamloom-allow")
+            .map(|y| {
+                let mut allow = expect_hash(&y, "permissions.x-yamloom-allow")?.clone();
+                let packages_allow = hash_take(&mut allow, "packages")
+                    .map(|y| {
+                        y.as_vec()
+                            .ok_or_else(|| {
+                                PyValueError::new_err(
+                                    "Expected 'x-yamloom-allow.packages' to be a list",
+                                )
+                            })?
+                            .iter()
+                            .map(|v| yaml_scalar_to_string(v, "x-yamloom-allow.packages"))
+                            .collect::<PyResult<Vec<_>>>()
+                    })
+                    .transpose()?;
+                let contents_allow = hash_take(&mut allow, "contents")
+                    .map(|y| {
+                        y.as_vec()
+                            .ok_or_else(|| {
+                                PyValueError::new_err(
+                                    "Expected 'x-yamloom-allow.contents' to be a list",
+                                )
+                            })?
+                            .iter()
+                            .map(|v| yaml_scalar_to_string(v, "x-yamloom-allow.contents"))
+                            .collect::<PyResult<Vec<_>>>()
+                    })
+                    .transpose()?;
+                reject_unknown_keys(&allow, "permissions.x-yamloom-allow")?;
+                Ok::<_, PyErr>((packages_allow, contents_allow))
+            })
+            .transpose()?
+            .unwrap_or((None, None));
+        fn take_scope(hash: &mut Hash, key: &str) -> PyResult<Option<String>> {
+            hash.remove(&Yaml::String(key.to_string()))
+                .map(|y| yaml_scalar_to_string(&y, key))
+                .transpose()
+        }
+        let indiv = IndividualPermissions {
+            actions: parse_rw_scope("actions", take_scope(&mut hash, "actions")?)?,
+            artifact_metadata: parse_rw_scope(
+                "artifact_metadata",
+                take_scope(&mut hash, "artifact-metadata")?,
+            )?,
+            attestations: parse_rw_scope(
+                "attestations",
+                take_scope(&mut hash, "attestations")?,
+            )?,
+            checks: parse_rw_scope("checks", take_scope(&mut hash, "checks")?)?,
+            contents: parse_rw_scope("contents", take_scope(&mut hash, "contents")?)?,
+            deployments: parse_rw_scope("deployments", take_scope(&mut hash, "deployments")?)?,
+            id_token: parse_write_scope("id_token", take_scope(&mut hash, "id-token")?)?,
+            issues: parse_rw_scope("issues", take_scope(&mut hash, "issues")?)?,
+            models: parse_read_scope("models", take_scope(&mut hash, "models")?)?,
+            discussions: parse_rw_scope("discussions", take_scope(&mut hash, "discussion")?)?,
+            packages: parse_rw_scope("packages", take_scope(&mut hash, "packages")?)?,
+            pages: parse_rw_scope("pages", take_scope(&mut hash, "pages")?)?,
+            pull_requests: parse_rw_scope(
+                "pull_requests",
+                take_scope(&mut hash, "pull-requests")?,
+            )?,
+            security_events: parse_rw_scope(
+                "security_events",
+                take_scope(&mut hash, "security-events")?,
+            )?,
+            statuses: parse_rw_scope("statuses", take_scope(&mut hash, "statuses")?)?,
+            packages_allow,
+            contents_allow,
+        };
+        reject_unknown_keys(&hash, "permissions")?;
+        Ok(Permissions {
+            options: PermissionsOptions::Individual(indiv),
+        })
+    }
+    impl Yamlable for &Permissions {
+        fn as_yaml(&self) -> Yaml {
+            match &self.options {
+                PermissionsOptions::Individual(indiv_perms) => {
+                    let mut permissions = Hash::new();
+                    permissions.insert_yaml_opt("actions", &indiv_perms.actions);
+                    permissions
+                        .insert_yaml_opt("artifact-metadata", &indiv_perms.artifact_metadata);
+                    permissions.insert_yaml_opt("attestations", &indiv_perms.attestations);
+                    permissions.insert_yaml_opt("checks", &indiv_perms.checks);
+                    permissions.insert_yaml_opt("contents", &indiv_perms.contents);
+                    permissions.insert_yaml_opt("deployments", &indiv_perms.deployments);
+                    permissions.insert_yaml_opt("id-token", &indiv_perms.id_token);
+                    permissions.insert_yaml_opt("issues", &indiv_perms.issues);
+                    permissions.insert_yaml_opt("models", &indiv_perms.models);
+                    permissions.insert_yaml_opt("discussion", &indiv_perms.discussions);
+                    permissions.insert_yaml_opt("packages", &indiv_perms.packages);
+                    permissions.insert_yaml_opt("pages", &indiv_perms.pages);
+                    permissions.insert_yaml_opt("pull-requests", &indiv_perms.pull_requests);
+                    permissions.insert_yaml_opt("security-events", &indiv_perms.security_events);
+                    permissions.insert_yaml_opt("statuses", &indiv_perms.statuses);
+                    if indiv_perms.packages_allow.is_some() || indiv_perms.contents_allow.is_some() {
+                        let mut allow = Hash::new();
+                        allow.insert_yaml_opt("packages", &indiv_perms.packages_allow);
+                        allow.insert_yaml_opt("contents", &indiv_perms.contents_allow);
+                        permissions.insert_yaml("x-yamloom-allow", Yaml::Hash(allow));
+                    }
+                    Yaml::Hash(permissions)
+                }
+                PermissionsOptions::ReadAll => "read-all".as_yaml(),
+                PermissionsOptions::WriteAll => "write-all".as_yaml(),
+                PermissionsOptions::None => Yaml::Hash(Hash::new()), // TODO: test
+            }
         }
-        Ok(())
     }
 
-    fn validate_container_for_job(container: &Container) -> PyResult<()> {
-        validate_string_like(&container.image, ALLOWED_JOB_CONTAINER_IMAGE)?;
-        if let Some(options) = &container.options {
-            validate_string_like(options, ALLOWED_JOB_CONTAINER)?;
+    fn max_read_write_none(
+        left: ReadWriteNonePermission,
+        right: ReadWriteNonePermission,
+    ) -> ReadWriteNonePermission {
+        match (left, right) {
+            (ReadWriteNonePermission::Write, _) | (_, ReadWriteNonePermission::Write) => {
+                ReadWriteNonePermission::Write
+            }
+            (ReadWriteNonePermission::Read, _) | (_, ReadWriteNonePermission::Read) => {
+                ReadWriteNonePermission::Read
+            }
+            _ => ReadWriteNonePermission::None,
         }
-        if let Some(volumes) = &container.volumes {
-            validate_string_vec(volumes, ALLOWED_JOB_CONTAINER)?;
+    }
+    fn max_write_none(
+        left: WriteNonePermission,
+        right: WriteNonePermission,
+    ) -> WriteNonePermission {
+        match (left, right) {
+            (WriteNonePermission::Write, _) | (_, WriteNonePermission::Write) => {
+                WriteNonePermission::Write
+            }
+            _ => WriteNonePermission::None,
         }
-        if let Some(ports) = &container.ports {
-            for port in ports {
-                validate_int_like(port, ALLOWED_JOB_CONTAINER)?;
+    }
+    fn max_read_none(left: ReadNonePermission, right: ReadNonePermission) -> ReadNonePermission {
+        match (left, right) {
+            (ReadNonePermission::Read, _) | (_, ReadNonePermission::Read) => {
+                ReadNonePermission::Read
             }
+            _ => ReadNonePermission::None,
         }
-        if let Some(credentials) = &container.credentials {
-            validate_string_like(&credentials.username, ALLOWED_JOB_CONTAINER_CREDENTIALS)?;
-            validate_string_like(&credentials.password, ALLOWED_JOB_CONTAINER_CREDENTIALS)?;
+    }
+    fn merge_rw_opt(
+        left: Option<ReadWriteNonePermission>,
+        right: Option<ReadWriteNonePermission>,
+    ) -> Option<ReadWriteNonePermission> {
+        match (left, right) {
+            (None, None) => None,
+            (Some(value), None) | (None, Some(value)) => Some(value),
+            (Some(left), Some(right)) => Some(max_read_write_none(left, right)),
         }
-        if let Some(env) = &container.env {
-            validate_string_map(env, ALLOWED_JOB_CONTAINER_ENV)?;
+    }
+    fn merge_write_opt(
+        left: Option<WriteNonePermission>,
+        right: Option<WriteNonePermission>,
+    ) -> Option<WriteNonePermission> {
+        match (left, right) {
+            (None, None) => None,
+            (Some(value), None) | (None, Some(value)) => Some(value),
+            (Some(left), Some(right)) => Some(max_write_none(left, right)),
+        }
+    }
+    fn merge_read_opt(
+        left: Option<ReadNonePermission>,
+        right: Option<ReadNonePermission>,
+    ) -> Option<ReadNonePermission> {
+        match (left, right) {
+            (None, None) => None,
+            (Some(value), None) | (None, Some(value)) => Some(value),
+            (Some(left), Some(right)) => Some(max_read_none(left, right)),
         }
-        Ok(())
     }
-
-    fn validate_container_for_service(container: &Container) -> PyResult<()> {
-        validate_string_like(&container.image, ALLOWED_JOB_SERVICES)?;
-        if let Some(options) = &container.options {
-            validate_string_like(options, ALLOWED_JOB_SERVICES)?;
-        }
-        if let Some(volumes) = &container.volumes {
-            validate_string_vec(volumes, ALLOWED_JOB_SERVICES)?;
+    fn merge_individual(
+        left: &IndividualPermissions,
+        right: &IndividualPermissions,
+    ) -> IndividualPermissions {
+        IndividualPermissions {
+            actions: merge_rw_opt(left.actions, right.actions),
+            artifact_metadata: merge_rw_opt(left.artifact_metadata, right.artifact_metadata),
+            attestations: merge_rw_opt(left.attestations, right.attestations),
+            checks: merge_rw_opt(left.checks, right.checks),
+            contents: merge_rw_opt(left.contents, right.contents),
+            deployments: merge_rw_opt(left.deployments, right.deployments),
+            id_token: merge_write_opt(left.id_token, right.id_token),
+            issues: merge_rw_opt(left.issues, right.issues),
+            models: merge_read_opt(left.models, right.models),
+            discussions: merge_rw_opt(left.discussions, right.discussions),
+            packages: merge_rw_opt(left.packages, right.packages),
+            pages: merge_rw_opt(left.pages, right.pages),
+            pull_requests: merge_rw_opt(left.pull_requests, right.pull_requests),
+            security_events: merge_rw_opt(left.security_events, right.security_events),
+            statuses: merge_rw_opt(left.statuses, right.statuses),
+            packages_allow: merge_allowlist(&left.packages_allow, &right.packages_allow),
+            contents_allow: merge_allowlist(&left.contents_allow, &right.contents_allow),
         }
-        if let Some(ports) = &container.ports {
-            for port in ports {
-                validate_int_like(port, ALLOWED_JOB_SERVICES)?;
+    }
+    /// Union two allowlists when both sides scope the same resource; if either side has no
+    /// allowlist at all (i.e. is unscoped for this resource), the merge widens to unscoped too,
+    /// since an unscoped side could touch anything.
+    fn merge_allowlist(left: &Option<Vec<String>>, right: &Option<Vec<String>>) -> Option<Vec<String>> {
+        let (left, right) = (left.as_ref()?, right.as_ref()?);
+        let mut union = left.clone();
+        for item in right {
+            if !union.contains(item) {
+                union.push(item.clone());
             }
         }
-        if let Some(credentials) = &container.credentials {
-            validate_string_like(&credentials.username, ALLOWED_JOB_SERVICES_CREDENTIALS)?;
-            validate_string_like(&credentials.password, ALLOWED_JOB_SERVICES_CREDENTIALS)?;
+        Some(union)
+    }
+    fn individual_from_permissions(permissions: &Permissions) -> IndividualPermissions {
+        match &permissions.options {
+            PermissionsOptions::Individual(indiv) => indiv.clone(),
+            PermissionsOptions::None => IndividualPermissions {
+                actions: None,
+                artifact_metadata: None,
+                attestations: None,
+                checks: None,
+                contents: None,
+                deployments: None,
+                id_token: None,
+                issues: None,
+                models: None,
+                discussions: None,
+                packages: None,
+                pages: None,
+                pull_requests: None,
+                security_events: None,
+                statuses: None,
+                packages_allow: None,
+                contents_allow: None,
+            },
+            PermissionsOptions::ReadAll => IndividualPermissions {
+                actions: Some(ReadWriteNonePermission::Read),
+                artifact_metadata: Some(ReadWriteNonePermission::Read),
+                attestations: Some(ReadWriteNonePermission::Read),
+                checks: Some(ReadWriteNonePermission::Read),
+                contents: Some(ReadWriteNonePermission::Read),
+                deployments: Some(ReadWriteNonePermission::Read),
+                id_token: Some(WriteNonePermission::None),
+                issues: Some(ReadWriteNonePermission::Read),
+                models: Some(ReadNonePermission::Read),
+                discussions: Some(ReadWriteNonePermission::Read),
+                packages: Some(ReadWriteNonePermission::Read),
+                pages: Some(ReadWriteNonePermission::Read),
+                pull_requests: Some(ReadWriteNonePermission::Read),
+                security_events: Some(ReadWriteNonePermission::Read),
+                statuses: Some(ReadWriteNonePermission::Read),
+                packages_allow: None,
+                contents_allow: None,
+            },
+            PermissionsOptions::WriteAll => IndividualPermissions {
+                actions: Some(ReadWriteNonePermission::Write),
+                artifact_metadata: Some(ReadWriteNonePermission::Write),
+                attestations: Some(ReadWriteNonePermission::Write),
+                checks: Some(ReadWriteNonePermission::Write),
+                contents: Some(ReadWriteNonePermission::Write),
+                deployments: Some(ReadWriteNonePermission::Write),
+                id_token: Some(WriteNonePermission::Write),
+                issues: Some(ReadWriteNonePermission::Write),
+                models: Some(ReadNonePermission::Read),
+                discussions: Some(ReadWriteNonePermission::Write),
+                packages: Some(ReadWriteNonePermission::Write),
+                pages: Some(ReadWriteNonePermission::Write),
+                pull_requests: Some(ReadWriteNonePermission::Write),
+                security_events: Some(ReadWriteNonePermission::Write),
+                statuses: Some(ReadWriteNonePermission::Write),
+                packages_allow: None,
+                contents_allow: None,
+            },
         }
-        if let Some(env) = &container.env {
-            validate_string_map(env, ALLOWED_JOB_SERVICES_ENV)?;
+    }
+    fn merge_permissions(left: &Permissions, right: &Permissions) -> Permissions {
+        match (&left.options, &right.options) {
+            (PermissionsOptions::WriteAll, _) | (_, PermissionsOptions::WriteAll) => Permissions {
+                options: PermissionsOptions::WriteAll,
+            },
+            (PermissionsOptions::None, PermissionsOptions::None) => Permissions {
+                options: PermissionsOptions::None,
+            },
+            (
+                PermissionsOptions::ReadAll,
+                PermissionsOptions::None | PermissionsOptions::ReadAll,
+            )
+            | (PermissionsOptions::None, PermissionsOptions::ReadAll) => Permissions {
+                options: PermissionsOptions::ReadAll,
+            },
+            _ => Permissions {
+                options: PermissionsOptions::Individual(merge_individual(
+                    &individual_from_permissions(left),
+                    &individual_from_permissions(right),
+                )),
+            },
         }
-        Ok(())
     }
-
-    fn validate_concurrency(concurrency: &Concurrency, allowed: Allowed) -> PyResult<()> {
-        validate_string_like(&concurrency.group, allowed)?;
-        if let Some(cancel_in_progress) = &concurrency.cancel_in_progress {
-            validate_bool_like(cancel_in_progress, allowed)?;
+    fn is_empty_individual_permissions(permissions: &Permissions) -> bool {
+        match &permissions.options {
+            PermissionsOptions::Individual(indiv) => indiv.is_empty(),
+            _ => false,
         }
-        Ok(())
     }
 
-    fn validate_environment(environment: &Environment) -> PyResult<()> {
-        validate_string_like(&environment.name, ALLOWED_JOB_ENVIRONMENT)?;
-        if let Some(url) = &environment.url {
-            validate_string_like(url, ALLOWED_JOB_ENVIRONMENT_URL)?;
+    /// Fold every step's `recommended_permissions` into the tightest `Permissions` that covers
+    /// all of them, via repeated `merge_permissions`. Returns `None` if no step recommends any
+    /// permissions at all, distinguishing "nothing to minimize" from `Permissions::none()`.
+    fn minimal_permissions_from_steps(steps: &[Step]) -> Option<Permissions> {
+        let mut merged: Option<Permissions> = None;
+        for step in steps {
+            if let Some(step_permissions) = &step.recommended_permissions {
+                merged = Some(match &merged {
+                    Some(current) => merge_permissions(current, step_permissions),
+                    None => step_permissions.clone(),
+                });
+            }
         }
-        Ok(())
-    }
-    impl TryYamlable for Bound<'_, PyAny> {
-        fn try_as_yaml(&self) -> PyResult<Yaml> {
-            if self.is_none() {
-                Ok(Yaml::Null)
-            } else if let Ok(e) = self.extract::<StringExpression>() {
-                Ok((&e).as_yaml())
-            } else if let Ok(e) = self.extract::<BooleanExpression>() {
-                Ok((&e).as_yaml())
-            } else if let Ok(e) = self.extract::<NumberExpression>() {
-                Ok((&e).as_yaml())
-            } else if self.is_instance_of::<PyBool>() {
-                Ok(self.extract::<bool>()?.as_yaml())
-            } else if self.is_instance_of::<PyInt>() {
-                Ok(self.extract::<i64>()?.as_yaml())
-            } else if self.is_instance_of::<PyFloat>() {
-                Ok(self.extract::<f64>()?.as_yaml())
-            } else if self.is_instance_of::<PyString>() {
-                Ok(self.extract::<String>()?.as_yaml())
-            } else if let Ok(list) = self.cast::<PyList>() {
-                Ok(Yaml::Array(list.try_as_array()?))
-            } else if let Ok(dict) = self.cast::<PyDict>() {
-                Ok(Yaml::Hash(dict.try_as_hash()?))
+        merged.map(|merged| {
+            if is_empty_individual_permissions(&merged) {
+                Permissions::none()
             } else {
-                Err(PyValueError::new_err("Invalid value"))
+                merged
             }
-        }
+        })
     }
 
-    impl TryHash for Bound<'_, PyDict> {
-        fn try_as_hash(&self) -> PyResult<Hash> {
-            let mut dict_internals = Hash::new();
-            for (key, entry) in self.iter() {
-                if let Ok(key) = key.extract::<String>() {
-                    dict_internals.insert_yaml(key, entry.try_as_yaml()?);
-                } else {
-                    return Err(PyValueError::new_err("Invalid key"));
-                }
-            }
-            Ok(dict_internals)
+    fn parse_rw_scope(
+        scope: &str,
+        value: Option<String>,
+    ) -> PyResult<Option<ReadWriteNonePermission>> {
+        value
+            .map(|s| {
+                s.parse().map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "Invalid value '{s}' for permission scope '{scope}'; expected one of \
+                         'read', 'write', 'none'"
+                    ))
+                })
+            })
+            .transpose()
+    }
+    fn parse_write_scope(scope: &str, value: Option<String>) -> PyResult<Option<WriteNonePermission>> {
+        value
+            .map(|s| {
+                s.parse().map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "Invalid value '{s}' for permission scope '{scope}'; expected one of \
+                         'write', 'none'"
+                    ))
+                })
+            })
+            .transpose()
+    }
+    fn parse_read_scope(scope: &str, value: Option<String>) -> PyResult<Option<ReadNonePermission>> {
+        value
+            .map(|s| {
+                s.parse().map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "Invalid value '{s}' for permission scope '{scope}'; expected one of \
+                         'read', 'none'"
+                    ))
+                })
+            })
+            .transpose()
+    }
+    /// Whether two `IndividualPermissions` declare the same level on every scope (ignoring the
+    /// documentation-only `packages_allow`/`contents_allow` allowlists), used to detect when a
+    /// user has spelled out `read_all`/`write_all` scope-by-scope instead of using the shorthand.
+    fn individual_eq(a: &IndividualPermissions, b: &IndividualPermissions) -> bool {
+        a.actions == b.actions
+            && a.artifact_metadata == b.artifact_metadata
+            && a.attestations == b.attestations
+            && a.checks == b.checks
+            && a.contents == b.contents
+            && a.deployments == b.deployments
+            && a.id_token == b.id_token
+            && a.issues == b.issues
+            && a.models == b.models
+            && a.discussions == b.discussions
+            && a.packages == b.packages
+            && a.pages == b.pages
+            && a.pull_requests == b.pull_requests
+            && a.security_events == b.security_events
+            && a.statuses == b.statuses
+    }
+    fn rw_label(level: Option<ReadWriteNonePermission>) -> &'static str {
+        match level.unwrap_or(ReadWriteNonePermission::None) {
+            ReadWriteNonePermission::Read => "read",
+            ReadWriteNonePermission::Write => "write",
+            ReadWriteNonePermission::None => "none",
+        }
+    }
+    fn write_label(level: Option<WriteNonePermission>) -> &'static str {
+        match level.unwrap_or(WriteNonePermission::None) {
+            WriteNonePermission::Write => "write",
+            WriteNonePermission::None => "none",
+        }
+    }
+    fn read_label(level: Option<ReadNonePermission>) -> &'static str {
+        match level.unwrap_or(ReadNonePermission::None) {
+            ReadNonePermission::Read => "read",
+            ReadNonePermission::None => "none",
+        }
+    }
+    fn rw_satisfies(
+        granted: Option<ReadWriteNonePermission>,
+        required: Option<ReadWriteNonePermission>,
+    ) -> bool {
+        let granted = granted.unwrap_or(ReadWriteNonePermission::None);
+        let required = required.unwrap_or(ReadWriteNonePermission::None);
+        max_read_write_none(granted, required) == granted
+    }
+    fn write_satisfies(
+        granted: Option<WriteNonePermission>,
+        required: Option<WriteNonePermission>,
+    ) -> bool {
+        let granted = granted.unwrap_or(WriteNonePermission::None);
+        let required = required.unwrap_or(WriteNonePermission::None);
+        max_write_none(granted, required) == granted
+    }
+    fn read_satisfies(granted: Option<ReadNonePermission>, required: Option<ReadNonePermission>) -> bool {
+        let granted = granted.unwrap_or(ReadNonePermission::None);
+        let required = required.unwrap_or(ReadNonePermission::None);
+        max_read_none(granted, required) == granted
+    }
+    fn individual_satisfies(
+        granted: &IndividualPermissions,
+        required: &IndividualPermissions,
+    ) -> bool {
+        rw_satisfies(granted.actions, required.actions)
+            && rw_satisfies(granted.artifact_metadata, required.artifact_metadata)
+            && rw_satisfies(granted.attestations, required.attestations)
+            && rw_satisfies(granted.checks, required.checks)
+            && rw_satisfies(granted.contents, required.contents)
+            && rw_satisfies(granted.deployments, required.deployments)
+            && write_satisfies(granted.id_token, required.id_token)
+            && rw_satisfies(granted.issues, required.issues)
+            && read_satisfies(granted.models, required.models)
+            && rw_satisfies(granted.discussions, required.discussions)
+            && rw_satisfies(granted.packages, required.packages)
+            && rw_satisfies(granted.pages, required.pages)
+            && rw_satisfies(granted.pull_requests, required.pull_requests)
+            && rw_satisfies(granted.security_events, required.security_events)
+            && rw_satisfies(granted.statuses, required.statuses)
+    }
+    fn push_rw_diff(
+        scopes: &mut Vec<String>,
+        name: &str,
+        mine: Option<ReadWriteNonePermission>,
+        theirs: Option<ReadWriteNonePermission>,
+    ) {
+        if rw_satisfies(mine, theirs) && !rw_satisfies(theirs, mine) {
+            scopes.push(name.to_string());
+        }
+    }
+    fn diff_individual(mine: &IndividualPermissions, theirs: &IndividualPermissions) -> Vec<String> {
+        let mut scopes = Vec::new();
+        push_rw_diff(&mut scopes, "actions", mine.actions, theirs.actions);
+        push_rw_diff(
+            &mut scopes,
+            "artifact_metadata",
+            mine.artifact_metadata,
+            theirs.artifact_metadata,
+        );
+        push_rw_diff(
+            &mut scopes,
+            "attestations",
+            mine.attestations,
+            theirs.attestations,
+        );
+        push_rw_diff(&mut scopes, "checks", mine.checks, theirs.checks);
+        push_rw_diff(&mut scopes, "contents", mine.contents, theirs.contents);
+        push_rw_diff(
+            &mut scopes,
+            "deployments",
+            mine.deployments,
+            theirs.deployments,
+        );
+        push_rw_diff(&mut scopes, "issues", mine.issues, theirs.issues);
+        push_rw_diff(
+            &mut scopes,
+            "discussions",
+            mine.discussions,
+            theirs.discussions,
+        );
+        push_rw_diff(&mut scopes, "packages", mine.packages, theirs.packages);
+        push_rw_diff(&mut scopes, "pages", mine.pages, theirs.pages);
+        push_rw_diff(
+            &mut scopes,
+            "pull_requests",
+            mine.pull_requests,
+            theirs.pull_requests,
+        );
+        push_rw_diff(
+            &mut scopes,
+            "security_events",
+            mine.security_events,
+            theirs.security_events,
+        );
+        push_rw_diff(&mut scopes, "statuses", mine.statuses, theirs.statuses);
+        if write_satisfies(mine.id_token, theirs.id_token)
+            && !write_satisfies(theirs.id_token, mine.id_token)
+        {
+            scopes.push("id_token".to_string());
+        }
+        if read_satisfies(mine.models, theirs.models) && !read_satisfies(theirs.models, mine.models)
+        {
+            scopes.push("models".to_string());
         }
+        scopes
     }
-
-    impl TryArray for Bound<'_, PyList> {
-        fn try_as_array(&self) -> PyResult<Vec<Yaml>> {
-            let mut list_internals = Vec::new();
-            for entry in self.iter() {
-                list_internals.push(entry.try_as_yaml()?);
+    impl IndividualPermissions {
+        fn scope_label(&self, scope: &str) -> PyResult<String> {
+            Ok(match scope {
+                "actions" => rw_label(self.actions),
+                "artifact_metadata" => rw_label(self.artifact_metadata),
+                "attestations" => rw_label(self.attestations),
+                "checks" => rw_label(self.checks),
+                "contents" => rw_label(self.contents),
+                "deployments" => rw_label(self.deployments),
+                "id_token" => write_label(self.id_token),
+                "issues" => rw_label(self.issues),
+                "models" => read_label(self.models),
+                "discussions" => rw_label(self.discussions),
+                "packages" => rw_label(self.packages),
+                "pages" => rw_label(self.pages),
+                "pull_requests" => rw_label(self.pull_requests),
+                "security_events" => rw_label(self.security_events),
+                "statuses" => rw_label(self.statuses),
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "Unknown permission scope '{scope}'"
+                    )));
+                }
             }
-            Ok(list_internals)
+            .to_string())
         }
     }
 
     #[derive(Clone)]
-    struct WithArgs {
-        options: Option<Hash>,
-        args: Option<StringLike>,
-        entrypoint: Option<StringLike>,
-    }
-
-    impl Yamlable for WithArgs {
-        fn as_yaml(&self) -> Yaml {
-            let mut entries = self.options.clone().unwrap_or_default();
-            entries.insert_yaml_opt("args", &self.args);
-            entries.insert_yaml_opt("entrypoint", &self.entrypoint);
-            Yaml::Hash(entries)
-        }
+    enum RunsOnSpecOptions {
+        Group(StringLike),
+        Labels(OneOrVec<StringLike>),
+        GroupAndLabels(StringLike, OneOrVec<StringLike>),
     }
-
+    #[pyclass]
     #[derive(Clone)]
-    enum StepAction {
-        Run(StringLike),
-        Action {
-            uses: String,
-            with: Option<WithArgs>,
-        },
+    struct RunsOnSpec {
+        options: RunsOnSpecOptions,
     }
-    impl StepAction {
-        fn uses(&self) -> Option<String> {
-            match self {
-                StepAction::Run(_) => None,
-                StepAction::Action { uses, .. } => Some(uses.clone()),
+    #[pymethods]
+    impl RunsOnSpec {
+        #[new]
+        fn new(group: StringLike, labels: OneOrVec<StringLike>) -> Self {
+            Self {
+                options: RunsOnSpecOptions::GroupAndLabels(group, labels),
             }
         }
-        fn with(&self) -> Option<WithArgs> {
-            match self {
-                StepAction::Run(_) => None,
-                StepAction::Action { with, .. } => with.clone(),
+        #[staticmethod]
+        fn group(group: StringLike) -> Self {
+            Self {
+                options: RunsOnSpecOptions::Group(group),
             }
         }
-        fn run(&self) -> Option<&StringLike> {
-            match self {
-                StepAction::Run(script) => Some(script),
-                StepAction::Action { .. } => None,
+        #[staticmethod]
+        fn labels(labels: OneOrVec<StringLike>) -> Self {
+            Self {
+                options: RunsOnSpecOptions::Labels(labels),
             }
         }
-    }
-
-    #[pyclass(subclass)]
-    #[derive(Clone)]
-    struct Step {
-        name: Option<StringLike>,
-        step_action: StepAction,
-        options: StepOptions,
-        recommended_permissions: Option<Permissions>,
-    }
-
-    #[derive(Clone)]
-    struct StepOptions {
-        condition: Option<Either<BooleanExpression, String>>,
-        working_directory: Option<StringLike>,
-        shell: Option<String>,
-        id: Option<String>,
-        env: Option<PyMap<String, StringLike>>,
-        continue_on_error: Option<BoolLike>,
-        timeout_minutes: Option<IntLike>,
-    }
 
-    #[pymethods]
-    impl Step {
         fn __str__(&self) -> PyResult<String> {
             self.as_yaml_string()
         }
+
+        /// Parse a ``runs-on: {group, labels}`` mapping from an existing workflow file.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            match parse_runs_on(&parse_yaml_document(yaml)?)? {
+                RunsOn::Spec(spec) => Ok(spec),
+                _ => Err(PyValueError::new_err(
+                    "Expected 'runs-on' to be a mapping with a 'group' and/or 'labels' key",
+                )),
+            }
+        }
     }
-    impl Yamlable for Step {
+    impl Yamlable for &RunsOnSpec {
         fn as_yaml(&self) -> Yaml {
-            let mut entries = Hash::new();
-            entries.insert_yaml_opt("name", &self.name);
-            entries.insert_yaml_opt("if", &self.options.condition);
-            entries.insert_yaml_opt("uses", self.step_action.uses());
-            entries.insert_yaml_opt("with", self.step_action.with());
-            entries.insert_yaml_opt("run", self.step_action.run());
-            entries.insert_yaml_opt("working-directory", &self.options.working_directory);
-            entries.insert_yaml_opt("shell", &self.options.shell);
-            entries.insert_yaml_opt("id", &self.options.id);
-            entries.insert_yaml_opt("env", &self.options.env);
-            entries.insert_yaml_opt("continue-on-error", &self.options.continue_on_error);
-            entries.insert_yaml_opt("timeout-minutes", &self.options.timeout_minutes);
-            Yaml::Hash(entries)
+            let mut out = Hash::new();
+            match &self.options {
+                RunsOnSpecOptions::Group(group) => out.insert_yaml("group", group),
+                RunsOnSpecOptions::Labels(labels) => out.insert_yaml("labels", labels),
+                RunsOnSpecOptions::GroupAndLabels(group, labels) => {
+                    out.insert_yaml("group", group);
+                    out.insert_yaml("labels", labels);
+                }
+            }
+            Yaml::Hash(out)
         }
     }
-    fn collect_script_lines(script: Vec<StringLike>) -> StringLike {
-        let lines = script
-            .into_iter()
-            .map(|line| match line {
-                Either::A(expr) => expr.as_expression_string(),
-                Either::B(raw) => raw,
-            })
-            .collect::<Vec<String>>()
-            .join("\n");
-        Either::B(lines)
-    }
 
-    /// Generate a `Step` from a list of shell commands.
-    ///
-    /// Parameters
-    /// ----------
-    /// *script
-    ///     A list of shell commands to run in sequence. These will be concatenated with newlines
-    ///     and passed as the ``run`` key of the generated step. Note that this must not exceed
-    ///     21,000 characters in total.
-    /// name
-    ///     The name of the step to display on GitHub.
-    /// condition
-    ///     A boolean expression which must be met for the step to run. Note that this represents the ``if`` key in the actual YAML file.
-    /// working_directory
-    ///     Specifies the directory in which the script is run.
-    /// shell
-    ///     Used to override the default shell settings of the runner's OS (or `Job`/`Workflow` defaults).
-    /// id
-    ///     A unique identifier for the step which can be referenced in expressions.
-    /// env
-    ///     Used to specify environment variables for the step.
-    /// continue_on_error
-    ///     Prevents the job from failing if this step fails.
-    /// timeout_minutes
-    ///     The maximum number of minutes to let the step run before GitHub automatically cancels it (defaults to 360 if not specified).
-    ///
-    #[pyfunction]
-    #[pyo3(signature = (*script, name = None, condition = None, working_directory = None, shell = None, id = None, env = None, continue_on_error = None, timeout_minutes= None))]
-    fn script(
-        script: &Bound<'_, PyTuple>,
-        name: Option<StringLike>,
-        condition: Option<Either<BooleanExpression, String>>,
-        working_directory: Option<StringLike>,
-        shell: Option<String>,
-        id: Option<String>,
-        env: Option<PyMap<String, StringLike>>,
-        continue_on_error: Option<BoolLike>,
-        timeout_minutes: Option<IntLike>,
-    ) -> PyResult<Step> {
-        let script = script
-            .iter()
-            .map(|item| item.extract::<StringLike>())
-            .collect::<PyResult<Vec<StringLike>>>()?;
-        for line in &script {
-            validate_string_like(line, ALLOWED_STEP_RUN)?;
-        }
-        validate_step_options(
-            name.as_ref(),
-            condition.as_ref(),
-            working_directory.as_ref(),
-            env.as_ref(),
-            continue_on_error.as_ref(),
-            timeout_minutes.as_ref(),
-        )?;
-        let script = collect_script_lines(script);
-        Ok(Step {
-            name,
-            step_action: StepAction::Run(script),
-            options: StepOptions {
-                condition,
-                working_directory,
-                shell,
-                id,
-                env,
-                continue_on_error,
-                timeout_minutes,
-            },
-            recommended_permissions: None,
-        })
+    #[derive(Clone)]
+    enum RunsOn {
+        String(StringLike),
+        Array(Vec<StringLike>),
+        Spec(RunsOnSpec),
     }
-    fn make_action(
-        name: Option<StringLike>,
-        action: &str,
-        r#ref: Option<String>,
-        with_opts: Option<Hash>,
-        args: Option<StringLike>,
-        entrypoint: Option<StringLike>,
-        condition: Option<Either<BooleanExpression, String>>,
-        id: Option<String>,
-        env: Option<PyMap<String, StringLike>>,
-        continue_on_error: Option<BoolLike>,
-        timeout_minutes: Option<IntLike>,
-        recommended_permissions: Option<Permissions>,
-    ) -> PyResult<Step> {
-        validate_step_options(
-            name.as_ref(),
-            condition.as_ref(),
-            None,
-            env.as_ref(),
-            continue_on_error.as_ref(),
-            timeout_minutes.as_ref(),
-        )?;
-        if let Some(args) = &args {
-            validate_string_like(args, ALLOWED_STEP_WITH)?;
+    impl<'py> FromPyObject<'py> for RunsOn {
+        fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+            if let Ok(spec) = obj.extract::<RunsOnSpec>() {
+                Ok(Self::Spec(spec))
+            } else if let Ok(list) = obj.extract::<Vec<StringLike>>() {
+                Ok(Self::Array(list))
+            } else if let Ok(single) = obj.extract::<StringLike>() {
+                Ok(Self::String(single))
+            } else {
+                Err(PyValueError::new_err(
+                    "Expected a 'RunsOnSpec', list of strings, or a single string",
+                ))
+            }
         }
-        if let Some(entrypoint) = &entrypoint {
-            validate_string_like(entrypoint, ALLOWED_STEP_WITH)?;
+    }
+    impl Yamlable for &RunsOn {
+        fn as_yaml(&self) -> Yaml {
+            match self {
+                RunsOn::String(s) => s.as_yaml(),
+                RunsOn::Array(l) => l.as_yaml(),
+                RunsOn::Spec(spec) => spec.as_yaml(),
+            }
         }
-        let with_args = if with_opts.is_some() || args.is_some() || entrypoint.is_some() {
-            Some(WithArgs {
-                options: with_opts,
-                args,
-                entrypoint,
-            })
-        } else {
-            None
-        };
-        Ok(Step {
-            name,
-            step_action: StepAction::Action {
-                uses: format!(
-                    "{}{}",
-                    action,
-                    r#ref.map(|s| format!("@{s}")).unwrap_or_default()
-                ),
-                with: with_args,
-            },
-            options: StepOptions {
-                condition,
-                working_directory: None,
-                shell: None,
-                id,
-                env,
-                continue_on_error,
-                timeout_minutes,
-            },
-            recommended_permissions,
-        })
     }
-
-    /// Generate a `Step` from a reusable unit of code called an action.
-    ///
-    /// Parameters
-    /// ----------
-    /// name
-    ///     The name of the step to display on GitHub.
-    /// action
-    ///     The location of the action's public GitHub repository (a string of the form {owner}/{repo}).
-    /// ref
-    ///     The branch, ref, or SHA of the action's repository to use. This is used to specify a specific version of an action.
-    /// with_opts
-    ///     A map of input parameters for the action. These are passed as the ``with`` key of the generated step.
-    /// args
-    ///     The inputs for a Docker container which are passed to the container's entrypoint. This
-    ///     is a subkey of the ``with`` key of the generated step.
-    /// entrypoint
-    ///     Overrides the Docker ENTRYPOINT in the action's Dockerfile or sets one if it was not
-    ///     specified. Accepts a single string defining the executable to run (note that this is
-    ///     different from Docker's ENTRYPOINT instruction which has both a shell and exec form).
-    ///     This is a subkey of the ``with`` key of the generated step.
-    /// condition
-    ///     A boolean expression which must be met for the step to run. Note that this represents the ``if`` key in the actual YAML file.
-    /// id
-    ///     A unique identifier for the step which can be referenced in expressions.
-    /// env
-    ///     Used to specify environment variables for the step.
-    /// continue_on_error
-    ///     Prevents the job from failing if this step fails.
-    /// timeout_minutes
-    ///     The maximum number of minutes to let the step run before GitHub automatically cancels it (defaults to 360 if not specified).
-    /// recommended_permissions
-    ///     Recommended permissions required to run this action.
-    ///
-    #[pyfunction]
-    #[pyo3(signature = (name, action, *, r#ref = None, with_opts = None, args = None, entrypoint = None, condition = None, id = None, env = None, continue_on_error = None, timeout_minutes = None, recommended_permissions = None))]
-    fn action(
-        name: Option<StringLike>,
-        action: &str,
-        r#ref: Option<String>,
-        with_opts: Option<Bound<PyDict>>,
-        args: Option<StringLike>,
-        entrypoint: Option<StringLike>,
-        condition: Option<Either<BooleanExpression, String>>,
-        id: Option<String>,
-        env: Option<PyMap<String, StringLike>>,
-        continue_on_error: Option<BoolLike>,
-        timeout_minutes: Option<IntLike>,
-        recommended_permissions: Option<Permissions>,
-    ) -> PyResult<Step> {
-        if let Some(with_opts) = &with_opts {
-            validate_with_opts(with_opts, ALLOWED_STEP_WITH)?;
+    /// Parse a ``runs-on:`` value from an existing workflow file: a single string, a list of
+    /// strings, or the ``{group, labels}`` mapping form.
+    fn parse_runs_on(yaml: &Yaml) -> PyResult<RunsOn> {
+        if let Some(hash) = yaml.as_hash() {
+            let mut hash = hash.clone();
+            let group = hash_take(&mut hash, "group")
+                .map(|y| parse_string_like(&y, "runs-on.group"))
+                .transpose()?;
+            let labels = hash_take(&mut hash, "labels")
+                .map(|y| parse_one_or_vec_string_like(&y, "runs-on.labels"))
+                .transpose()?;
+            reject_unknown_keys(&hash, "runs-on")?;
+            let options = match (group, labels) {
+                (Some(group), Some(labels)) => RunsOnSpecOptions::GroupAndLabels(group, labels),
+                (Some(group), None) => RunsOnSpecOptions::Group(group),
+                (None, Some(labels)) => RunsOnSpecOptions::Labels(labels),
+                (None, None) => {
+                    return Err(PyValueError::new_err(
+                        "Expected 'runs-on' mapping to have a 'group' and/or 'labels' key",
+                    ));
+                }
+            };
+            Ok(RunsOn::Spec(RunsOnSpec { options }))
+        } else if let Some(arr) = yaml.as_vec() {
+            Ok(RunsOn::Array(
+                arr.iter()
+                    .map(|y| parse_string_like(y, "runs-on"))
+                    .collect::<PyResult<Vec<_>>>()?,
+            ))
+        } else {
+            Ok(RunsOn::String(parse_string_like(yaml, "runs-on")?))
         }
-        make_action(
-            name,
-            action,
-            r#ref,
-            with_opts.map(|d| d.try_as_hash()).transpose()?,
-            args,
-            entrypoint,
-            condition,
-            id,
-            env,
-            continue_on_error,
-            timeout_minutes,
-            recommended_permissions,
-        )
     }
 
-    #[pyclass(extends=Step, subclass)]
-    struct ActionStep;
+    #[pyclass]
+    #[derive(Clone)]
+    struct Environment {
+        name: StringLike,
+        url: Option<StringLike>,
+    }
     #[pymethods]
-    impl ActionStep {
+    impl Environment {
         #[new]
-        #[pyo3(signature = (name, action, *, r#ref = None, with_opts = None, args = None, entrypoint = None, condition = None, id = None, env = None, continue_on_error = None, timeout_minutes = None, recommended_permissions = None))]
-        fn new(
-            name: Option<StringLike>,
-            action: &str,
-            r#ref: Option<String>,
-            with_opts: Option<Bound<PyDict>>,
-            args: Option<StringLike>,
-            entrypoint: Option<StringLike>,
-            condition: Option<Either<BooleanExpression, String>>,
-            id: Option<String>,
-            env: Option<PyMap<String, StringLike>>,
-            continue_on_error: Option<BoolLike>,
-            timeout_minutes: Option<IntLike>,
-            recommended_permissions: Option<Permissions>,
-        ) -> PyResult<(Self, Step)> {
-            if let Some(with_opts) = &with_opts {
-                validate_with_opts(with_opts, ALLOWED_STEP_WITH)?;
+        #[pyo3(signature = (name, url = None))]
+        fn new(name: StringLike, url: Option<StringLike>) -> Self {
+            Self { name, url }
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        /// Parse an ``environment:`` value from an existing workflow, either the bare string form
+        /// or the ``{name, url}`` mapping form.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            environment_from_yaml(&parse_yaml_document(yaml)?)
+        }
+    }
+    /// Parse an `environment:` node already extracted from a larger document, shared between
+    /// `Environment::from_yaml` and `Job::from_yaml`.
+    fn environment_from_yaml(yaml: &Yaml) -> PyResult<Environment> {
+        if let Some(hash) = yaml.as_hash() {
+            let mut hash = hash.clone();
+            let name = hash_take(&mut hash, "name").ok_or_else(|| {
+                PyValueError::new_err("Expected 'environment' mapping to have a 'name' key")
+            })?;
+            let name = parse_string_like(&name, "environment.name")?;
+            let url = hash_take(&mut hash, "url")
+                .map(|y| parse_string_like(&y, "environment.url"))
+                .transpose()?;
+            reject_unknown_keys(&hash, "environment")?;
+            Ok(Environment { name, url })
+        } else {
+            Ok(Environment {
+                name: parse_string_like(yaml, "environment")?,
+                url: None,
+            })
+        }
+    }
+    impl Yamlable for &Environment {
+        fn as_yaml(&self) -> Yaml {
+            if let Some(url) = &self.url {
+                let mut sub = Hash::new();
+                sub.insert_yaml("name", &self.name);
+                sub.insert_yaml("url", url);
+                Yaml::Hash(sub)
+            } else {
+                self.name.as_yaml()
             }
-            let step = make_action(
-                name,
-                action,
-                r#ref,
-                with_opts.map(|d| d.try_as_hash()).transpose()?,
-                args,
-                entrypoint,
-                condition,
-                id,
-                env,
-                continue_on_error,
-                timeout_minutes,
-                recommended_permissions,
-            )?;
-            Ok((ActionStep, step))
         }
     }
 
-    #[derive(Clone, Copy)]
-    enum ReadWriteNonePermission {
-        Read,
-        Write,
-        None,
+    #[pyclass]
+    #[derive(Clone)]
+    struct Concurrency {
+        group: StringLike,
+        cancel_in_progress: Option<BoolLike>,
     }
-    impl FromStr for ReadWriteNonePermission {
-        type Err = PyErr;
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            match s.to_lowercase().as_str() {
-                "read" => Ok(Self::Read),
-                "write" => Ok(Self::Write),
-                "none" => Ok(Self::None),
-                _ => Err(PyValueError::new_err("Invalid permission")),
+    #[pymethods]
+    impl Concurrency {
+        #[new]
+        #[pyo3(signature = (group, *, cancel_in_progress=None))]
+        fn new(group: StringLike, cancel_in_progress: Option<BoolLike>) -> Self {
+            Self {
+                group,
+                cancel_in_progress,
             }
         }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        /// Parse a ``concurrency:`` value from an existing workflow file.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            concurrency_from_yaml(&parse_yaml_document(yaml)?)
+        }
+    }
+    /// Parse a `concurrency:` node already extracted from a larger document, shared between
+    /// `Concurrency::from_yaml` and `Job::from_yaml`/`Workflow::from_yaml`.
+    fn concurrency_from_yaml(yaml: &Yaml) -> PyResult<Concurrency> {
+        if let Some(hash) = yaml.as_hash() {
+            let mut hash = hash.clone();
+            let group = hash_take(&mut hash, "group").ok_or_else(|| {
+                PyValueError::new_err("Expected 'concurrency' mapping to have a 'group' key")
+            })?;
+            let group = parse_string_like(&group, "concurrency.group")?;
+            let cancel_in_progress = hash_take(&mut hash, "cancel-in-progress")
+                .map(|y| parse_bool_like(&y, "concurrency.cancel-in-progress"))
+                .transpose()?;
+            reject_unknown_keys(&hash, "concurrency")?;
+            Ok(Concurrency {
+                group,
+                cancel_in_progress,
+            })
+        } else {
+            Ok(Concurrency {
+                group: parse_string_like(yaml, "concurrency")?,
+                cancel_in_progress: None,
+            })
+        }
     }
-    impl Yamlable for &ReadWriteNonePermission {
+    impl Yamlable for &Concurrency {
         fn as_yaml(&self) -> Yaml {
-            match self {
-                ReadWriteNonePermission::Read => "read",
-                ReadWriteNonePermission::Write => "write",
-                ReadWriteNonePermission::None => "none",
+            let mut out = Hash::new();
+            out.insert_yaml("group", &self.group);
+            out.insert_yaml_opt("cancel-in-progress", &self.cancel_in_progress);
+            Yaml::Hash(out)
+        }
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct RunDefaults {
+        shell: Option<StringLike>,
+        working_directory: Option<StringLike>,
+    }
+    #[pymethods]
+    impl RunDefaults {
+        #[new]
+        #[pyo3(signature = (*, shell=None, working_directory=None))]
+        fn new(shell: Option<StringLike>, working_directory: Option<StringLike>) -> Self {
+            Self {
+                shell,
+                working_directory,
             }
-            .as_yaml()
         }
+
+        /// Parse a ``defaults.run:`` mapping from an existing workflow file.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            run_defaults_from_hash(expect_hash(&parse_yaml_document(yaml)?, "defaults.run")?.clone())
+        }
+    }
+    fn run_defaults_from_hash(mut hash: Hash) -> PyResult<RunDefaults> {
+        let shell = hash_take(&mut hash, "shell")
+            .map(|y| parse_string_like(&y, "defaults.run.shell"))
+            .transpose()?;
+        let working_directory = hash_take(&mut hash, "working-directory")
+            .map(|y| parse_string_like(&y, "defaults.run.working-directory"))
+            .transpose()?;
+        reject_unknown_keys(&hash, "defaults.run")?;
+        Ok(RunDefaults {
+            shell,
+            working_directory,
+        })
     }
-    #[derive(Clone, Copy)]
-    enum WriteNonePermission {
-        Write,
-        None,
+    impl MaybeYamlable for &RunDefaults {
+        fn maybe_as_yaml(&self) -> Option<Yaml> {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("shell", &self.shell);
+            out.insert_yaml_opt("working-directory", &self.working_directory);
+            if out.is_empty() {
+                None
+            } else {
+                Some(Yaml::Hash(out))
+            }
+        }
     }
-    impl FromStr for WriteNonePermission {
-        type Err = PyErr;
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            match s.to_lowercase().as_str() {
-                "write" => Ok(Self::Write),
-                "none" => Ok(Self::None),
-                _ => Err(PyValueError::new_err("Invalid permission")),
+    #[pyclass]
+    #[derive(Clone)]
+    struct Defaults {
+        defaults: Option<PyMap<String, String>>,
+        run_defaults: Option<RunDefaults>,
+    }
+    #[pymethods]
+    impl Defaults {
+        #[new]
+        #[pyo3(signature = (*, defaults=None, run_defaults=None))]
+        fn new(defaults: Option<PyMap<String, String>>, run_defaults: Option<RunDefaults>) -> Self {
+            Self {
+                defaults,
+                run_defaults,
+            }
+        }
+
+        /// Parse a ``defaults:`` mapping from an existing workflow file.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            defaults_from_hash(expect_hash(&parse_yaml_document(yaml)?, "defaults")?.clone())
+        }
+    }
+    fn defaults_from_hash(mut hash: Hash) -> PyResult<Defaults> {
+        let run_defaults = hash_take(&mut hash, "run")
+            .map(|y| run_defaults_from_hash(expect_hash(&y, "defaults.run")?.clone()))
+            .transpose()?;
+        let defaults = hash_take(&mut hash, "defaults")
+            .map(|y| {
+                expect_hash(&y, "defaults.defaults")?
+                    .iter()
+                    .map(|(k, v)| {
+                        Ok((
+                            yaml_scalar_to_string(k, "defaults.defaults")?,
+                            yaml_scalar_to_string(v, "defaults.defaults")?,
+                        ))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+                    .map(|pairs| pairs.into_iter().collect())
+            })
+            .transpose()?;
+        reject_unknown_keys(&hash, "defaults")?;
+        Ok(Defaults {
+            defaults,
+            run_defaults,
+        })
+    }
+    impl MaybeYamlable for &Defaults {
+        fn maybe_as_yaml(&self) -> Option<Yaml> {
+            let mut out = Hash::new();
+            if let Some(run_defaults) = &self.run_defaults {
+                out.insert_yaml_opt("run", run_defaults.maybe_as_yaml());
+            }
+            out.insert_yaml_opt("defaults", &self.defaults);
+            if out.is_empty() {
+                None
+            } else {
+                Some(Yaml::Hash(out))
             }
         }
     }
-    impl Yamlable for &WriteNonePermission {
-        fn as_yaml(&self) -> Yaml {
-            match self {
-                WriteNonePermission::Write => "write",
-                WriteNonePermission::None => "none",
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct Matrix {
+        matrix: Option<Hash>,
+        include: Option<Array>,
+        exclude: Option<Array>,
+    }
+    #[pymethods]
+    impl Matrix {
+        #[new]
+        #[pyo3(signature = (*, include = None, exclude = None, **matrix))]
+        fn new(
+            include: Option<&Bound<'_, PyList>>,
+            exclude: Option<&Bound<'_, PyList>>,
+            matrix: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<Self> {
+            Ok(Self {
+                matrix: matrix
+                    .map(|m| {
+                        let mut hash = Hash::new();
+                        for (k, v) in m.iter() {
+                            hash.insert_yaml(k.try_as_yaml()?, v.try_as_yaml()?);
+                        }
+                        Ok::<Hash, PyErr>(hash)
+                    })
+                    .transpose()?,
+                include: include
+                    .map(|i| {
+                        let mut arr = Array::new();
+                        for v in i.iter() {
+                            arr.push_yaml(v.try_as_yaml()?);
+                        }
+                        Ok::<Array, PyErr>(arr)
+                    })
+                    .transpose()?,
+                exclude: exclude
+                    .map(|e| {
+                        let mut arr = Array::new();
+                        for v in e.iter() {
+                            arr.push_yaml(v.try_as_yaml()?);
+                        }
+                        Ok::<Array, PyErr>(arr)
+                    })
+                    .transpose()?,
+            })
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        /// Compute the concrete list of variant dicts this `Matrix` expands to, following
+        /// GitHub Actions' own job-generation semantics exactly: the cartesian product of the
+        /// base ``matrix`` key/value-list pairs, with every combination matching an ``exclude``
+        /// entry removed, followed by each ``include`` entry either merged into every matching
+        /// combination (matched by its keys that overlap the base ``matrix`` keys) or appended as
+        /// a standalone combination if it matches none. Useful for previewing or validating the
+        /// actual set of jobs a `Strategy` will produce.
+        fn expand(&self, py: Python<'_>) -> PyResult<Vec<Py<PyAny>>> {
+            expand_matrix(self.matrix.as_ref(), self.include.as_ref(), self.exclude.as_ref())?
+                .into_iter()
+                .map(|combo| Ok(json_to_py(py, &yaml_to_json(&Yaml::Hash(combo))?)?.unbind()))
+                .collect()
+        }
+
+        /// Parse a ``strategy.matrix:`` mapping from an existing workflow file, splitting out the
+        /// ``include``/``exclude`` keys and keeping every other key as a matrix variable.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            matrix_from_hash(expect_hash(&parse_yaml_document(yaml)?, "matrix")?.clone())
+        }
+    }
+    fn matrix_from_hash(mut hash: Hash) -> PyResult<Matrix> {
+        let include = hash_take(&mut hash, "include")
+            .map(|y| {
+                y.as_vec()
+                    .cloned()
+                    .ok_or_else(|| PyValueError::new_err("Expected 'matrix.include' to be a list"))
+            })
+            .transpose()?;
+        let exclude = hash_take(&mut hash, "exclude")
+            .map(|y| {
+                y.as_vec()
+                    .cloned()
+                    .ok_or_else(|| PyValueError::new_err("Expected 'matrix.exclude' to be a list"))
+            })
+            .transpose()?;
+        Ok(Matrix {
+            matrix: if hash.is_empty() { None } else { Some(hash) },
+            include,
+            exclude,
+        })
+    }
+    impl Yamlable for &Matrix {
+        fn as_yaml(&self) -> Yaml {
+            let mut matrix = self.matrix.clone().unwrap_or_default();
+            matrix.insert_yaml_opt("include", &self.include);
+            matrix.insert_yaml_opt("exclude", &self.exclude);
+            Yaml::Hash(matrix)
+        }
+    }
+
+    fn cartesian_product(matrix: Option<&Hash>) -> PyResult<Vec<Hash>> {
+        let Some(matrix) = matrix.filter(|m| !m.is_empty()) else {
+            return Ok(Vec::new());
+        };
+        let mut combos: Vec<Hash> = vec![Hash::new()];
+        for (key, value) in matrix {
+            let values = value.as_vec().ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "Matrix key '{}' must map to a list of candidate values",
+                    key.as_str().unwrap_or("?")
+                ))
+            })?;
+            let mut next = Vec::with_capacity(combos.len() * values.len());
+            for combo in &combos {
+                for value in values {
+                    let mut combo = combo.clone();
+                    combo.insert(key.clone(), value.clone());
+                    next.push(combo);
+                }
             }
-            .as_yaml()
+            combos = next;
         }
+        Ok(combos)
     }
-    #[derive(Clone, Copy)]
-    enum ReadNonePermission {
-        Read,
-        None,
+
+    fn hash_is_submatch(sub: &Hash, full: &Hash) -> bool {
+        sub.iter().all(|(k, v)| full.get(k) == Some(v))
     }
-    impl FromStr for ReadNonePermission {
-        type Err = PyErr;
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            match s.to_lowercase().as_str() {
-                "read" => Ok(Self::Read),
-                "none" => Ok(Self::None),
-                _ => Err(PyValueError::new_err("Invalid permission")),
+
+    /// Compute the concrete set of variant hashes a `Matrix` expands to, following GitHub
+    /// Actions' own semantics: exclude is applied to the base cartesian product before include,
+    /// so an include entry can re-add a combination exclude just removed.
+    fn expand_matrix(
+        matrix: Option<&Hash>,
+        include: Option<&Array>,
+        exclude: Option<&Array>,
+    ) -> PyResult<Vec<Hash>> {
+        let mut combos = cartesian_product(matrix)?;
+
+        if let Some(exclude) = exclude {
+            for entry in exclude {
+                let entry = entry.as_hash().ok_or_else(|| {
+                    PyValueError::new_err(
+                        "Each 'exclude' entry must be a mapping of matrix key to value",
+                    )
+                })?;
+                combos.retain(|combo| !hash_is_submatch(entry, combo));
+            }
+        }
+
+        if let Some(include) = include {
+            let matrix_keys: Vec<Yaml> = matrix
+                .map(|m| m.keys().cloned().collect())
+                .unwrap_or_default();
+            for entry in include {
+                let entry = entry.as_hash().ok_or_else(|| {
+                    PyValueError::new_err(
+                        "Each 'include' entry must be a mapping of matrix key to value",
+                    )
+                })?;
+                let overlap: Hash = entry
+                    .iter()
+                    .filter(|(k, _)| matrix_keys.contains(k))
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                let mut matched = false;
+                for combo in combos.iter_mut() {
+                    if hash_is_submatch(&overlap, combo) {
+                        matched = true;
+                        for (k, v) in entry {
+                            combo.insert(k.clone(), v.clone());
+                        }
+                    }
+                }
+                if !matched {
+                    combos.push(entry.clone());
+                }
             }
         }
+
+        Ok(combos)
     }
-    impl Yamlable for &ReadNonePermission {
-        fn as_yaml(&self) -> Yaml {
-            match self {
-                ReadNonePermission::Read => "read",
-                ReadNonePermission::None => "none",
+
+    #[cfg(test)]
+    mod expand_matrix_tests {
+        use super::*;
+
+        fn strs(values: &[&str]) -> Array {
+            values.iter().map(|v| Yaml::String(v.to_string())).collect()
+        }
+
+        // Mirrors the matrix/include example from GitHub's own documentation:
+        // https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstrategymatrixinclude
+        #[test]
+        fn include_entry_with_no_overlapping_keys_merges_into_every_combination() {
+            let mut matrix = Hash::new();
+            matrix.insert(Yaml::String("fruit".into()), Yaml::Array(strs(&["apple", "pear"])));
+            matrix.insert(Yaml::String("animal".into()), Yaml::Array(strs(&["cat", "dog"])));
+
+            let mut include_entry = Hash::new();
+            include_entry.insert(Yaml::String("color".into()), Yaml::String("green".into()));
+            let include = vec![Yaml::Hash(include_entry)];
+
+            let combos = expand_matrix(Some(&matrix), Some(&include), None).unwrap();
+
+            assert_eq!(combos.len(), 4);
+            for combo in &combos {
+                assert_eq!(
+                    combo.get(&Yaml::String("color".into())),
+                    Some(&Yaml::String("green".into()))
+                );
+                assert!(combo.contains_key(&Yaml::String("fruit".into())));
+                assert!(combo.contains_key(&Yaml::String("animal".into())));
             }
-            .as_yaml()
+        }
+
+        #[test]
+        fn include_entry_overlapping_a_matrix_key_only_augments_matching_combinations() {
+            let mut matrix = Hash::new();
+            matrix.insert(Yaml::String("fruit".into()), Yaml::Array(strs(&["apple", "pear"])));
+
+            let mut include_entry = Hash::new();
+            include_entry.insert(Yaml::String("fruit".into()), Yaml::String("apple".into()));
+            include_entry.insert(Yaml::String("color".into()), Yaml::String("green".into()));
+            let include = vec![Yaml::Hash(include_entry)];
+
+            let combos = expand_matrix(Some(&matrix), Some(&include), None).unwrap();
+
+            assert_eq!(combos.len(), 2);
+            let apple = combos
+                .iter()
+                .find(|c| c.get(&Yaml::String("fruit".into())) == Some(&Yaml::String("apple".into())))
+                .unwrap();
+            assert_eq!(
+                apple.get(&Yaml::String("color".into())),
+                Some(&Yaml::String("green".into()))
+            );
+            let pear = combos
+                .iter()
+                .find(|c| c.get(&Yaml::String("fruit".into())) == Some(&Yaml::String("pear".into())))
+                .unwrap();
+            assert_eq!(pear.get(&Yaml::String("color".into())), None);
         }
     }
 
+    #[pyclass]
     #[derive(Clone)]
-    struct IndividualPermissions {
-        actions: Option<ReadWriteNonePermission>,
-        artifact_metadata: Option<ReadWriteNonePermission>,
-        attestations: Option<ReadWriteNonePermission>,
-        checks: Option<ReadWriteNonePermission>,
-        contents: Option<ReadWriteNonePermission>,
-        deployments: Option<ReadWriteNonePermission>,
-        id_token: Option<WriteNonePermission>,
-        issues: Option<ReadWriteNonePermission>,
-        models: Option<ReadNonePermission>,
-        discussions: Option<ReadWriteNonePermission>,
-        packages: Option<ReadWriteNonePermission>,
-        pages: Option<ReadWriteNonePermission>,
-        pull_requests: Option<ReadWriteNonePermission>,
-        security_events: Option<ReadWriteNonePermission>,
-        statuses: Option<ReadWriteNonePermission>,
+    struct Strategy {
+        matrix: Option<Matrix>,
+        fast_fail: Option<BoolLike>,
+        max_parallel: Option<IntLike>,
     }
-    impl IndividualPermissions {
-        fn is_empty(&self) -> bool {
-            self.actions.is_none()
-                && self.artifact_metadata.is_none()
-                && self.attestations.is_none()
-                && self.checks.is_none()
-                && self.contents.is_none()
-                && self.deployments.is_none()
-                && self.id_token.is_none()
-                && self.issues.is_none()
-                && self.models.is_none()
-                && self.discussions.is_none()
-                && self.packages.is_none()
-                && self.pages.is_none()
-                && self.pull_requests.is_none()
-                && self.security_events.is_none()
-                && self.statuses.is_none()
+    #[pymethods]
+    impl Strategy {
+        #[new]
+        #[pyo3(signature = (*, matrix = None, fast_fail = None, max_parallel = None))]
+        fn new(
+            // TODO: prevent invalid state where all are None
+            matrix: Option<Matrix>,
+            fast_fail: Option<BoolLike>,
+            max_parallel: Option<IntLike>,
+        ) -> Self {
+            Self {
+                matrix,
+                fast_fail,
+                max_parallel,
+            }
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
         }
+
+        /// Parse a ``strategy:`` mapping from an existing workflow file.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            strategy_from_hash(expect_hash(&parse_yaml_document(yaml)?, "strategy")?.clone())
+        }
+    }
+    fn strategy_from_hash(mut hash: Hash) -> PyResult<Strategy> {
+        let matrix = hash_take(&mut hash, "matrix")
+            .map(|y| matrix_from_hash(expect_hash(&y, "strategy.matrix")?.clone()))
+            .transpose()?;
+        let fast_fail = hash_take(&mut hash, "fail-fast")
+            .map(|y| parse_bool_like(&y, "strategy.fail-fast"))
+            .transpose()?;
+        let max_parallel = hash_take(&mut hash, "max-parallel")
+            .map(|y| parse_int_like(&y, "strategy.max-parallel"))
+            .transpose()?;
+        reject_unknown_keys(&hash, "strategy")?;
+        Ok(Strategy {
+            matrix,
+            fast_fail,
+            max_parallel,
+        })
     }
-    #[derive(Clone)]
-    enum PermissionsOptions {
-        Individual(IndividualPermissions),
-        ReadAll,
-        WriteAll,
-        None,
+    impl Yamlable for &Strategy {
+        fn as_yaml(&self) -> Yaml {
+            let mut strategy = Hash::new();
+            strategy.insert_yaml_opt("matrix", &self.matrix);
+            strategy.insert_yaml_opt("fail-fast", &self.fast_fail);
+            strategy.insert_yaml_opt("max-parallel", &self.max_parallel);
+            Yaml::Hash(strategy)
+        }
     }
+
     #[pyclass]
     #[derive(Clone)]
-    struct Permissions {
-        options: PermissionsOptions,
+    struct Credentials {
+        username: StringLike,
+        password: StringLike,
     }
     #[pymethods]
-    impl Permissions {
+    impl Credentials {
         #[new]
-        #[pyo3(signature= (actions=None, artifact_metadata=None, attestations=None, checks=None, contents=None, deployments=None, id_token=None, issues=None, models=None, discussions=None, packages=None, pages=None, pull_requests=None, security_events=None, statuses=None))]
-        fn new(
-            actions: Option<String>,
-            artifact_metadata: Option<String>,
-            attestations: Option<String>,
-            checks: Option<String>,
-            contents: Option<String>,
-            deployments: Option<String>,
-            id_token: Option<String>,
-            issues: Option<String>,
-            models: Option<String>,
-            discussions: Option<String>,
-            packages: Option<String>,
-            pages: Option<String>,
-            pull_requests: Option<String>,
-            security_events: Option<String>,
-            statuses: Option<String>,
-        ) -> PyResult<Self> {
-            Ok(Self {
-                options: PermissionsOptions::Individual(IndividualPermissions {
-                    actions: actions.map(|s| s.parse()).transpose()?,
-                    artifact_metadata: artifact_metadata.map(|s| s.parse()).transpose()?,
-                    attestations: attestations.map(|s| s.parse()).transpose()?,
-                    checks: checks.map(|s| s.parse()).transpose()?,
-                    contents: contents.map(|s| s.parse()).transpose()?,
-                    deployments: deployments.map(|s| s.parse()).transpose()?,
-                    id_token: id_token.map(|s| s.parse()).transpose()?,
-                    issues: issues.map(|s| s.parse()).transpose()?,
-                    models: models.map(|s| s.parse()).transpose()?,
-                    discussions: discussions.map(|s| s.parse()).transpose()?,
-                    packages: packages.map(|s| s.parse()).transpose()?,
-                    pages: pages.map(|s| s.parse()).transpose()?,
-                    pull_requests: pull_requests.map(|s| s.parse()).transpose()?,
-                    security_events: security_events.map(|s| s.parse()).transpose()?,
-                    statuses: statuses.map(|s| s.parse()).transpose()?,
-                }),
-            })
+        fn new(username: StringLike, password: StringLike) -> Self {
+            Self { username, password }
         }
-        #[staticmethod]
-        fn none() -> Self {
-            Self {
-                options: PermissionsOptions::None,
-            }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
         }
+
+        /// Parse a ``container.credentials:`` mapping from an existing workflow file.
         #[staticmethod]
-        fn read_all() -> Self {
-            Self {
-                options: PermissionsOptions::ReadAll,
-            }
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            let mut hash = expect_hash(&parse_yaml_document(yaml)?, "credentials")?.clone();
+            let username = hash_take(&mut hash, "username").ok_or_else(|| {
+                PyValueError::new_err("Expected 'credentials' mapping to have a 'username' key")
+            })?;
+            let username = parse_string_like(&username, "credentials.username")?;
+            let password = hash_take(&mut hash, "password").ok_or_else(|| {
+                PyValueError::new_err("Expected 'credentials' mapping to have a 'password' key")
+            })?;
+            let password = parse_string_like(&password, "credentials.password")?;
+            reject_unknown_keys(&hash, "credentials")?;
+            Ok(Self { username, password })
         }
-        #[staticmethod]
-        fn write_all() -> Self {
+    }
+    impl Yamlable for &Credentials {
+        fn as_yaml(&self) -> Yaml {
+            let mut out = Hash::new();
+            out.insert_yaml("username", &self.username);
+            out.insert_yaml("password", &self.password);
+            Yaml::Hash(out)
+        }
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct Container {
+        image: StringLike,
+        credentials: Option<Credentials>,
+        env: Option<PyMap<String, StringLike>>,
+        ports: Option<Vec<IntLike>>,
+        volumes: Option<Vec<StringLike>>,
+        options: Option<StringLike>,
+    }
+    #[pymethods]
+    impl Container {
+        #[new]
+        #[pyo3(signature = (image, *, credentials = None, env = None, ports = None, volumes = None, options = None))]
+        fn new(
+            image: StringLike,
+            credentials: Option<Credentials>,
+            env: Option<PyMap<String, StringLike>>,
+            ports: Option<Vec<IntLike>>,
+            volumes: Option<Vec<StringLike>>,
+            options: Option<StringLike>,
+        ) -> Self {
             Self {
-                options: PermissionsOptions::WriteAll,
+                image,
+                credentials,
+                env,
+                ports,
+                volumes,
+                options,
             }
         }
+
         fn __str__(&self) -> PyResult<String> {
             self.as_yaml_string()
         }
+
+        /// Parse a ``container:`` value from an existing workflow file, either the bare image
+        /// string form or the full mapping form.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            container_from_yaml(&parse_yaml_document(yaml)?)
+        }
+    }
+    /// Parse a `container:` node already extracted from a larger document, shared between
+    /// `Container::from_yaml` and `Job::from_yaml` (both the job-level `container:` and each
+    /// entry of `services:`).
+    fn container_from_yaml(yaml: &Yaml) -> PyResult<Container> {
+        if let Some(hash) = yaml.as_hash() {
+            let mut hash = hash.clone();
+            let image = hash_take(&mut hash, "image").ok_or_else(|| {
+                PyValueError::new_err("Expected 'container' mapping to have an 'image' key")
+            })?;
+            let image = parse_string_like(&image, "container.image")?;
+            let credentials = hash_take(&mut hash, "credentials")
+                .map(|y| {
+                    let mut c = expect_hash(&y, "container.credentials")?.clone();
+                    let username = hash_take(&mut c, "username").ok_or_else(|| {
+                        PyValueError::new_err(
+                            "Expected 'container.credentials' to have a 'username' key",
+                        )
+                    })?;
+                    let username = parse_string_like(&username, "container.credentials.username")?;
+                    let password = hash_take(&mut c, "password").ok_or_else(|| {
+                        PyValueError::new_err(
+                            "Expected 'container.credentials' to have a 'password' key",
+                        )
+                    })?;
+                    let password = parse_string_like(&password, "container.credentials.password")?;
+                    reject_unknown_keys(&c, "container.credentials")?;
+                    Ok::<Credentials, PyErr>(Credentials { username, password })
+                })
+                .transpose()?;
+            let env = hash_take(&mut hash, "env")
+                .map(|y| parse_string_map(&y, "container.env"))
+                .transpose()?;
+            let ports = hash_take(&mut hash, "ports")
+                .map(|y| {
+                    y.as_vec()
+                        .ok_or_else(|| PyValueError::new_err("Expected 'container.ports' to be a list"))?
+                        .iter()
+                        .map(|p| parse_int_like(p, "container.ports"))
+                        .collect::<PyResult<Vec<_>>>()
+                })
+                .transpose()?;
+            let volumes = hash_take(&mut hash, "volumes")
+                .map(|y| {
+                    y.as_vec()
+                        .ok_or_else(|| PyValueError::new_err("Expected 'container.volumes' to be a list"))?
+                        .iter()
+                        .map(|v| parse_string_like(v, "container.volumes"))
+                        .collect::<PyResult<Vec<_>>>()
+                })
+                .transpose()?;
+            let options = hash_take(&mut hash, "options")
+                .map(|y| parse_string_like(&y, "container.options"))
+                .transpose()?;
+            reject_unknown_keys(&hash, "container")?;
+            Ok(Container {
+                image,
+                credentials,
+                env,
+                ports,
+                volumes,
+                options,
+            })
+        } else {
+            Ok(Container {
+                image: parse_string_like(yaml, "container")?,
+                credentials: None,
+                env: None,
+                ports: None,
+                volumes: None,
+                options: None,
+            })
+        }
     }
-    impl Yamlable for &Permissions {
+    impl Yamlable for &Container {
         fn as_yaml(&self) -> Yaml {
-            match &self.options {
-                PermissionsOptions::Individual(indiv_perms) => {
-                    let mut permissions = Hash::new();
-                    permissions.insert_yaml_opt("actions", &indiv_perms.actions);
-                    permissions
-                        .insert_yaml_opt("artifact-metadata", &indiv_perms.artifact_metadata);
-                    permissions.insert_yaml_opt("attestations", &indiv_perms.attestations);
-                    permissions.insert_yaml_opt("checks", &indiv_perms.checks);
-                    permissions.insert_yaml_opt("contents", &indiv_perms.contents);
-                    permissions.insert_yaml_opt("deployments", &indiv_perms.deployments);
-                    permissions.insert_yaml_opt("id-token", &indiv_perms.id_token);
-                    permissions.insert_yaml_opt("issues", &indiv_perms.issues);
-                    permissions.insert_yaml_opt("models", &indiv_perms.models);
-                    permissions.insert_yaml_opt("discussion", &indiv_perms.discussions);
-                    permissions.insert_yaml_opt("packages", &indiv_perms.packages);
-                    permissions.insert_yaml_opt("pages", &indiv_perms.pages);
-                    permissions.insert_yaml_opt("pull-requests", &indiv_perms.pull_requests);
-                    permissions.insert_yaml_opt("security-events", &indiv_perms.security_events);
-                    permissions.insert_yaml_opt("statuses", &indiv_perms.statuses);
-                    Yaml::Hash(permissions)
-                }
-                PermissionsOptions::ReadAll => "read-all".as_yaml(),
-                PermissionsOptions::WriteAll => "write-all".as_yaml(),
-                PermissionsOptions::None => Yaml::Hash(Hash::new()), // TODO: test
-            }
+            let mut out = Hash::new();
+            out.insert_yaml("image", &self.image);
+            out.insert_yaml_opt("credentials", &self.credentials);
+            out.insert_yaml_opt("env", &self.env);
+            out.insert_yaml_opt("ports", &self.ports);
+            out.insert_yaml_opt("volumes", &self.volumes);
+            out.insert_yaml_opt("options", &self.options);
+            Yaml::Hash(out)
         }
     }
 
-    fn max_read_write_none(
-        left: ReadWriteNonePermission,
-        right: ReadWriteNonePermission,
-    ) -> ReadWriteNonePermission {
-        match (left, right) {
-            (ReadWriteNonePermission::Write, _) | (_, ReadWriteNonePermission::Write) => {
-                ReadWriteNonePermission::Write
-            }
-            (ReadWriteNonePermission::Read, _) | (_, ReadWriteNonePermission::Read) => {
-                ReadWriteNonePermission::Read
-            }
-            _ => ReadWriteNonePermission::None,
-        }
+    /// A named, reusable fragment of a job's `steps:` list, created via `Workflow.anchor` and
+    /// assigned to more than one `Job`'s `steps`. Of the jobs sharing a given `StepsAnchor`, the
+    /// first one (in `jobs` iteration order) renders its steps tagged with a YAML anchor
+    /// (``&name``), and every later one renders a bare alias (``*name``) instead of repeating the
+    /// steps, so `Workflow.dump`/`__str__` emit real anchor/alias YAML for de-duplicated CI
+    /// config. Schema validation is unaffected either way: `Job` always stores the fully resolved
+    /// steps, so `Workflow.validate`/`is_valid` see the expanded document regardless of how the
+    /// rendered YAML folds it.
+    #[pyclass]
+    #[derive(Clone)]
+    struct StepsAnchor {
+        name: String,
+        steps: Vec<Step>,
     }
-    fn max_write_none(
-        left: WriteNonePermission,
-        right: WriteNonePermission,
-    ) -> WriteNonePermission {
-        match (left, right) {
-            (WriteNonePermission::Write, _) | (_, WriteNonePermission::Write) => {
-                WriteNonePermission::Write
-            }
-            _ => WriteNonePermission::None,
+    #[pymethods]
+    impl StepsAnchor {
+        fn __str__(&self) -> PyResult<String> {
+            (&self.steps).as_yaml_string()
         }
     }
-    fn max_read_none(left: ReadNonePermission, right: ReadNonePermission) -> ReadNonePermission {
-        match (left, right) {
-            (ReadNonePermission::Read, _) | (_, ReadNonePermission::Read) => {
-                ReadNonePermission::Read
-            }
-            _ => ReadNonePermission::None,
-        }
+
+    #[derive(Clone)]
+    enum JobSecretsOptions {
+        Secrets(HashMap<String, StringLike>),
+        Inherit,
     }
-    fn merge_rw_opt(
-        left: Option<ReadWriteNonePermission>,
-        right: Option<ReadWriteNonePermission>,
-    ) -> Option<ReadWriteNonePermission> {
-        match (left, right) {
-            (None, None) => None,
-            (Some(value), None) | (None, Some(value)) => Some(value),
-            (Some(left), Some(right)) => Some(max_read_write_none(left, right)),
-        }
+    #[pyclass]
+    #[derive(Clone)]
+    struct JobSecrets {
+        options: JobSecretsOptions,
     }
-    fn merge_write_opt(
-        left: Option<WriteNonePermission>,
-        right: Option<WriteNonePermission>,
-    ) -> Option<WriteNonePermission> {
-        match (left, right) {
-            (None, None) => None,
-            (Some(value), None) | (None, Some(value)) => Some(value),
-            (Some(left), Some(right)) => Some(max_write_none(left, right)),
+    #[pymethods]
+    impl JobSecrets {
+        #[new]
+        fn new(secrets: HashMap<String, StringLike>) -> Self {
+            Self {
+                options: JobSecretsOptions::Secrets(secrets),
+            }
         }
-    }
-    fn merge_read_opt(
-        left: Option<ReadNonePermission>,
-        right: Option<ReadNonePermission>,
-    ) -> Option<ReadNonePermission> {
-        match (left, right) {
-            (None, None) => None,
-            (Some(value), None) | (None, Some(value)) => Some(value),
-            (Some(left), Some(right)) => Some(max_read_none(left, right)),
+        #[staticmethod]
+        fn inherit() -> Self {
+            Self {
+                options: JobSecretsOptions::Inherit,
+            }
         }
-    }
-    fn merge_individual(
-        left: &IndividualPermissions,
-        right: &IndividualPermissions,
-    ) -> IndividualPermissions {
-        IndividualPermissions {
-            actions: merge_rw_opt(left.actions, right.actions),
-            artifact_metadata: merge_rw_opt(left.artifact_metadata, right.artifact_metadata),
-            attestations: merge_rw_opt(left.attestations, right.attestations),
-            checks: merge_rw_opt(left.checks, right.checks),
-            contents: merge_rw_opt(left.contents, right.contents),
-            deployments: merge_rw_opt(left.deployments, right.deployments),
-            id_token: merge_write_opt(left.id_token, right.id_token),
-            issues: merge_rw_opt(left.issues, right.issues),
-            models: merge_read_opt(left.models, right.models),
-            discussions: merge_rw_opt(left.discussions, right.discussions),
-            packages: merge_rw_opt(left.packages, right.packages),
-            pages: merge_rw_opt(left.pages, right.pages),
-            pull_requests: merge_rw_opt(left.pull_requests, right.pull_requests),
-            security_events: merge_rw_opt(left.security_events, right.security_events),
-            statuses: merge_rw_opt(left.statuses, right.statuses),
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
         }
-    }
-    fn individual_from_permissions(permissions: &Permissions) -> IndividualPermissions {
-        match &permissions.options {
-            PermissionsOptions::Individual(indiv) => indiv.clone(),
-            PermissionsOptions::None => IndividualPermissions {
-                actions: None,
-                artifact_metadata: None,
-                attestations: None,
-                checks: None,
-                contents: None,
-                deployments: None,
-                id_token: None,
-                issues: None,
-                models: None,
-                discussions: None,
-                packages: None,
-                pages: None,
-                pull_requests: None,
-                security_events: None,
-                statuses: None,
-            },
-            PermissionsOptions::ReadAll => IndividualPermissions {
-                actions: Some(ReadWriteNonePermission::Read),
-                artifact_metadata: Some(ReadWriteNonePermission::Read),
-                attestations: Some(ReadWriteNonePermission::Read),
-                checks: Some(ReadWriteNonePermission::Read),
-                contents: Some(ReadWriteNonePermission::Read),
-                deployments: Some(ReadWriteNonePermission::Read),
-                id_token: Some(WriteNonePermission::None),
-                issues: Some(ReadWriteNonePermission::Read),
-                models: Some(ReadNonePermission::Read),
-                discussions: Some(ReadWriteNonePermission::Read),
-                packages: Some(ReadWriteNonePermission::Read),
-                pages: Some(ReadWriteNonePermission::Read),
-                pull_requests: Some(ReadWriteNonePermission::Read),
-                security_events: Some(ReadWriteNonePermission::Read),
-                statuses: Some(ReadWriteNonePermission::Read),
-            },
-            PermissionsOptions::WriteAll => IndividualPermissions {
-                actions: Some(ReadWriteNonePermission::Write),
-                artifact_metadata: Some(ReadWriteNonePermission::Write),
-                attestations: Some(ReadWriteNonePermission::Write),
-                checks: Some(ReadWriteNonePermission::Write),
-                contents: Some(ReadWriteNonePermission::Write),
-                deployments: Some(ReadWriteNonePermission::Write),
-                id_token: Some(WriteNonePermission::Write),
-                issues: Some(ReadWriteNonePermission::Write),
-                models: Some(ReadNonePermission::Read),
-                discussions: Some(ReadWriteNonePermission::Write),
-                packages: Some(ReadWriteNonePermission::Write),
-                pages: Some(ReadWriteNonePermission::Write),
-                pull_requests: Some(ReadWriteNonePermission::Write),
-                security_events: Some(ReadWriteNonePermission::Write),
-                statuses: Some(ReadWriteNonePermission::Write),
-            },
+
+        /// Parse a ``secrets:`` value from an existing workflow file, either the literal
+        /// ``"inherit"`` form or the mapping of secret name to value.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            job_secrets_from_yaml(&parse_yaml_document(yaml)?)
         }
     }
-    fn merge_permissions(left: &Permissions, right: &Permissions) -> Permissions {
-        match (&left.options, &right.options) {
-            (PermissionsOptions::WriteAll, _) | (_, PermissionsOptions::WriteAll) => Permissions {
-                options: PermissionsOptions::WriteAll,
-            },
-            (PermissionsOptions::None, PermissionsOptions::None) => Permissions {
-                options: PermissionsOptions::None,
-            },
-            (
-                PermissionsOptions::ReadAll,
-                PermissionsOptions::None | PermissionsOptions::ReadAll,
-            )
-            | (PermissionsOptions::None, PermissionsOptions::ReadAll) => Permissions {
-                options: PermissionsOptions::ReadAll,
-            },
-            _ => Permissions {
-                options: PermissionsOptions::Individual(merge_individual(
-                    &individual_from_permissions(left),
-                    &individual_from_permissions(right),
-                )),
-            },
+    /// Parse a `secrets:` node already extracted from a larger document, shared between
+    /// `JobSecrets::from_yaml` and `Job::from_yaml`.
+    fn job_secrets_from_yaml(yaml: &Yaml) -> PyResult<JobSecrets> {
+        if matches!(yaml, Yaml::String(s) if s == "inherit") {
+            return Ok(JobSecrets {
+                options: JobSecretsOptions::Inherit,
+            });
         }
+        let hash = expect_hash(yaml, "secrets")?;
+        let secrets = hash
+            .iter()
+            .map(|(k, v)| {
+                Ok((
+                    yaml_scalar_to_string(k, "secrets")?,
+                    parse_string_like(v, "secrets")?,
+                ))
+            })
+            .collect::<PyResult<HashMap<String, StringLike>>>()?;
+        Ok(JobSecrets {
+            options: JobSecretsOptions::Secrets(secrets),
+        })
     }
-    fn is_empty_individual_permissions(permissions: &Permissions) -> bool {
-        match &permissions.options {
-            PermissionsOptions::Individual(indiv) => indiv.is_empty(),
-            _ => false,
+    impl Yamlable for &JobSecrets {
+        fn as_yaml(&self) -> Yaml {
+            match &self.options {
+                JobSecretsOptions::Secrets(s) => {
+                    let mut hash = Hash::new();
+                    for (k, v) in s {
+                        hash.insert_yaml(k, v);
+                    }
+                    Yaml::Hash(hash)
+                }
+                JobSecretsOptions::Inherit => Yaml::String("inherit".to_string()),
+            }
         }
     }
 
-    #[derive(Clone)]
-    enum RunsOnSpecOptions {
-        Group(StringLike),
-        Labels(StringLike),
-        GroupAndLabels(StringLike, StringLike),
-    }
     #[pyclass]
     #[derive(Clone)]
-    struct RunsOnSpec {
-        options: RunsOnSpecOptions,
+    struct Job {
+        name: Option<StringLike>,
+        permissions: Option<Permissions>,
+        needs: Option<Vec<String>>,
+        condition: Option<Either<BooleanExpression, String>>,
+        runs_on: Option<RunsOn>,
+        snapshot: Option<String>,
+        environment: Option<Environment>,
+        concurrency: Option<Concurrency>,
+        outputs: Option<PyMap<String, StringLike>>,
+        env: Option<PyMap<String, StringLike>>,
+        defaults: Option<Defaults>,
+        steps: Option<Vec<Step>>,
+        steps_anchor: Option<String>,
+        timeout_minutes: Option<IntLike>,
+        strategy: Option<Strategy>,
+        continue_on_error: Option<Either<StringLike, BoolLike>>,
+        container: Option<Container>,
+        services: Option<PyMap<String, Container>>,
+        uses: Option<String>,
+        with: Option<Hash>,
+        secrets: Option<JobSecrets>,
     }
+    // TODO: support mapping syntax for snapshot argument
     #[pymethods]
-    impl RunsOnSpec {
+    impl Job {
+        /// A set of `Step`s which runs in an isolated environemnt.
+        ///
+        /// All `Job`s in a `Workflow` run in parallel by default, but dependencies can be created
+        /// with the ``needs`` argument. `Job`s may also specify the ``uses`` argument to call
+        /// another reusable workflow rather than a set of `Step`s. Note that exactly one of ``runs_on`` or ``uses`` must be specified, and a `Job` which specifies ``uses`` may not have any ``steps``.
+        ///
+        /// Parameters
+        /// ----------
+        /// steps
+        ///     The set of `Step`s to run sequentially, or a `StepsAnchor` (see `Workflow.anchor`)
+        ///     shared with other jobs to de-duplicate a repeated step list in the emitted YAML.
+        /// name
+        ///     The name of the job displayed in the GitHub UI.
+        /// permissions
+        ///     The permissions granted to the ``GITHUB_TOKEN`` for this job.
+        /// use_recommended_permissions
+        ///     Merge recommended permissions from steps into this job's permissions.
+        /// needs
+        ///     A list of `Job`s which must complete successfully before this job will run.
+        /// condition
+        ///     A condition which must be met for this job to run. Note that this represents the ``if`` key in the actual YAML file.
+        /// runs_on
+        ///     The type of machine on which the job will run (e.g. ``'ubuntu-latest'``)
+        /// snapshot
+        ///     Used to generate a custom image.
+        /// environment
+        ///     Used to define the environment which the job references. This is often used for trusted publishing.
+        /// concurrency
+        ///     The concurrency group for this job. Only a single `Job` or `Workflow` using the
+        ///     same concurrency group will run at a time.
+        /// outputs
+        ///     Used to create a set of outputs available to all downstream jobs which depend on this job.
+        /// env
+        ///     A map of environment variables available to all steps in the job.
+        /// defaults
+        ///     A map of default settings which apply to all steps in the job.
+        /// timeout_minutes
+        ///     The maximum number of minutes to let a job run before GitHub automatically cancels it (defaults to 360 if not specified).
+        /// strategy
+        ///     Used to create a matrix strategy for a job, generating multiple jobs from a single one based on combinations of matrix variables.
+        /// continue_on_error
+        ///     If True, this job's failure will not trigger workflow failure (or cause other matrix strategy jobs to fail if ``fail-fast`` is enabled).
+        /// container
+        ///     Used to create a container to run any steps of a job which do not already specify one.
+        /// services
+        ///     Used to host service containers for a job.
+        /// uses
+        ///     Used to specify the location and version of a reusable workflow file to run as a
+        ///     job. Such a job will not specify ``runs_on`` or ``steps``.
+        /// with_opts
+        ///     A map of inputs which are passed to a reusable workflow job specified by ``uses``. Note that this represents the ``with`` key in the actual YAML file.
+        /// secrets
+        ///     A map of secrets passed to a resulable workflow job specified by ``uses``.
         #[new]
-        fn new(group: StringLike, labels: StringLike) -> Self {
-            Self {
-                options: RunsOnSpecOptions::GroupAndLabels(group, labels),
+        #[pyo3(signature = (*, steps=None, name=None, permissions=None, use_recommended_permissions=true, needs=None, condition=None, runs_on=None, snapshot=None, environment=None, concurrency=None, outputs=None, env=None, defaults=None, timeout_minutes=None, strategy=None, continue_on_error=None, container=None, services=None, uses=None, with_opts=None, secrets=None))]
+        fn new(
+            steps: Option<Either<StepsAnchor, Vec<Step>>>,
+            name: Option<StringLike>,
+            permissions: Option<Permissions>,
+            use_recommended_permissions: bool,
+            needs: Option<OneOrVec<String>>,
+            condition: Option<Either<BooleanExpression, String>>,
+            runs_on: Option<RunsOn>,
+            snapshot: Option<String>,
+            environment: Option<Environment>,
+            concurrency: Option<Concurrency>,
+            outputs: Option<PyMap<String, StringLike>>,
+            env: Option<PyMap<String, StringLike>>,
+            defaults: Option<Defaults>,
+            timeout_minutes: Option<IntLike>,
+            strategy: Option<Strategy>,
+            continue_on_error: Option<Either<StringLike, BoolLike>>,
+            container: Option<Container>,
+            services: Option<PyMap<String, Container>>,
+            uses: Option<String>,
+            with_opts: Option<Bound<PyDict>>,
+            secrets: Option<JobSecrets>,
+        ) -> PyResult<Self> {
+            let (steps, steps_anchor) = match steps {
+                Some(Either::A(anchor)) => (Some(anchor.steps), Some(anchor.name)),
+                Some(Either::B(steps)) => (Some(steps), None),
+                None => (None, None),
+            };
+            match (&uses, &runs_on) {
+                (Some(_), Some(_)) => {
+                    return Err(PyValueError::new_err(
+                        "Job cannot set both 'uses' and 'runs_on'",
+                    ));
+                }
+                (None, None) => {
+                    return Err(PyValueError::new_err(
+                        "Job must set either 'uses' or 'runs_on'",
+                    ));
+                }
+                _ => {}
             }
-        }
-        #[staticmethod]
-        fn group(group: StringLike) -> Self {
-            Self {
-                options: RunsOnSpecOptions::Group(group),
+            if uses.is_some() {
+                if let Some(steps) = &steps
+                    && !steps.is_empty()
+                {
+                    return Err(PyValueError::new_err(
+                        "Job using 'uses' cannot define 'steps'",
+                    ));
+                }
+            } else {
+                match &steps {
+                    Some(steps) if !steps.is_empty() => {}
+                    _ => {
+                        return Err(PyValueError::new_err(
+                            "Job with 'runs_on' must define at least one step",
+                        ));
+                    }
+                }
             }
-        }
-        #[staticmethod]
-        fn labels(labels: StringLike) -> Self {
-            Self {
-                options: RunsOnSpecOptions::Labels(labels),
+            if let Some(name) = &name {
+                validate_string_like(name, ALLOWED_JOB_NAME)?;
+            }
+            if let Some(condition) = &condition {
+                validate_condition(condition, ALLOWED_JOB_IF)?;
+            }
+            if let Some(runs_on) = &runs_on {
+                validate_runs_on(runs_on)?;
+            }
+            if let Some(environment) = &environment {
+                validate_environment(environment)?;
+            }
+            if let Some(concurrency) = &concurrency {
+                validate_concurrency(concurrency, ALLOWED_JOB_CONCURRENCY)?;
+            }
+            if let Some(outputs) = &outputs {
+                validate_string_map(outputs, ALLOWED_JOB_OUTPUTS)?;
+            }
+            if let Some(env) = &env {
+                validate_string_map(env, ALLOWED_JOB_ENV)?;
+            }
+            if let Some(defaults) = &defaults
+                && let Some(run_defaults) = &defaults.run_defaults
+            {
+                if let Some(shell) = &run_defaults.shell {
+                    validate_string_like(shell, ALLOWED_JOB_DEFAULTS_RUN)?;
+                }
+                if let Some(working_directory) = &run_defaults.working_directory {
+                    validate_string_like(working_directory, ALLOWED_JOB_DEFAULTS_RUN)?;
+                }
+            }
+            if let Some(strategy) = &strategy {
+                if let Some(fast_fail) = &strategy.fast_fail {
+                    validate_bool_like(fast_fail, ALLOWED_JOB_STRATEGY)?;
+                }
+                if let Some(max_parallel) = &strategy.max_parallel {
+                    validate_int_like(max_parallel, ALLOWED_JOB_STRATEGY)?;
+                }
+            }
+            if let Some(timeout_minutes) = &timeout_minutes {
+                validate_int_like(timeout_minutes, ALLOWED_JOB_TIMEOUT_MINUTES)?;
+            }
+            if let Some(continue_on_error) = &continue_on_error {
+                match continue_on_error {
+                    Either::A(string_like) => {
+                        validate_string_like(string_like, ALLOWED_JOB_CONTINUE_ON_ERROR)?;
+                    }
+                    Either::B(bool_like) => {
+                        validate_bool_like(bool_like, ALLOWED_JOB_CONTINUE_ON_ERROR)?;
+                    }
+                }
+            }
+            if let Some(container) = &container {
+                validate_container_for_job(container)?;
+            }
+            if let Some(services) = &services {
+                for (_, container) in services.iter() {
+                    validate_container_for_service(container)?;
+                }
+            }
+            if let Some(with_opts) = &with_opts {
+                validate_with_opts(with_opts, ALLOWED_JOB_WITH)?;
+            }
+            if let Some(secrets) = &secrets
+                && let JobSecretsOptions::Secrets(values) = &secrets.options
+            {
+                for value in values.values() {
+                    validate_string_like(value, ALLOWED_JOB_SECRETS)?;
+                }
+            }
+            let mut permissions = permissions;
+            if use_recommended_permissions
+                && let Some(steps) = &steps
+                && let Some(merged) = minimal_permissions_from_steps(steps)
+            {
+                permissions = Some(match permissions {
+                    Some(current) => merge_permissions(&current, &merged),
+                    None => merged,
+                });
             }
+            Ok(Self {
+                name,
+                permissions,
+                needs: needs.map(OneOrVec::into_vec),
+                condition,
+                runs_on,
+                snapshot,
+                environment,
+                concurrency,
+                outputs,
+                env,
+                defaults,
+                steps,
+                steps_anchor,
+                timeout_minutes,
+                strategy,
+                continue_on_error,
+                container,
+                services,
+                uses,
+                with: with_opts.map(|w| w.try_as_hash()).transpose()?,
+                secrets,
+            })
         }
-
         fn __str__(&self) -> PyResult<String> {
             self.as_yaml_string()
         }
-    }
-    impl Yamlable for &RunsOnSpec {
-        fn as_yaml(&self) -> Yaml {
-            let mut out = Hash::new();
-            match &self.options {
-                RunsOnSpecOptions::Group(group) => out.insert_yaml("group", group),
-                RunsOnSpecOptions::Labels(labels) => out.insert_yaml("labels", labels),
-                RunsOnSpecOptions::GroupAndLabels(group, labels) => {
-                    out.insert_yaml("group", group);
-                    out.insert_yaml("labels", labels);
-                }
-            }
-            Yaml::Hash(out)
+
+        /// Fold the `recommended_permissions` of every step in this job into the tightest
+        /// `Permissions` that covers all of them, the same computation
+        /// `use_recommended_permissions` performs automatically in `__new__`. Returns `None` if
+        /// this job has no steps, or none of them recommend any permissions.
+        fn minimize(&self) -> Option<Permissions> {
+            minimal_permissions_from_steps(self.steps.as_deref().unwrap_or_default())
         }
-    }
 
-    #[derive(Clone)]
-    enum RunsOn {
-        String(StringLike),
-        Array(Vec<StringLike>),
-        Spec(RunsOnSpec),
+        /// Parse a single ``jobs.<job_id>:`` entry from an existing workflow file. Replicates the
+        /// ``uses``/``runs_on`` exclusivity `__new__` enforces, but skips `__new__`'s
+        /// context-specific `validate_*` calls: those guard which expression contexts are valid
+        /// for newly-authored Python construction, and applying them to arbitrary pre-existing
+        /// YAML risks rejecting legitimate real-world workflows. `recommended_permissions` is
+        /// always `None`, since it is yamloom-specific metadata with no representation in
+        /// rendered YAML.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            job_from_hash(expect_hash(&parse_yaml_document(yaml)?, "job")?.clone())
+        }
+    }
+    /// Parse a job's ``continue-on-error:``, which (unlike a step's, always `BoolLike`) may also
+    /// be an arbitrary non-boolean string. A literal `true`/`false` becomes `BoolLike`; anything
+    /// else, including a `${{ ... }}` expression, is kept as a `StringLike` rather than forced
+    /// through `as_bool`.
+    fn parse_continue_on_error(yaml: &Yaml, what: &str) -> PyResult<Either<StringLike, BoolLike>> {
+        if let Yaml::Boolean(b) = yaml {
+            return Ok(Either::B(Either::B(*b)));
+        }
+        let s = yaml_scalar_to_string(yaml, what)?;
+        match parse_scalar(&s) {
+            Some(expr) => Ok(Either::A(Either::A(expr))),
+            None => match s.parse::<bool>() {
+                Ok(b) => Ok(Either::B(Either::B(b))),
+                Err(_) => Ok(Either::A(Either::B(s))),
+            },
+        }
     }
-    impl<'a, 'py> FromPyObject<'a, 'py> for RunsOn {
-        type Error = PyErr;
-
-        fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
-            if let Ok(spec) = obj.extract::<RunsOnSpec>() {
-                Ok(Self::Spec(spec))
-            } else if let Ok(list) = obj.extract::<Vec<StringLike>>() {
-                Ok(Self::Array(list))
-            } else if let Ok(single) = obj.extract::<StringLike>() {
-                Ok(Self::String(single))
-            } else {
-                Err(PyValueError::new_err(
-                    "Expected a 'RunsOnSpec', list of strings, or a single string",
-                ))
+    fn job_from_hash(mut hash: Hash) -> PyResult<Job> {
+        let name = hash_take(&mut hash, "name")
+            .map(|y| parse_string_like(&y, "job.name"))
+            .transpose()?;
+        let permissions = hash_take(&mut hash, "permissions")
+            .map(|y| permissions_from_yaml(&y))
+            .transpose()?;
+        let needs = hash_take(&mut hash, "needs")
+            .map(|y| match &y {
+                Yaml::Array(arr) => arr
+                    .iter()
+                    .map(|v| yaml_scalar_to_string(v, "job.needs"))
+                    .collect::<PyResult<Vec<_>>>(),
+                other => Ok(vec![yaml_scalar_to_string(other, "job.needs")?]),
+            })
+            .transpose()?;
+        let condition = hash_take(&mut hash, "if")
+            .map(|y| parse_condition(&y, "job.if"))
+            .transpose()?;
+        let runs_on = hash_take(&mut hash, "runs-on")
+            .map(|y| parse_runs_on(&y))
+            .transpose()?;
+        let snapshot = hash_take(&mut hash, "snapshot")
+            .map(|y| yaml_scalar_to_string(&y, "job.snapshot"))
+            .transpose()?;
+        let environment = hash_take(&mut hash, "environment")
+            .map(|y| environment_from_yaml(&y))
+            .transpose()?;
+        let concurrency = hash_take(&mut hash, "concurrency")
+            .map(|y| concurrency_from_yaml(&y))
+            .transpose()?;
+        let outputs = hash_take(&mut hash, "outputs")
+            .map(|y| parse_string_map(&y, "job.outputs"))
+            .transpose()?;
+        let env = hash_take(&mut hash, "env")
+            .map(|y| parse_string_map(&y, "job.env"))
+            .transpose()?;
+        let defaults = hash_take(&mut hash, "defaults")
+            .map(|y| defaults_from_hash(expect_hash(&y, "job.defaults")?.clone()))
+            .transpose()?;
+        let steps = hash_take(&mut hash, "steps")
+            .map(|y| {
+                y.as_vec()
+                    .ok_or_else(|| PyValueError::new_err("Expected 'job.steps' to be a list"))?
+                    .iter()
+                    .map(|s| step_from_hash(expect_hash(s, "job.steps")?.clone()))
+                    .collect::<PyResult<Vec<_>>>()
+            })
+            .transpose()?;
+        let timeout_minutes = hash_take(&mut hash, "timeout-minutes")
+            .map(|y| parse_int_like(&y, "job.timeout-minutes"))
+            .transpose()?;
+        let strategy = hash_take(&mut hash, "strategy")
+            .map(|y| strategy_from_hash(expect_hash(&y, "job.strategy")?.clone()))
+            .transpose()?;
+        let continue_on_error = hash_take(&mut hash, "continue-on-error")
+            .map(|y| parse_continue_on_error(&y, "job.continue-on-error"))
+            .transpose()?;
+        let container = hash_take(&mut hash, "container")
+            .map(|y| container_from_yaml(&y))
+            .transpose()?;
+        let services = hash_take(&mut hash, "services")
+            .map(|y| {
+                expect_hash(&y, "job.services")?
+                    .iter()
+                    .map(|(k, v)| {
+                        Ok((
+                            yaml_scalar_to_string(k, "job.services")?,
+                            container_from_yaml(v)?,
+                        ))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+                    .map(|pairs| pairs.into_iter().collect())
+            })
+            .transpose()?;
+        let uses = hash_take(&mut hash, "uses")
+            .map(|y| yaml_scalar_to_string(&y, "job.uses"))
+            .transpose()?;
+        let with = hash_take(&mut hash, "with")
+            .map(|y| expect_hash(&y, "job.with").map(|h| h.clone()))
+            .transpose()?;
+        let secrets = hash_take(&mut hash, "secrets")
+            .map(|y| job_secrets_from_yaml(&y))
+            .transpose()?;
+        match (&uses, &runs_on) {
+            (Some(_), Some(_)) => {
+                return Err(PyValueError::new_err(
+                    "Job cannot set both 'uses' and 'runs-on'",
+                ));
+            }
+            (None, None) => {
+                return Err(PyValueError::new_err(
+                    "Job must set either 'uses' or 'runs-on'",
+                ));
             }
+            _ => {}
         }
+        reject_unknown_keys(&hash, "job")?;
+        Ok(Job {
+            name,
+            permissions,
+            needs,
+            condition,
+            runs_on,
+            snapshot,
+            environment,
+            concurrency,
+            outputs,
+            env,
+            defaults,
+            steps,
+            steps_anchor: None,
+            timeout_minutes,
+            strategy,
+            continue_on_error,
+            container,
+            services,
+            uses,
+            with,
+            secrets,
+        })
     }
-    impl Yamlable for &RunsOn {
+    impl Yamlable for &Job {
         fn as_yaml(&self) -> Yaml {
-            match self {
-                RunsOn::String(s) => s.as_yaml(),
-                RunsOn::Array(l) => l.as_yaml(),
-                RunsOn::Spec(spec) => spec.as_yaml(),
+            let mut out = Hash::new();
+            out.insert_yaml_opt("name", &self.name);
+            out.insert_yaml_opt("permissions", self.permissions.as_ref());
+            let needs = self.needs.clone().map(OneOrVec::from);
+            out.insert_yaml_opt("needs", &needs);
+            out.insert_yaml_opt("if", &self.condition);
+            out.insert_yaml_opt("runs-on", &self.runs_on);
+            out.insert_yaml_opt("snapshot", &self.snapshot);
+            out.insert_yaml_opt("environment", &self.environment);
+            out.insert_yaml_opt("concurrency", &self.concurrency);
+            out.insert_yaml_opt("outputs", &self.outputs);
+            out.insert_yaml_opt("env", &self.env);
+            if let Some(defaults) = &self.defaults {
+                out.insert_yaml_opt("defaults", defaults.maybe_as_yaml());
             }
+            out.insert_yaml_opt("strategy", &self.strategy);
+            out.insert_yaml_opt("steps", &self.steps);
+            out.insert_yaml_opt("timeout-minutes", &self.timeout_minutes);
+            out.insert_yaml_opt("continue-on-error", &self.continue_on_error);
+            out.insert_yaml_opt("container", &self.container);
+            out.insert_yaml_opt("services", &self.services);
+            out.insert_yaml_opt("uses", &self.uses);
+            out.insert_yaml_opt("with", self.with.clone().map(Yaml::Hash));
+            out.insert_yaml_opt("secrets", &self.secrets);
+            Yaml::Hash(out)
         }
     }
 
-    #[pyclass]
-    #[derive(Clone)]
-    struct Environment {
-        name: StringLike,
-        url: Option<StringLike>,
-    }
-    #[pymethods]
-    impl Environment {
-        #[new]
-        #[pyo3(signature = (name, url = None))]
-        fn new(name: StringLike, url: Option<StringLike>) -> Self {
-            Self { name, url }
-        }
-
-        fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
-        }
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum BranchProtectionRuleActivity {
+        Created,
+        Edited,
+        Deleted,
     }
-    impl Yamlable for &Environment {
-        fn as_yaml(&self) -> Yaml {
-            if let Some(url) = &self.url {
-                let mut sub = Hash::new();
-                sub.insert_yaml("name", &self.name);
-                sub.insert_yaml("url", url);
-                Yaml::Hash(sub)
-            } else {
-                self.name.as_yaml()
+    impl ActivityKind for BranchProtectionRuleActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Edited, Self::Deleted];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct Concurrency {
-        group: StringLike,
-        cancel_in_progress: Option<BoolLike>,
+    struct BranchProtectionRuleEvent {
+        types: ActivityTypes<BranchProtectionRuleActivity>,
     }
     #[pymethods]
-    impl Concurrency {
+    impl BranchProtectionRuleEvent {
         #[new]
-        #[pyo3(signature = (group, *, cancel_in_progress=None))]
-        fn new(group: StringLike, cancel_in_progress: Option<BoolLike>) -> Self {
+        #[pyo3(signature = (*, created=false, edited=false, deleted=false))]
+        fn new(created: bool, edited: bool, deleted: bool) -> Self {
             Self {
-                group,
-                cancel_in_progress,
+                types: ActivityTypes::from_flags([(BranchProtectionRuleActivity::Created, created), (BranchProtectionRuleActivity::Edited, edited), (BranchProtectionRuleActivity::Deleted, deleted)]),
             }
         }
 
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            BranchProtectionRuleActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
         fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
+            self.maybe_as_yaml_string()
         }
-    }
-    impl Yamlable for &Concurrency {
-        fn as_yaml(&self) -> Yaml {
-            let mut out = Hash::new();
-            out.insert_yaml("group", &self.group);
-            out.insert_yaml_opt("cancel-in-progress", &self.cancel_in_progress);
-            Yaml::Hash(out)
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            branch_protection_rule_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "branch_protection_rule",
+            )?)
         }
-    }
 
-    #[pyclass]
-    #[derive(Clone)]
-    struct RunDefaults {
-        shell: Option<StringLike>,
-        working_directory: Option<StringLike>,
-    }
-    #[pymethods]
-    impl RunDefaults {
-        #[new]
-        #[pyo3(signature = (*, shell=None, working_directory=None))]
-        fn new(shell: Option<StringLike>, working_directory: Option<StringLike>) -> Self {
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
             Self {
-                shell,
-                working_directory,
+                types: merge_activity_types(&self.types, &other.types),
             }
         }
     }
-    impl MaybeYamlable for &RunDefaults {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut out = Hash::new();
-            out.insert_yaml_opt("shell", &self.shell);
-            out.insert_yaml_opt("working-directory", &self.working_directory);
-            if out.is_empty() {
-                None
-            } else {
-                Some(Yaml::Hash(out))
-            }
+    impl ActivityEvent for BranchProtectionRuleEvent {
+        type Kind = BranchProtectionRuleActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
         }
     }
-    #[pyclass]
-    #[derive(Clone)]
-    struct Defaults {
-        defaults: Option<PyMap<String, String>>,
-        run_defaults: Option<RunDefaults>,
+    fn branch_protection_rule_event_from_hash(mut hash: Hash) -> PyResult<BranchProtectionRuleEvent> {
+        let types = ActivityTypes::parse(&mut hash, "branch_protection_rule")?;
+        reject_unknown_keys(&hash, "branch_protection_rule")?;
+        Ok(BranchProtectionRuleEvent { types })
     }
-    #[pymethods]
-    impl Defaults {
-        #[new]
-        #[pyo3(signature = (*, defaults=None, run_defaults=None))]
-        fn new(defaults: Option<PyMap<String, String>>, run_defaults: Option<RunDefaults>) -> Self {
-            Self {
-                defaults,
-                run_defaults,
-            }
-        }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum CheckRunActivity {
+        Created,
+        Rerequested,
+        Completed,
+        RequestedAction,
     }
-    impl MaybeYamlable for &Defaults {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut out = Hash::new();
-            if let Some(run_defaults) = &self.run_defaults {
-                out.insert_yaml_opt("run", run_defaults.maybe_as_yaml());
-            }
-            out.insert_yaml_opt("defaults", &self.defaults);
-            if out.is_empty() {
-                None
-            } else {
-                Some(Yaml::Hash(out))
+    impl ActivityKind for CheckRunActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Rerequested, Self::Completed, Self::RequestedAction];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Rerequested => "rerequested",
+                Self::Completed => "completed",
+                Self::RequestedAction => "requested_action",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct Matrix {
-        matrix: Option<Hash>,
-        include: Option<Array>,
-        exclude: Option<Array>,
+    struct CheckRunEvent {
+        types: ActivityTypes<CheckRunActivity>,
     }
     #[pymethods]
-    impl Matrix {
+    impl CheckRunEvent {
         #[new]
-        #[pyo3(signature = (*, include = None, exclude = None, **matrix))]
-        fn new(
-            include: Option<&Bound<'_, PyList>>,
-            exclude: Option<&Bound<'_, PyList>>,
-            matrix: Option<&Bound<'_, PyDict>>,
-        ) -> PyResult<Self> {
-            Ok(Self {
-                matrix: matrix
-                    .map(|m| {
-                        let mut hash = Hash::new();
-                        for (k, v) in m.iter() {
-                            hash.insert_yaml(k.try_as_yaml()?, v.try_as_yaml()?);
-                        }
-                        Ok::<Hash, PyErr>(hash)
-                    })
-                    .transpose()?,
-                include: include
-                    .map(|i| {
-                        let mut arr = Array::new();
-                        for v in i.iter() {
-                            arr.push_yaml(v.try_as_yaml()?);
-                        }
-                        Ok::<Array, PyErr>(arr)
-                    })
-                    .transpose()?,
-                exclude: exclude
-                    .map(|e| {
-                        let mut arr = Array::new();
-                        for v in e.iter() {
-                            arr.push_yaml(v.try_as_yaml()?);
-                        }
-                        Ok::<Array, PyErr>(arr)
-                    })
-                    .transpose()?,
-            })
+        #[pyo3(signature = (*, created=false, rerequested=false, completed=false, requested_action=false))]
+        fn new(created: bool, rerequested: bool, completed: bool, requested_action: bool) -> Self {
+            Self {
+                types: ActivityTypes::from_flags([(CheckRunActivity::Created, created), (CheckRunActivity::Rerequested, rerequested), (CheckRunActivity::Completed, completed), (CheckRunActivity::RequestedAction, requested_action)]),
+            }
+        }
+
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            CheckRunActivity::ALL.iter().map(|k| k.as_str()).collect()
         }
 
         fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
+            self.maybe_as_yaml_string()
         }
-    }
-    impl Yamlable for &Matrix {
-        fn as_yaml(&self) -> Yaml {
-            let mut matrix = self.matrix.clone().unwrap_or_default();
-            matrix.insert_yaml_opt("include", &self.include);
-            matrix.insert_yaml_opt("exclude", &self.exclude);
-            Yaml::Hash(matrix)
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            check_run_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "check_run",
+            )?)
         }
-    }
 
-    #[pyclass]
-    #[derive(Clone)]
-    struct Strategy {
-        matrix: Option<Matrix>,
-        fast_fail: Option<BoolLike>,
-        max_parallel: Option<IntLike>,
-    }
-    #[pymethods]
-    impl Strategy {
-        #[new]
-        #[pyo3(signature = (*, matrix = None, fast_fail = None, max_parallel = None))]
-        fn new(
-            // TODO: prevent invalid state where all are None
-            matrix: Option<Matrix>,
-            fast_fail: Option<BoolLike>,
-            max_parallel: Option<IntLike>,
-        ) -> Self {
-            Self {
-                matrix,
-                fast_fail,
-                max_parallel,
-            }
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
         }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
         }
     }
-    impl Yamlable for &Strategy {
-        fn as_yaml(&self) -> Yaml {
-            let mut strategy = Hash::new();
-            strategy.insert_yaml_opt("matrix", &self.matrix);
-            strategy.insert_yaml_opt("fail-fast", &self.fast_fail);
-            strategy.insert_yaml_opt("max-parallel", &self.max_parallel);
-            Yaml::Hash(strategy)
+    impl ActivityEvent for CheckRunEvent {
+        type Kind = CheckRunActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
         }
     }
-
-    #[pyclass]
-    #[derive(Clone)]
-    struct Credentials {
-        username: StringLike,
-        password: StringLike,
+    fn check_run_event_from_hash(mut hash: Hash) -> PyResult<CheckRunEvent> {
+        let types = ActivityTypes::parse(&mut hash, "check_run")?;
+        reject_unknown_keys(&hash, "check_run")?;
+        Ok(CheckRunEvent { types })
     }
-    #[pymethods]
-    impl Credentials {
-        #[new]
-        fn new(username: StringLike, password: StringLike) -> Self {
-            Self { username, password }
-        }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
-        }
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum CheckSuiteActivity {
+        Created,
     }
-    impl Yamlable for &Credentials {
-        fn as_yaml(&self) -> Yaml {
-            let mut out = Hash::new();
-            out.insert_yaml("username", &self.username);
-            out.insert_yaml("password", &self.password);
-            Yaml::Hash(out)
+    impl ActivityKind for CheckSuiteActivity {
+        const ALL: &'static [Self] = &[Self::Created];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+            }
         }
     }
 
     #[pyclass]
-    #[derive(Clone)]
-    struct Container {
-        image: StringLike,
-        credentials: Option<Credentials>,
-        env: Option<PyMap<String, StringLike>>,
-        ports: Option<Vec<IntLike>>,
-        volumes: Option<Vec<StringLike>>,
-        options: Option<StringLike>,
-    }
-    #[pymethods]
-    impl Container {
-        #[new]
-        #[pyo3(signature = (image, *, credentials = None, env = None, ports = None, volumes = None, options = None))]
-        fn new(
-            image: StringLike,
-            credentials: Option<Credentials>,
-            env: Option<PyMap<String, StringLike>>,
-            ports: Option<Vec<IntLike>>,
-            volumes: Option<Vec<StringLike>>,
-            options: Option<StringLike>,
-        ) -> Self {
+    #[derive(Clone)]
+    struct CheckSuiteEvent {
+        types: ActivityTypes<CheckSuiteActivity>,
+    }
+    #[pymethods]
+    impl CheckSuiteEvent {
+        #[new]
+        #[pyo3(signature = (*, created=false))]
+        fn new(created: bool) -> Self {
             Self {
-                image,
-                credentials,
-                env,
-                ports,
-                volumes,
-                options,
+                types: ActivityTypes::from_flags([(CheckSuiteActivity::Created, created)]),
             }
         }
 
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            CheckSuiteActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
         fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            check_suite_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "check_suite",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
         }
     }
-    impl Yamlable for &Container {
-        fn as_yaml(&self) -> Yaml {
-            let mut out = Hash::new();
-            out.insert_yaml("image", &self.image);
-            out.insert_yaml_opt("credentials", &self.credentials);
-            out.insert_yaml_opt("env", &self.env);
-            out.insert_yaml_opt("ports", &self.ports);
-            out.insert_yaml_opt("volumes", &self.volumes);
-            out.insert_yaml_opt("options", &self.options);
-            Yaml::Hash(out)
+    impl ActivityEvent for CheckSuiteEvent {
+        type Kind = CheckSuiteActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
         }
     }
+    fn check_suite_event_from_hash(mut hash: Hash) -> PyResult<CheckSuiteEvent> {
+        let types = ActivityTypes::parse(&mut hash, "check_suite")?;
+        reject_unknown_keys(&hash, "check_suite")?;
+        Ok(CheckSuiteEvent { types })
+    }
 
-    #[derive(Clone)]
-    enum JobSecretsOptions {
-        Secrets(HashMap<String, StringLike>),
-        Inherit,
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum DiscussionActivity {
+        Created,
+        Edited,
+        Deleted,
+        Transferred,
+        Pinned,
+        Unpinned,
+        Labeled,
+        Unlabeled,
+        Locked,
+        Unlocked,
+        CategoryChanged,
+        Answered,
+        Unanswered,
+    }
+    impl ActivityKind for DiscussionActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Edited, Self::Deleted, Self::Transferred, Self::Pinned, Self::Unpinned, Self::Labeled, Self::Unlabeled, Self::Locked, Self::Unlocked, Self::CategoryChanged, Self::Answered, Self::Unanswered];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
+                Self::Transferred => "transferred",
+                Self::Pinned => "pinned",
+                Self::Unpinned => "unpinned",
+                Self::Labeled => "labeled",
+                Self::Unlabeled => "unlabeled",
+                Self::Locked => "locked",
+                Self::Unlocked => "unlocked",
+                Self::CategoryChanged => "category_changed",
+                Self::Answered => "answered",
+                Self::Unanswered => "unanswered",
+            }
+        }
     }
+
     #[pyclass]
     #[derive(Clone)]
-    struct JobSecrets {
-        options: JobSecretsOptions,
+    struct DiscussionEvent {
+        types: ActivityTypes<DiscussionActivity>,
     }
     #[pymethods]
-    impl JobSecrets {
+    impl DiscussionEvent {
         #[new]
-        fn new(secrets: HashMap<String, StringLike>) -> Self {
+        #[pyo3(signature = (*, created=false, edited=false, deleted=false, transferred=false, pinned=false, unpinned=false, labeled=false, unlabeled=false, locked=false, unlocked=false, category_changed=false, answered=false, unanswered=false))]
+        fn new(
+            created: bool,
+            edited: bool,
+            deleted: bool,
+            transferred: bool,
+            pinned: bool,
+            unpinned: bool,
+            labeled: bool,
+            unlabeled: bool,
+            locked: bool,
+            unlocked: bool,
+            category_changed: bool,
+            answered: bool,
+            unanswered: bool,
+        ) -> Self {
             Self {
-                options: JobSecretsOptions::Secrets(secrets),
+                types: ActivityTypes::from_flags([(DiscussionActivity::Created, created), (DiscussionActivity::Edited, edited), (DiscussionActivity::Deleted, deleted), (DiscussionActivity::Transferred, transferred), (DiscussionActivity::Pinned, pinned), (DiscussionActivity::Unpinned, unpinned), (DiscussionActivity::Labeled, labeled), (DiscussionActivity::Unlabeled, unlabeled), (DiscussionActivity::Locked, locked), (DiscussionActivity::Unlocked, unlocked), (DiscussionActivity::CategoryChanged, category_changed), (DiscussionActivity::Answered, answered), (DiscussionActivity::Unanswered, unanswered)]),
             }
         }
+
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
         #[staticmethod]
-        fn inherit() -> Self {
-            Self {
-                options: JobSecretsOptions::Inherit,
-            }
+        fn allowed_types() -> Vec<&'static str> {
+            DiscussionActivity::ALL.iter().map(|k| k.as_str()).collect()
         }
 
         fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            discussion_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "discussion",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
         }
     }
-    impl Yamlable for &JobSecrets {
-        fn as_yaml(&self) -> Yaml {
-            match &self.options {
-                JobSecretsOptions::Secrets(s) => {
-                    let mut hash = Hash::new();
-                    for (k, v) in s {
-                        hash.insert_yaml(k, v);
-                    }
-                    Yaml::Hash(hash)
-                }
-                JobSecretsOptions::Inherit => Yaml::String("inherit".to_string()),
+    impl ActivityEvent for DiscussionEvent {
+        type Kind = DiscussionActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn discussion_event_from_hash(mut hash: Hash) -> PyResult<DiscussionEvent> {
+        let types = ActivityTypes::parse(&mut hash, "discussion")?;
+        reject_unknown_keys(&hash, "discussion")?;
+        Ok(DiscussionEvent { types })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum DiscussionCommentActivity {
+        Created,
+        Edited,
+        Deleted,
+    }
+    impl ActivityKind for DiscussionCommentActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Edited, Self::Deleted];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct Job {
-        name: Option<StringLike>,
-        permissions: Option<Permissions>,
-        needs: Option<Vec<String>>,
-        condition: Option<Either<BooleanExpression, String>>,
-        runs_on: Option<RunsOn>,
-        snapshot: Option<String>,
-        environment: Option<Environment>,
-        concurrency: Option<Concurrency>,
-        outputs: Option<PyMap<String, StringLike>>,
-        env: Option<PyMap<String, StringLike>>,
-        defaults: Option<Defaults>,
-        steps: Option<Vec<Step>>,
-        timeout_minutes: Option<IntLike>,
-        strategy: Option<Strategy>,
-        continue_on_error: Option<Either<StringLike, BoolLike>>,
-        container: Option<Container>,
-        services: Option<PyMap<String, Container>>,
-        uses: Option<String>,
-        with: Option<Hash>,
-        secrets: Option<JobSecrets>,
+    struct DiscussionCommentEvent {
+        types: ActivityTypes<DiscussionCommentActivity>,
     }
-    // TODO: support mapping syntax for snapshot argument
     #[pymethods]
-    impl Job {
-        /// A set of `Step`s which runs in an isolated environemnt.
-        ///
-        /// All `Job`s in a `Workflow` run in parallel by default, but dependencies can be created
-        /// with the ``needs`` argument. `Job`s may also specify the ``uses`` argument to call
-        /// another reusable workflow rather than a set of `Step`s. Note that exactly one of ``runs_on`` or ``uses`` must be specified, and a `Job` which specifies ``uses`` may not have any ``steps``.
-        ///
-        /// Parameters
-        /// ----------
-        /// steps
-        ///     The set of `Step`s to run sequentially.
-        /// name
-        ///     The name of the job displayed in the GitHub UI.
-        /// permissions
-        ///     The permissions granted to the ``GITHUB_TOKEN`` for this job.
-        /// use_recommended_permissions
-        ///     Merge recommended permissions from steps into this job's permissions.
-        /// needs
-        ///     A list of `Job`s which must complete successfully before this job will run.
-        /// condition
-        ///     A condition which must be met for this job to run. Note that this represents the ``if`` key in the actual YAML file.
-        /// runs_on
-        ///     The type of machine on which the job will run (e.g. ``'ubuntu-latest'``)
-        /// snapshot
-        ///     Used to generate a custom image.
-        /// environment
-        ///     Used to define the environment which the job references. This is often used for trusted publishing.
-        /// concurrency
-        ///     The concurrency group for this job. Only a single `Job` or `Workflow` using the
-        ///     same concurrency group will run at a time.
-        /// outputs
-        ///     Used to create a set of outputs available to all downstream jobs which depend on this job.
-        /// env
-        ///     A map of environment variables available to all steps in the job.
-        /// defaults
-        ///     A map of default settings which apply to all steps in the job.
-        /// timeout_minutes
-        ///     The maximum number of minutes to let a job run before GitHub automatically cancels it (defaults to 360 if not specified).
-        /// strategy
-        ///     Used to create a matrix strategy for a job, generating multiple jobs from a single one based on combinations of matrix variables.
-        /// continue_on_error
-        ///     If True, this job's failure will not trigger workflow failure (or cause other matrix strategy jobs to fail if ``fail-fast`` is enabled).
-        /// container
-        ///     Used to create a container to run any steps of a job which do not already specify one.
-        /// services
-        ///     Used to host service containers for a job.
-        /// uses
-        ///     Used to specify the location and version of a reusable workflow file to run as a
-        ///     job. Such a job will not specify ``runs_on`` or ``steps``.
-        /// with_opts
-        ///     A map of inputs which are passed to a reusable workflow job specified by ``uses``. Note that this represents the ``with`` key in the actual YAML file.
-        /// secrets
-        ///     A map of secrets passed to a resulable workflow job specified by ``uses``.
+    impl DiscussionCommentEvent {
         #[new]
-        #[pyo3(signature = (*, steps=None, name=None, permissions=None, use_recommended_permissions=true, needs=None, condition=None, runs_on=None, snapshot=None, environment=None, concurrency=None, outputs=None, env=None, defaults=None, timeout_minutes=None, strategy=None, continue_on_error=None, container=None, services=None, uses=None, with_opts=None, secrets=None))]
-        fn new(
-            steps: Option<Vec<Step>>,
-            name: Option<StringLike>,
-            permissions: Option<Permissions>,
-            use_recommended_permissions: bool,
-            needs: Option<Vec<String>>,
-            condition: Option<Either<BooleanExpression, String>>,
-            runs_on: Option<RunsOn>,
-            snapshot: Option<String>,
-            environment: Option<Environment>,
-            concurrency: Option<Concurrency>,
-            outputs: Option<PyMap<String, StringLike>>,
-            env: Option<PyMap<String, StringLike>>,
-            defaults: Option<Defaults>,
-            timeout_minutes: Option<IntLike>,
-            strategy: Option<Strategy>,
-            continue_on_error: Option<Either<StringLike, BoolLike>>,
-            container: Option<Container>,
-            services: Option<PyMap<String, Container>>,
-            uses: Option<String>,
-            with_opts: Option<Bound<PyDict>>,
-            secrets: Option<JobSecrets>,
-        ) -> PyResult<Self> {
-            match (&uses, &runs_on) {
-                (Some(_), Some(_)) => {
-                    return Err(PyValueError::new_err(
-                        "Job cannot set both 'uses' and 'runs_on'",
-                    ));
-                }
-                (None, None) => {
-                    return Err(PyValueError::new_err(
-                        "Job must set either 'uses' or 'runs_on'",
-                    ));
-                }
-                _ => {}
-            }
-            if uses.is_some() {
-                if let Some(steps) = &steps
-                    && !steps.is_empty()
-                {
-                    return Err(PyValueError::new_err(
-                        "Job using 'uses' cannot define 'steps'",
-                    ));
-                }
-            } else {
-                match &steps {
-                    Some(steps) if !steps.is_empty() => {}
-                    _ => {
-                        return Err(PyValueError::new_err(
-                            "Job with 'runs_on' must define at least one step",
-                        ));
-                    }
-                }
-            }
-            if let Some(name) = &name {
-                validate_string_like(name, ALLOWED_JOB_NAME)?;
-            }
-            if let Some(condition) = &condition {
-                validate_condition(condition, ALLOWED_JOB_IF)?;
-            }
-            if let Some(runs_on) = &runs_on {
-                validate_runs_on(runs_on)?;
-            }
-            if let Some(environment) = &environment {
-                validate_environment(environment)?;
-            }
-            if let Some(concurrency) = &concurrency {
-                validate_concurrency(concurrency, ALLOWED_JOB_CONCURRENCY)?;
-            }
-            if let Some(outputs) = &outputs {
-                validate_string_map(outputs, ALLOWED_JOB_OUTPUTS)?;
-            }
-            if let Some(env) = &env {
-                validate_string_map(env, ALLOWED_JOB_ENV)?;
+        #[pyo3(signature = (*, created=false, edited=false, deleted=false))]
+        fn new(created: bool, edited: bool, deleted: bool) -> Self {
+            Self {
+                types: ActivityTypes::from_flags([(DiscussionCommentActivity::Created, created), (DiscussionCommentActivity::Edited, edited), (DiscussionCommentActivity::Deleted, deleted)]),
             }
-            if let Some(defaults) = &defaults
-                && let Some(run_defaults) = &defaults.run_defaults
-            {
-                if let Some(shell) = &run_defaults.shell {
-                    validate_string_like(shell, ALLOWED_JOB_DEFAULTS_RUN)?;
-                }
-                if let Some(working_directory) = &run_defaults.working_directory {
-                    validate_string_like(working_directory, ALLOWED_JOB_DEFAULTS_RUN)?;
-                }
+        }
+
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            DiscussionCommentActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            discussion_comment_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "discussion_comment",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
             }
-            if let Some(strategy) = &strategy {
-                if let Some(fast_fail) = &strategy.fast_fail {
-                    validate_bool_like(fast_fail, ALLOWED_JOB_STRATEGY)?;
-                }
-                if let Some(max_parallel) = &strategy.max_parallel {
-                    validate_int_like(max_parallel, ALLOWED_JOB_STRATEGY)?;
-                }
+        }
+    }
+    impl ActivityEvent for DiscussionCommentEvent {
+        type Kind = DiscussionCommentActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn discussion_comment_event_from_hash(mut hash: Hash) -> PyResult<DiscussionCommentEvent> {
+        let types = ActivityTypes::parse(&mut hash, "discussion_comment")?;
+        reject_unknown_keys(&hash, "discussion_comment")?;
+        Ok(DiscussionCommentEvent { types })
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct ImageVersionEvent {
+        names: Option<Vec<String>>,
+        versions: Option<Vec<String>>,
+    }
+    #[pymethods]
+    impl ImageVersionEvent {
+        #[new]
+        #[pyo3(signature = (*, names=None, versions=None))]
+        fn new(names: Option<Vec<String>>, versions: Option<Vec<String>>) -> Self {
+            let names = names.filter(|v| !v.is_empty());
+            let versions = versions.filter(|v| !v.is_empty());
+            Self { names, versions }
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            image_version_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "image_version",
+            )?)
+        }
+    }
+    impl MaybeYamlable for &ImageVersionEvent {
+        fn maybe_as_yaml(&self) -> Option<Yaml> {
+            if self.names.is_some() || self.versions.is_some() {
+                let mut out = Hash::new();
+                out.insert_yaml_opt("names", self.names.as_ref());
+                out.insert_yaml_opt("versions", self.versions.as_ref());
+                Some(Yaml::Hash(out))
+            } else {
+                None
             }
-            if let Some(timeout_minutes) = &timeout_minutes {
-                validate_int_like(timeout_minutes, ALLOWED_JOB_TIMEOUT_MINUTES)?;
+        }
+    }
+    fn image_version_event_from_hash(mut hash: Hash) -> PyResult<ImageVersionEvent> {
+        let names = take_string_vec(&mut hash, "names", "image_version")?;
+        let versions = take_string_vec(&mut hash, "versions", "image_version")?;
+        reject_unknown_keys(&hash, "image_version")?;
+        Ok(ImageVersionEvent { names, versions })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum IssueCommentActivity {
+        Created,
+        Edited,
+        Deleted,
+    }
+    impl ActivityKind for IssueCommentActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Edited, Self::Deleted];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
             }
-            if let Some(continue_on_error) = &continue_on_error {
-                match continue_on_error {
-                    Either::A(string_like) => {
-                        validate_string_like(string_like, ALLOWED_JOB_CONTINUE_ON_ERROR)?;
-                    }
-                    Either::B(bool_like) => {
-                        validate_bool_like(bool_like, ALLOWED_JOB_CONTINUE_ON_ERROR)?;
-                    }
-                }
+        }
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct IssueCommentEvent {
+        types: ActivityTypes<IssueCommentActivity>,
+    }
+    #[pymethods]
+    impl IssueCommentEvent {
+        #[new]
+        #[pyo3(signature = (*, created=false, edited=false, deleted=false))]
+        fn new(created: bool, edited: bool, deleted: bool) -> Self {
+            Self {
+                types: ActivityTypes::from_flags([(IssueCommentActivity::Created, created), (IssueCommentActivity::Edited, edited), (IssueCommentActivity::Deleted, deleted)]),
             }
-            if let Some(container) = &container {
-                validate_container_for_job(container)?;
+        }
+
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            IssueCommentActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            issue_comment_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "issue_comment",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
+        }
+    }
+    impl ActivityEvent for IssueCommentEvent {
+        type Kind = IssueCommentActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn issue_comment_event_from_hash(mut hash: Hash) -> PyResult<IssueCommentEvent> {
+        let types = ActivityTypes::parse(&mut hash, "issue_comment")?;
+        reject_unknown_keys(&hash, "issue_comment")?;
+        Ok(IssueCommentEvent { types })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum IssuesActivity {
+        Created,
+        Edited,
+        Deleted,
+        Transferred,
+        Pinned,
+        Unpinned,
+        Closed,
+        Reopened,
+        Assigned,
+        Unassigned,
+        Labeled,
+        Unlabeled,
+        Locked,
+        Unlocked,
+        Milestoned,
+        Demilestoned,
+        Typed,
+        Untyped,
+    }
+    impl ActivityKind for IssuesActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Edited, Self::Deleted, Self::Transferred, Self::Pinned, Self::Unpinned, Self::Closed, Self::Reopened, Self::Assigned, Self::Unassigned, Self::Labeled, Self::Unlabeled, Self::Locked, Self::Unlocked, Self::Milestoned, Self::Demilestoned, Self::Typed, Self::Untyped];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
+                Self::Transferred => "transferred",
+                Self::Pinned => "pinned",
+                Self::Unpinned => "unpinned",
+                Self::Closed => "closed",
+                Self::Reopened => "reopened",
+                Self::Assigned => "assigned",
+                Self::Unassigned => "unassigned",
+                Self::Labeled => "labeled",
+                Self::Unlabeled => "unlabeled",
+                Self::Locked => "locked",
+                Self::Unlocked => "unlocked",
+                Self::Milestoned => "milestoned",
+                Self::Demilestoned => "demilestoned",
+                Self::Typed => "typed",
+                Self::Untyped => "untyped",
             }
-            if let Some(services) = &services {
-                for (_, container) in services.iter() {
-                    validate_container_for_service(container)?;
-                }
+        }
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct IssuesEvent {
+        types: ActivityTypes<IssuesActivity>,
+    }
+    #[pymethods]
+    impl IssuesEvent {
+        #[new]
+        #[pyo3(signature = (*, created=false, edited=false, deleted=false,  transferred=false, pinned=false, unpinned=false, closed=false, reopened=false, assigned=false, unassigned=false, labeled=false, unlabeled=false, locked=false, unlocked=false, milestoned=false, demilestoned=false, typed=false, untyped=false))]
+        fn new(
+            created: bool,
+            edited: bool,
+            deleted: bool,
+            transferred: bool,
+            pinned: bool,
+            unpinned: bool,
+            closed: bool,
+            reopened: bool,
+            assigned: bool,
+            unassigned: bool,
+            labeled: bool,
+            unlabeled: bool,
+            locked: bool,
+            unlocked: bool,
+            milestoned: bool,
+            demilestoned: bool,
+            typed: bool,
+            untyped: bool,
+        ) -> Self {
+            Self {
+                types: ActivityTypes::from_flags([(IssuesActivity::Created, created), (IssuesActivity::Edited, edited), (IssuesActivity::Deleted, deleted), (IssuesActivity::Transferred, transferred), (IssuesActivity::Pinned, pinned), (IssuesActivity::Unpinned, unpinned), (IssuesActivity::Closed, closed), (IssuesActivity::Reopened, reopened), (IssuesActivity::Assigned, assigned), (IssuesActivity::Unassigned, unassigned), (IssuesActivity::Labeled, labeled), (IssuesActivity::Unlabeled, unlabeled), (IssuesActivity::Locked, locked), (IssuesActivity::Unlocked, unlocked), (IssuesActivity::Milestoned, milestoned), (IssuesActivity::Demilestoned, demilestoned), (IssuesActivity::Typed, typed), (IssuesActivity::Untyped, untyped)]),
             }
-            if let Some(with_opts) = &with_opts {
-                validate_with_opts(with_opts, ALLOWED_JOB_WITH)?;
+        }
+
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            IssuesActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            issues_event_from_hash(expect_hash_or_empty(&parse_yaml_document(yaml)?, "issues")?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
             }
-            if let Some(secrets) = &secrets
-                && let JobSecretsOptions::Secrets(values) = &secrets.options
-            {
-                for value in values.values() {
-                    validate_string_like(value, ALLOWED_JOB_SECRETS)?;
-                }
+        }
+    }
+    impl ActivityEvent for IssuesEvent {
+        type Kind = IssuesActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn issues_event_from_hash(mut hash: Hash) -> PyResult<IssuesEvent> {
+        let types = ActivityTypes::parse(&mut hash, "issues")?;
+        reject_unknown_keys(&hash, "issues")?;
+        Ok(IssuesEvent { types })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum LabelActivity {
+        Created,
+        Edited,
+        Deleted,
+    }
+    impl ActivityKind for LabelActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Edited, Self::Deleted];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
             }
-            let mut permissions = permissions;
-            if use_recommended_permissions && let Some(steps) = &steps {
-                let mut saw_recommendation = false;
-                let mut merged: Option<Permissions> = None;
-                for step in steps {
-                    if let Some(step_permissions) = &step.recommended_permissions {
-                        saw_recommendation = true;
-                        merged = Some(match &merged {
-                            Some(current) => merge_permissions(current, step_permissions),
-                            None => step_permissions.clone(),
-                        });
-                    }
-                }
-                if saw_recommendation {
-                    let mut merged = merged.unwrap_or_else(Permissions::none);
-                    if is_empty_individual_permissions(&merged) {
-                        merged = Permissions::none();
-                    }
-                    permissions = Some(match permissions {
-                        Some(current) => merge_permissions(&current, &merged),
-                        None => merged,
-                    });
-                }
+        }
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct LabelEvent {
+        types: ActivityTypes<LabelActivity>,
+    }
+    #[pymethods]
+    impl LabelEvent {
+        #[new]
+        #[pyo3(signature = (*, created=false, edited=false, deleted=false))]
+        fn new(created: bool, edited: bool, deleted: bool) -> Self {
+            Self {
+                types: ActivityTypes::from_flags([(LabelActivity::Created, created), (LabelActivity::Edited, edited), (LabelActivity::Deleted, deleted)]),
             }
-            Ok(Self {
-                name,
-                permissions,
-                needs,
-                condition,
-                runs_on,
-                snapshot,
-                environment,
-                concurrency,
-                outputs,
-                env,
-                defaults,
-                steps,
-                timeout_minutes,
-                strategy,
-                continue_on_error,
-                container,
-                services,
-                uses,
-                with: with_opts.map(|w| w.try_as_hash()).transpose()?,
-                secrets,
-            })
         }
+
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            LabelActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
         fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
+            self.maybe_as_yaml_string()
         }
-    }
-    impl Yamlable for &Job {
-        fn as_yaml(&self) -> Yaml {
-            let mut out = Hash::new();
-            out.insert_yaml_opt("name", &self.name);
-            out.insert_yaml_opt("permissions", self.permissions.as_ref());
-            out.insert_yaml_opt("needs", &self.needs);
-            out.insert_yaml_opt("if", &self.condition);
-            out.insert_yaml_opt("runs-on", &self.runs_on);
-            out.insert_yaml_opt("snapshot", &self.snapshot);
-            out.insert_yaml_opt("environment", &self.environment);
-            out.insert_yaml_opt("concurrency", &self.concurrency);
-            out.insert_yaml_opt("outputs", &self.outputs);
-            out.insert_yaml_opt("env", &self.env);
-            if let Some(defaults) = &self.defaults {
-                out.insert_yaml_opt("defaults", defaults.maybe_as_yaml());
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            label_event_from_hash(expect_hash_or_empty(&parse_yaml_document(yaml)?, "label")?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
+        }
+    }
+    impl ActivityEvent for LabelEvent {
+        type Kind = LabelActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn label_event_from_hash(mut hash: Hash) -> PyResult<LabelEvent> {
+        let types = ActivityTypes::parse(&mut hash, "label")?;
+        reject_unknown_keys(&hash, "label")?;
+        Ok(LabelEvent { types })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum MergeGroupActivity {
+        ChecksRequested,
+    }
+    impl ActivityKind for MergeGroupActivity {
+        const ALL: &'static [Self] = &[Self::ChecksRequested];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::ChecksRequested => "checks_requested",
             }
-            out.insert_yaml_opt("strategy", &self.strategy);
-            out.insert_yaml_opt("steps", &self.steps);
-            out.insert_yaml_opt("timeout-minutes", &self.timeout_minutes);
-            out.insert_yaml_opt("continue-on-error", &self.continue_on_error);
-            out.insert_yaml_opt("container", &self.container);
-            out.insert_yaml_opt("services", &self.services);
-            out.insert_yaml_opt("uses", &self.uses);
-            out.insert_yaml_opt("with", self.with.clone().map(Yaml::Hash));
-            out.insert_yaml_opt("secrets", &self.secrets);
-            Yaml::Hash(out)
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct BranchProtectionRuleEvent {
-        created: bool,
-        edited: bool,
-        deleted: bool,
+    struct MergeGroupEvent {
+        types: ActivityTypes<MergeGroupActivity>,
     }
     #[pymethods]
-    impl BranchProtectionRuleEvent {
+    impl MergeGroupEvent {
         #[new]
-        #[pyo3(signature = (*, created=false, edited=false, deleted=false))]
-        fn new(created: bool, edited: bool, deleted: bool) -> Self {
+        #[pyo3(signature = (*, checks_requested=false))]
+        fn new(checks_requested: bool) -> Self {
             Self {
-                created,
-                edited,
-                deleted,
+                types: ActivityTypes::from_flags([(MergeGroupActivity::ChecksRequested, checks_requested)]),
             }
         }
 
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            MergeGroupActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            merge_group_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "merge_group",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
+        }
     }
-    impl MaybeYamlable for &BranchProtectionRuleEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created || self.edited || self.deleted {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
+    impl ActivityEvent for MergeGroupEvent {
+        type Kind = MergeGroupActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn merge_group_event_from_hash(mut hash: Hash) -> PyResult<MergeGroupEvent> {
+        let types = ActivityTypes::parse(&mut hash, "merge_group")?;
+        reject_unknown_keys(&hash, "merge_group")?;
+        Ok(MergeGroupEvent { types })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum MilestoneActivity {
+        Created,
+        Closed,
+        Opened,
+        Edited,
+        Deleted,
+    }
+    impl ActivityKind for MilestoneActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Closed, Self::Opened, Self::Edited, Self::Deleted];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Closed => "closed",
+                Self::Opened => "opened",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct CheckRunEvent {
-        created: bool,
-        rerequested: bool,
-        completed: bool,
-        requested_action: bool,
+    struct MilestoneEvent {
+        types: ActivityTypes<MilestoneActivity>,
     }
     #[pymethods]
-    impl CheckRunEvent {
+    impl MilestoneEvent {
         #[new]
-        #[pyo3(signature = (*, created=false, rerequested=false, completed=false, requested_action=false))]
-        fn new(created: bool, rerequested: bool, completed: bool, requested_action: bool) -> Self {
+        #[pyo3(signature = (*, created=false, closed=false, opened=false, edited=false, deleted=false))]
+        fn new(created: bool, closed: bool, opened: bool, edited: bool, deleted: bool) -> Self {
             Self {
-                created,
-                rerequested,
-                completed,
-                requested_action,
+                types: ActivityTypes::from_flags([(MilestoneActivity::Created, created), (MilestoneActivity::Closed, closed), (MilestoneActivity::Opened, opened), (MilestoneActivity::Edited, edited), (MilestoneActivity::Deleted, deleted)]),
             }
         }
 
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            MilestoneActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
-    }
-    impl MaybeYamlable for &CheckRunEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created || self.rerequested || self.completed || self.requested_action {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("rerequested", self.rerequested);
-                arr.push_yaml_cond("completed", self.completed);
-                arr.push_yaml_cond("requested_action", self.requested_action);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            milestone_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "milestone",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
+        }
+    }
+    impl ActivityEvent for MilestoneEvent {
+        type Kind = MilestoneActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn milestone_event_from_hash(mut hash: Hash) -> PyResult<MilestoneEvent> {
+        let types = ActivityTypes::parse(&mut hash, "milestone")?;
+        reject_unknown_keys(&hash, "milestone")?;
+        Ok(MilestoneEvent { types })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum PullRequestActivity {
+        Assigned,
+        Unassigned,
+        Labeled,
+        Unlabeled,
+        Opened,
+        Edited,
+        Closed,
+        Reopened,
+        Synchronize,
+        ConvertedToDraft,
+        Locked,
+        Unlocked,
+        Enqueued,
+        Dequeued,
+        Milestoned,
+        Demilestoned,
+        ReadyForReview,
+        ReviewRequested,
+        ReviewRequestRemoved,
+        AutoMergeEnabled,
+        AutoMergeDisabled,
+    }
+    impl ActivityKind for PullRequestActivity {
+        const ALL: &'static [Self] = &[
+            Self::Assigned,
+            Self::Unassigned,
+            Self::Labeled,
+            Self::Unlabeled,
+            Self::Opened,
+            Self::Edited,
+            Self::Closed,
+            Self::Reopened,
+            Self::Synchronize,
+            Self::ConvertedToDraft,
+            Self::Locked,
+            Self::Unlocked,
+            Self::Enqueued,
+            Self::Dequeued,
+            Self::Milestoned,
+            Self::Demilestoned,
+            Self::ReadyForReview,
+            Self::ReviewRequested,
+            Self::ReviewRequestRemoved,
+            Self::AutoMergeEnabled,
+            Self::AutoMergeDisabled,
+        ];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Assigned => "assigned",
+                Self::Unassigned => "unassigned",
+                Self::Labeled => "labeled",
+                Self::Unlabeled => "unlabeled",
+                Self::Opened => "opened",
+                Self::Edited => "edited",
+                Self::Closed => "closed",
+                Self::Reopened => "reopened",
+                Self::Synchronize => "synchronize",
+                Self::ConvertedToDraft => "converted_to_draft",
+                Self::Locked => "locked",
+                Self::Unlocked => "unlocked",
+                Self::Enqueued => "enqueued",
+                Self::Dequeued => "dequeued",
+                Self::Milestoned => "milestoned",
+                Self::Demilestoned => "demilestoned",
+                Self::ReadyForReview => "ready_for_review",
+                Self::ReviewRequested => "review_requested",
+                Self::ReviewRequestRemoved => "review_request_removed",
+                Self::AutoMergeEnabled => "auto_merge_enabled",
+                Self::AutoMergeDisabled => "auto_merge_disabled",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct CheckSuiteEvent {
-        created: bool,
+    struct PullRequestEvent {
+        types: ActivityTypes<PullRequestActivity>,
+        branches: Option<Vec<String>>,
+        branches_ignore: Option<Vec<String>>,
+        paths: Option<Vec<String>>,
+        paths_ignore: Option<Vec<String>>,
     }
     #[pymethods]
-    impl CheckSuiteEvent {
+    impl PullRequestEvent {
         #[new]
-        #[pyo3(signature = (*, created=false))]
-        fn new(created: bool) -> Self {
-            Self { created }
+        #[pyo3(signature = (*, branches=None, branches_ignore=None, paths=None, paths_ignore=None, assigned=false, unassigned=false, labeled=false, unlabeled=false, opened=false, edited=false, closed=false, reopened=false, synchronize=false, converted_to_draft=false, locked=false, unlocked=false, enqueued=false, dequeued=false, milestoned=false, demilestoned=false, ready_for_review=false, review_requested=false, review_request_removed=false, auto_merge_enabled=false, auto_merge_disabled=false))]
+        fn new(
+            branches: Option<Vec<String>>,
+            branches_ignore: Option<Vec<String>>,
+            paths: Option<Vec<String>>,
+            paths_ignore: Option<Vec<String>>,
+            assigned: bool,
+            unassigned: bool,
+            labeled: bool,
+            unlabeled: bool,
+            opened: bool,
+            edited: bool,
+            closed: bool,
+            reopened: bool,
+            synchronize: bool,
+            converted_to_draft: bool,
+            locked: bool,
+            unlocked: bool,
+            enqueued: bool,
+            dequeued: bool,
+            milestoned: bool,
+            demilestoned: bool,
+            ready_for_review: bool,
+            review_requested: bool,
+            review_request_removed: bool,
+            auto_merge_enabled: bool,
+            auto_merge_disabled: bool,
+        ) -> PyResult<Self> {
+            let branches = branches.filter(|v| !v.is_empty());
+            let branches_ignore = branches_ignore.filter(|v| !v.is_empty());
+            let paths = paths.filter(|v| !v.is_empty());
+            let paths_ignore = paths_ignore.filter(|v| !v.is_empty());
+            validate_filter_conflict(
+                branches.as_ref(),
+                branches_ignore.as_ref(),
+                "pull_request",
+                "branches",
+                "branches-ignore",
+            )?;
+            validate_filter_conflict(
+                paths.as_ref(),
+                paths_ignore.as_ref(),
+                "pull_request",
+                "paths",
+                "paths-ignore",
+            )?;
+            Ok(Self {
+                types: ActivityTypes::from_flags([
+                    (PullRequestActivity::Assigned, assigned),
+                    (PullRequestActivity::Unassigned, unassigned),
+                    (PullRequestActivity::Labeled, labeled),
+                    (PullRequestActivity::Unlabeled, unlabeled),
+                    (PullRequestActivity::Opened, opened),
+                    (PullRequestActivity::Edited, edited),
+                    (PullRequestActivity::Closed, closed),
+                    (PullRequestActivity::Reopened, reopened),
+                    (PullRequestActivity::Synchronize, synchronize),
+                    (PullRequestActivity::ConvertedToDraft, converted_to_draft),
+                    (PullRequestActivity::Locked, locked),
+                    (PullRequestActivity::Unlocked, unlocked),
+                    (PullRequestActivity::Enqueued, enqueued),
+                    (PullRequestActivity::Dequeued, dequeued),
+                    (PullRequestActivity::Milestoned, milestoned),
+                    (PullRequestActivity::Demilestoned, demilestoned),
+                    (PullRequestActivity::ReadyForReview, ready_for_review),
+                    (PullRequestActivity::ReviewRequested, review_requested),
+                    (
+                        PullRequestActivity::ReviewRequestRemoved,
+                        review_request_removed,
+                    ),
+                    (PullRequestActivity::AutoMergeEnabled, auto_merge_enabled),
+                    (PullRequestActivity::AutoMergeDisabled, auto_merge_disabled),
+                ]),
+                branches,
+                branches_ignore,
+                paths,
+                paths_ignore,
+            })
+        }
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            PullRequestActivity::ALL.iter().map(|k| k.as_str()).collect()
         }
 
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            pull_request_event_from_hash(
+                expect_hash_or_empty(&parse_yaml_document(yaml)?, "pull_request")?,
+                "pull_request",
+            )
+        }
+
+        /// Whether `ref_name` (e.g. `"main"`) would satisfy this event's `branches`/
+        /// `branches-ignore` filter.
+        fn matches_ref(&self, ref_name: &str) -> PyResult<bool> {
+            matches_filter(ref_name, &self.branches, &self.branches_ignore)
+        }
+
+        /// Whether `path` (a changed file, relative to the repository root) would satisfy this
+        /// event's `paths`/`paths-ignore` filter.
+        fn matches_path(&self, path: &str) -> PyResult<bool> {
+            matches_filter(path, &self.paths, &self.paths_ignore)
+        }
+
+        /// What activity types `other` turned on or off relative to `self` (keyed
+        /// `"enabled"`/`"disabled"`), plus which `branches`/`branches-ignore`/`paths`/
+        /// `paths-ignore` entries were added or removed.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            let dict = diff_activity_types(py, &self.types, &other.types)?;
+            let dict = dict.into_bound(py);
+            let (branches_added, branches_removed) = diff_filter_list(&self.branches, &other.branches);
+            dict.set_item("branches_added", branches_added)?;
+            dict.set_item("branches_removed", branches_removed)?;
+            let (branches_ignore_added, branches_ignore_removed) =
+                diff_filter_list(&self.branches_ignore, &other.branches_ignore);
+            dict.set_item("branches_ignore_added", branches_ignore_added)?;
+            dict.set_item("branches_ignore_removed", branches_ignore_removed)?;
+            let (paths_added, paths_removed) = diff_filter_list(&self.paths, &other.paths);
+            dict.set_item("paths_added", paths_added)?;
+            dict.set_item("paths_removed", paths_removed)?;
+            let (paths_ignore_added, paths_ignore_removed) =
+                diff_filter_list(&self.paths_ignore, &other.paths_ignore);
+            dict.set_item("paths_ignore_added", paths_ignore_added)?;
+            dict.set_item("paths_ignore_removed", paths_ignore_removed)?;
+            Ok(dict.unbind())
+        }
+
+        /// The union of `self` and `other`'s enabled activity types and filter lists.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+                branches: merge_filter_list(&self.branches, &other.branches),
+                branches_ignore: merge_filter_list(&self.branches_ignore, &other.branches_ignore),
+                paths: merge_filter_list(&self.paths, &other.paths),
+                paths_ignore: merge_filter_list(&self.paths_ignore, &other.paths_ignore),
+            }
+        }
     }
-    impl MaybeYamlable for &CheckSuiteEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
+    impl ActivityEvent for PullRequestEvent {
+        type Kind = PullRequestActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+        fn extra_yaml(&self, out: &mut Hash) {
+            out.insert_yaml_opt("branches", self.branches.as_ref());
+            out.insert_yaml_opt("branches-ignore", self.branches_ignore.as_ref());
+            out.insert_yaml_opt("paths", self.paths.as_ref());
+            out.insert_yaml_opt("paths-ignore", self.paths_ignore.as_ref());
+        }
+    }
+    /// Shared by `PullRequestEvent.from_yaml` and `Events.pull_request_target`, which reuses this
+    /// same struct; `what` lets error messages name whichever trigger is actually being parsed.
+    fn pull_request_event_from_hash(mut hash: Hash, what: &str) -> PyResult<PullRequestEvent> {
+        let branches = take_string_vec(&mut hash, "branches", what)?;
+        let branches_ignore = take_string_vec(&mut hash, "branches-ignore", what)?;
+        let paths = take_string_vec(&mut hash, "paths", what)?;
+        let paths_ignore = take_string_vec(&mut hash, "paths-ignore", what)?;
+        validate_filter_conflict(
+            branches.as_ref(),
+            branches_ignore.as_ref(),
+            what,
+            "branches",
+            "branches-ignore",
+        )?;
+        validate_filter_conflict(
+            paths.as_ref(),
+            paths_ignore.as_ref(),
+            what,
+            "paths",
+            "paths-ignore",
+        )?;
+        let types = ActivityTypes::parse(&mut hash, what)?;
+        reject_unknown_keys(&hash, what)?;
+        Ok(PullRequestEvent {
+            types,
+            branches,
+            branches_ignore,
+            paths,
+            paths_ignore,
+        })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum PullRequestReviewActivity {
+        Submitted,
+        Edited,
+        Dismissed,
+    }
+    impl ActivityKind for PullRequestReviewActivity {
+        const ALL: &'static [Self] = &[Self::Submitted, Self::Edited, Self::Dismissed];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Submitted => "submitted",
+                Self::Edited => "edited",
+                Self::Dismissed => "dismissed",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct DiscussionEvent {
-        created: bool,
-        edited: bool,
-        deleted: bool,
-        transferred: bool,
-        pinned: bool,
-        unpinned: bool,
-        labeled: bool,
-        unlabeled: bool,
-        locked: bool,
-        unlocked: bool,
-        category_changed: bool,
-        answered: bool,
-        unanswered: bool,
+    struct PullRequestReviewEvent {
+        types: ActivityTypes<PullRequestReviewActivity>,
     }
     #[pymethods]
-    impl DiscussionEvent {
+    impl PullRequestReviewEvent {
         #[new]
-        #[pyo3(signature = (*, created=false, edited=false, deleted=false, transferred=false, pinned=false, unpinned=false, labeled=false, unlabeled=false, locked=false, unlocked=false, category_changed=false, answered=false, unanswered=false))]
-        fn new(
-            created: bool,
-            edited: bool,
-            deleted: bool,
-            transferred: bool,
-            pinned: bool,
-            unpinned: bool,
-            labeled: bool,
-            unlabeled: bool,
-            locked: bool,
-            unlocked: bool,
-            category_changed: bool,
-            answered: bool,
-            unanswered: bool,
-        ) -> Self {
+        #[pyo3(signature = (*, submitted=false, edited=false, dismissed=false))]
+        fn new(submitted: bool, edited: bool, dismissed: bool) -> Self {
             Self {
-                created,
-                edited,
-                deleted,
-                transferred,
-                pinned,
-                unpinned,
-                labeled,
-                unlabeled,
-                locked,
-                unlocked,
-                category_changed,
-                answered,
-                unanswered,
+                types: ActivityTypes::from_flags([(PullRequestReviewActivity::Submitted, submitted), (PullRequestReviewActivity::Edited, edited), (PullRequestReviewActivity::Dismissed, dismissed)]),
             }
         }
 
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            PullRequestReviewActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            pull_request_review_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "pull_request_review",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
+        }
     }
-    impl MaybeYamlable for &DiscussionEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created
-                || self.edited
-                || self.deleted
-                || self.transferred
-                || self.pinned
-                || self.unpinned
-                || self.labeled
-                || self.unlabeled
-                || self.locked
-                || self.unlocked
-                || self.category_changed
-                || self.answered
-                || self.unanswered
-            {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                arr.push_yaml_cond("transferred", self.transferred);
-                arr.push_yaml_cond("pinned", self.pinned);
-                arr.push_yaml_cond("unpinned", self.unpinned);
-                arr.push_yaml_cond("labeled", self.labeled);
-                arr.push_yaml_cond("unlabeled", self.unlabeled);
-                arr.push_yaml_cond("locked", self.locked);
-                arr.push_yaml_cond("unlocked", self.unlocked);
-                arr.push_yaml_cond("category_changed", self.category_changed);
-                arr.push_yaml_cond("answered", self.answered);
-                arr.push_yaml_cond("unanswered", self.unanswered);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
+    impl ActivityEvent for PullRequestReviewEvent {
+        type Kind = PullRequestReviewActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn pull_request_review_event_from_hash(mut hash: Hash) -> PyResult<PullRequestReviewEvent> {
+        let types = ActivityTypes::parse(&mut hash, "pull_request_review")?;
+        reject_unknown_keys(&hash, "pull_request_review")?;
+        Ok(PullRequestReviewEvent { types })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum PullRequestReviewCommentActivity {
+        Created,
+        Edited,
+        Deleted,
+    }
+    impl ActivityKind for PullRequestReviewCommentActivity {
+        const ALL: &'static [Self] = &[Self::Created, Self::Edited, Self::Deleted];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Created => "created",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct DiscussionCommentEvent {
-        created: bool,
-        edited: bool,
-        deleted: bool,
+    struct PullRequestReviewCommentEvent {
+        types: ActivityTypes<PullRequestReviewCommentActivity>,
     }
     #[pymethods]
-    impl DiscussionCommentEvent {
+    impl PullRequestReviewCommentEvent {
         #[new]
-        #[pyo3(signature = (*, created=false, edited=false, deleted=false))]
+        #[pyo3(signature = (*, created=false,edited=false, deleted=false))]
         fn new(created: bool, edited: bool, deleted: bool) -> Self {
             Self {
-                created,
-                edited,
-                deleted,
+                types: ActivityTypes::from_flags([(PullRequestReviewCommentActivity::Created, created), (PullRequestReviewCommentActivity::Edited, edited), (PullRequestReviewCommentActivity::Deleted, deleted)]),
+            }
+        }
+
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            PullRequestReviewCommentActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            pull_request_review_comment_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "pull_request_review_comment",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
             }
         }
+    }
+    impl ActivityEvent for PullRequestReviewCommentEvent {
+        type Kind = PullRequestReviewCommentActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn pull_request_review_comment_event_from_hash(mut hash: Hash) -> PyResult<PullRequestReviewCommentEvent> {
+        let types = ActivityTypes::parse(&mut hash, "pull_request_review_comment")?;
+        reject_unknown_keys(&hash, "pull_request_review_comment")?;
+        Ok(PullRequestReviewCommentEvent { types })
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct PushEvent {
+        branches: Option<Vec<String>>,
+        branches_ignore: Option<Vec<String>>,
+        tags: Option<Vec<String>>,
+        tags_ignore: Option<Vec<String>>,
+        paths: Option<Vec<String>>,
+        paths_ignore: Option<Vec<String>>,
+    }
+    #[pymethods]
+    impl PushEvent {
+        #[new]
+        #[pyo3(signature = (*, branches=None, branches_ignore=None, tags=None, tags_ignore=None, paths=None, paths_ignore=None))]
+        fn new(
+            branches: Option<Vec<String>>,
+            branches_ignore: Option<Vec<String>>,
+            tags: Option<Vec<String>>,
+            tags_ignore: Option<Vec<String>>,
+            paths: Option<Vec<String>>,
+            paths_ignore: Option<Vec<String>>,
+        ) -> PyResult<Self> {
+            validate_filter_conflict(
+                branches.as_ref(),
+                branches_ignore.as_ref(),
+                "push",
+                "branches",
+                "branches-ignore",
+            )?;
+            validate_filter_conflict(
+                tags.as_ref(),
+                tags_ignore.as_ref(),
+                "push",
+                "tags",
+                "tags-ignore",
+            )?;
+            validate_filter_conflict(
+                paths.as_ref(),
+                paths_ignore.as_ref(),
+                "push",
+                "paths",
+                "paths-ignore",
+            )?;
+            Ok(Self {
+                branches,
+                branches_ignore,
+                tags,
+                tags_ignore,
+                paths,
+                paths_ignore,
+            })
+        }
 
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            push_event_from_hash(expect_hash_or_empty(&parse_yaml_document(yaml)?, "push")?)
+        }
+
+        /// Whether `ref_name` (e.g. `"main"`) would satisfy this event's `branches`/
+        /// `branches-ignore` filter.
+        fn matches_ref(&self, ref_name: &str) -> PyResult<bool> {
+            matches_filter(ref_name, &self.branches, &self.branches_ignore)
+        }
+
+        /// Whether `tag` (e.g. `"v1.0.0"`) would satisfy this event's `tags`/`tags-ignore`
+        /// filter.
+        fn matches_tag(&self, tag: &str) -> PyResult<bool> {
+            matches_filter(tag, &self.tags, &self.tags_ignore)
+        }
+
+        /// Whether `path` (a changed file, relative to the repository root) would satisfy this
+        /// event's `paths`/`paths-ignore` filter.
+        fn matches_path(&self, path: &str) -> PyResult<bool> {
+            matches_filter(path, &self.paths, &self.paths_ignore)
+        }
+
+        /// Which `branches`/`branches-ignore`/`tags`/`tags-ignore`/`paths`/`paths-ignore`
+        /// entries `other` added or removed relative to `self`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            let dict = PyDict::new(py);
+            let (branches_added, branches_removed) = diff_filter_list(&self.branches, &other.branches);
+            dict.set_item("branches_added", branches_added)?;
+            dict.set_item("branches_removed", branches_removed)?;
+            let (branches_ignore_added, branches_ignore_removed) =
+                diff_filter_list(&self.branches_ignore, &other.branches_ignore);
+            dict.set_item("branches_ignore_added", branches_ignore_added)?;
+            dict.set_item("branches_ignore_removed", branches_ignore_removed)?;
+            let (tags_added, tags_removed) = diff_filter_list(&self.tags, &other.tags);
+            dict.set_item("tags_added", tags_added)?;
+            dict.set_item("tags_removed", tags_removed)?;
+            let (tags_ignore_added, tags_ignore_removed) =
+                diff_filter_list(&self.tags_ignore, &other.tags_ignore);
+            dict.set_item("tags_ignore_added", tags_ignore_added)?;
+            dict.set_item("tags_ignore_removed", tags_ignore_removed)?;
+            let (paths_added, paths_removed) = diff_filter_list(&self.paths, &other.paths);
+            dict.set_item("paths_added", paths_added)?;
+            dict.set_item("paths_removed", paths_removed)?;
+            let (paths_ignore_added, paths_ignore_removed) =
+                diff_filter_list(&self.paths_ignore, &other.paths_ignore);
+            dict.set_item("paths_ignore_added", paths_ignore_added)?;
+            dict.set_item("paths_ignore_removed", paths_ignore_removed)?;
+            Ok(dict.unbind())
+        }
+
+        /// The union of `self` and `other`'s filter lists.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                branches: merge_filter_list(&self.branches, &other.branches),
+                branches_ignore: merge_filter_list(&self.branches_ignore, &other.branches_ignore),
+                tags: merge_filter_list(&self.tags, &other.tags),
+                tags_ignore: merge_filter_list(&self.tags_ignore, &other.tags_ignore),
+                paths: merge_filter_list(&self.paths, &other.paths),
+                paths_ignore: merge_filter_list(&self.paths_ignore, &other.paths_ignore),
+            }
+        }
     }
-    impl MaybeYamlable for &DiscussionCommentEvent {
+    impl MaybeYamlable for &PushEvent {
         fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created || self.edited || self.deleted {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("branches", self.branches.as_ref());
+            out.insert_yaml_opt("branches-ignore", self.branches_ignore.as_ref());
+            out.insert_yaml_opt("tags", self.tags.as_ref());
+            out.insert_yaml_opt("tags-ignore", self.tags_ignore.as_ref());
+            out.insert_yaml_opt("paths", self.paths.as_ref());
+            out.insert_yaml_opt("paths-ignore", self.paths_ignore.as_ref());
+            if out.is_empty() {
                 None
+            } else {
+                Some(Yaml::Hash(out))
             }
         }
     }
-
-    #[pyclass]
-    #[derive(Clone)]
-    struct ImageVersionEvent {
-        names: Option<Vec<String>>,
-        versions: Option<Vec<String>>,
+    fn push_event_from_hash(mut hash: Hash) -> PyResult<PushEvent> {
+        let branches = take_string_vec(&mut hash, "branches", "push")?;
+        let branches_ignore = take_string_vec(&mut hash, "branches-ignore", "push")?;
+        let tags = take_string_vec(&mut hash, "tags", "push")?;
+        let tags_ignore = take_string_vec(&mut hash, "tags-ignore", "push")?;
+        let paths = take_string_vec(&mut hash, "paths", "push")?;
+        let paths_ignore = take_string_vec(&mut hash, "paths-ignore", "push")?;
+        validate_filter_conflict(
+            branches.as_ref(),
+            branches_ignore.as_ref(),
+            "push",
+            "branches",
+            "branches-ignore",
+        )?;
+        validate_filter_conflict(
+            tags.as_ref(),
+            tags_ignore.as_ref(),
+            "push",
+            "tags",
+            "tags-ignore",
+        )?;
+        validate_filter_conflict(
+            paths.as_ref(),
+            paths_ignore.as_ref(),
+            "push",
+            "paths",
+            "paths-ignore",
+        )?;
+        reject_unknown_keys(&hash, "push")?;
+        Ok(PushEvent {
+            branches,
+            branches_ignore,
+            tags,
+            tags_ignore,
+            paths,
+            paths_ignore,
+        })
     }
-    #[pymethods]
-    impl ImageVersionEvent {
-        #[new]
-        #[pyo3(signature = (*, names=None, versions=None))]
-        fn new(names: Option<Vec<String>>, versions: Option<Vec<String>>) -> Self {
-            let names = names.filter(|v| !v.is_empty());
-            let versions = versions.filter(|v| !v.is_empty());
-            Self { names, versions }
-        }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
-        }
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum RegistryPackageActivity {
+        Published,
+        Updated,
     }
-    impl MaybeYamlable for &ImageVersionEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.names.is_some() || self.versions.is_some() {
-                let mut out = Hash::new();
-                out.insert_yaml_opt("names", self.names.as_ref());
-                out.insert_yaml_opt("versions", self.versions.as_ref());
-                Some(Yaml::Hash(out))
-            } else {
-                None
+    impl ActivityKind for RegistryPackageActivity {
+        const ALL: &'static [Self] = &[Self::Published, Self::Updated];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Published => "published",
+                Self::Updated => "updated",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct IssueCommentEvent {
-        created: bool,
-        edited: bool,
-        deleted: bool,
+    struct RegistryPackageEvent {
+        types: ActivityTypes<RegistryPackageActivity>,
     }
     #[pymethods]
-    impl IssueCommentEvent {
+    impl RegistryPackageEvent {
         #[new]
-        #[pyo3(signature = (*, created=false, edited=false, deleted=false))]
-        fn new(created: bool, edited: bool, deleted: bool) -> Self {
+        #[pyo3(signature = (*, published=false, updated=false))]
+        fn new(published: bool, updated: bool) -> Self {
             Self {
-                created,
-                edited,
-                deleted,
+                types: ActivityTypes::from_flags([(RegistryPackageActivity::Published, published), (RegistryPackageActivity::Updated, updated)]),
             }
         }
 
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            RegistryPackageActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            registry_package_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "registry_package",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
+        }
     }
-    impl MaybeYamlable for &IssueCommentEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created || self.edited || self.deleted {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
+    impl ActivityEvent for RegistryPackageEvent {
+        type Kind = RegistryPackageActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn registry_package_event_from_hash(mut hash: Hash) -> PyResult<RegistryPackageEvent> {
+        let types = ActivityTypes::parse(&mut hash, "registry_package")?;
+        reject_unknown_keys(&hash, "registry_package")?;
+        Ok(RegistryPackageEvent { types })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum ReleaseActivity {
+        Published,
+        Unpublished,
+        Created,
+        Edited,
+        Deleted,
+        Prereleased,
+        Released,
+    }
+    impl ActivityKind for ReleaseActivity {
+        const ALL: &'static [Self] = &[Self::Published, Self::Unpublished, Self::Created, Self::Edited, Self::Deleted, Self::Prereleased, Self::Released];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Published => "published",
+                Self::Unpublished => "unpublished",
+                Self::Created => "created",
+                Self::Edited => "edited",
+                Self::Deleted => "deleted",
+                Self::Prereleased => "prereleased",
+                Self::Released => "released",
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct IssuesEvent {
-        created: bool,
-        edited: bool,
-        deleted: bool,
-        transferred: bool,
-        pinned: bool,
-        unpinned: bool,
-        closed: bool,
-        reopened: bool,
-        assigned: bool,
-        unassigned: bool,
-        labeled: bool,
-        unlabeled: bool,
-        locked: bool,
-        unlocked: bool,
-        milestoned: bool,
-        demilestoned: bool,
-        typed: bool,
-        untyped: bool,
+    struct ReleaseEvent {
+        types: ActivityTypes<ReleaseActivity>,
     }
     #[pymethods]
-    impl IssuesEvent {
+    impl ReleaseEvent {
         #[new]
-        #[pyo3(signature = (*, created=false, edited=false, deleted=false,  transferred=false, pinned=false, unpinned=false, closed=false, reopened=false, assigned=false, unassigned=false, labeled=false, unlabeled=false, locked=false, unlocked=false, milestoned=false, demilestoned=false, typed=false, untyped=false))]
+        #[pyo3(signature = (*, published=false, unpublished=false, created=false, edited=false, deleted=false, prereleased=false, released=false))]
         fn new(
+            published: bool,
+            unpublished: bool,
             created: bool,
             edited: bool,
             deleted: bool,
-            transferred: bool,
-            pinned: bool,
-            unpinned: bool,
-            closed: bool,
-            reopened: bool,
-            assigned: bool,
-            unassigned: bool,
-            labeled: bool,
-            unlabeled: bool,
-            locked: bool,
-            unlocked: bool,
-            milestoned: bool,
-            demilestoned: bool,
-            typed: bool,
-            untyped: bool,
+            prereleased: bool,
+            released: bool,
         ) -> Self {
             Self {
-                created,
-                edited,
-                deleted,
-                transferred,
-                pinned,
-                unpinned,
-                closed,
-                reopened,
-                assigned,
-                unassigned,
-                labeled,
-                unlabeled,
-                locked,
-                unlocked,
-                milestoned,
-                demilestoned,
-                typed,
-                untyped,
+                types: ActivityTypes::from_flags([(ReleaseActivity::Published, published), (ReleaseActivity::Unpublished, unpublished), (ReleaseActivity::Created, created), (ReleaseActivity::Edited, edited), (ReleaseActivity::Deleted, deleted), (ReleaseActivity::Prereleased, prereleased), (ReleaseActivity::Released, released)]),
             }
         }
 
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            ReleaseActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
-    }
-    impl MaybeYamlable for &IssuesEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created
-                || self.edited
-                || self.deleted
-                || self.transferred
-                || self.pinned
-                || self.unpinned
-                || self.closed
-                || self.reopened
-                || self.assigned
-                || self.unassigned
-                || self.labeled
-                || self.unlabeled
-                || self.locked
-                || self.unlocked
-                || self.milestoned
-                || self.demilestoned
-                || self.typed
-                || self.untyped
-            {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                arr.push_yaml_cond("transferred", self.transferred);
-                arr.push_yaml_cond("pinned", self.pinned);
-                arr.push_yaml_cond("unpinned", self.unpinned);
-                arr.push_yaml_cond("closed", self.closed);
-                arr.push_yaml_cond("reopened", self.reopened);
-                arr.push_yaml_cond("assigned", self.assigned);
-                arr.push_yaml_cond("unassigned", self.unassigned);
-                arr.push_yaml_cond("labeled", self.labeled);
-                arr.push_yaml_cond("unlabeled", self.unlabeled);
-                arr.push_yaml_cond("locked", self.locked);
-                arr.push_yaml_cond("unlocked", self.unlocked);
-                arr.push_yaml_cond("milestoned", self.milestoned);
-                arr.push_yaml_cond("demilestoned", self.demilestoned);
-                arr.push_yaml_cond("typed", self.typed);
-                arr.push_yaml_cond("untyped", self.untyped);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            release_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "release",
+            )?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
             }
         }
     }
+    impl ActivityEvent for ReleaseEvent {
+        type Kind = ReleaseActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+    }
+    fn release_event_from_hash(mut hash: Hash) -> PyResult<ReleaseEvent> {
+        let types = ActivityTypes::parse(&mut hash, "release")?;
+        reject_unknown_keys(&hash, "release")?;
+        Ok(ReleaseEvent { types })
+    }
 
     #[pyclass]
     #[derive(Clone)]
-    struct LabelEvent {
-        created: bool,
-        edited: bool,
-        deleted: bool,
+    struct RepositoryDispatchEvent {
+        types: Option<Vec<String>>,
     }
     #[pymethods]
-    impl LabelEvent {
+    impl RepositoryDispatchEvent {
         #[new]
-        #[pyo3(signature = (*, created=false, edited=false, deleted=false))]
-        fn new(created: bool, edited: bool, deleted: bool) -> Self {
-            Self {
-                created,
-                edited,
-                deleted,
-            }
+        #[pyo3(signature = (*, types=None))]
+        fn new(types: Option<Vec<String>>) -> Self {
+            let types = types.filter(|v| !v.is_empty());
+            Self { types }
         }
 
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            repository_dispatch_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "repository_dispatch",
+            )?)
+        }
     }
-    impl MaybeYamlable for &LabelEvent {
+    impl MaybeYamlable for &RepositoryDispatchEvent {
         fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created || self.edited || self.deleted {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("types", self.types.as_ref());
+            if out.is_empty() {
                 None
+            } else {
+                Some(Yaml::Hash(out))
             }
         }
     }
+    fn repository_dispatch_event_from_hash(mut hash: Hash) -> PyResult<RepositoryDispatchEvent> {
+        let types = take_string_vec(&mut hash, "types", "repository_dispatch")?;
+        reject_unknown_keys(&hash, "repository_dispatch")?;
+        Ok(RepositoryDispatchEvent { types })
+    }
 
-    #[pyclass]
     #[derive(Clone)]
-    struct MergeGroupEvent {
-        checks_requested: bool,
+    enum CronStepType {
+        Value(u8),
+        List(Vec<u8>),
+        Range(u8, u8),
+        Step { start: Option<u8>, step: u8 },
+        RangeStep { start: u8, end: u8, step: u8 },
     }
-    #[pymethods]
-    impl MergeGroupEvent {
-        #[new]
-        #[pyo3(signature = (*, checks_requested=false))]
-        fn new(checks_requested: bool) -> Self {
-            Self { checks_requested }
+    impl Display for CronStepType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "{}",
+                match self {
+                    Self::Value(v) => v.to_string(),
+                    Self::List(items) => items
+                        .iter()
+                        .map(std::string::ToString::to_string)
+                        .collect::<Vec<String>>()
+                        .join(","),
+
+                    Self::Range(min, max) => format!("{min}-{max}"),
+                    Self::Step { start, step } => format!(
+                        "{}/{}",
+                        start.map(|s| s.to_string()).unwrap_or("*".to_string()),
+                        step
+                    ),
+                    Self::RangeStep { start, end, step } => format!("{start}-{end}/{step}"),
+                }
+            )
         }
+    }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct CronMinute(u8);
+
+    impl<'py> FromPyObject<'py> for CronMinute {
+        fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+            if let Ok(num) = obj.extract::<u8>()
+                && num <= 59
+            {
+                return Ok(CronMinute(num));
+            }
+            Err(PyValueError::new_err(
+                "Minute must be an integer in range 0..=59",
+            ))
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct CronHour(u8);
+
+    impl<'py> FromPyObject<'py> for CronHour {
+        fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+            if let Ok(num) = obj.extract::<u8>()
+                && num <= 23
+            {
+                return Ok(CronHour(num));
+            }
+            Err(PyValueError::new_err(
+                "Hour must be an integer in range 0..=23",
+            ))
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct CronDay(u8);
+
+    impl<'py> FromPyObject<'py> for CronDay {
+        fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+            if let Ok(num) = obj.extract::<u8>()
+                && (1..=31).contains(&num)
+            {
+                return Ok(CronDay(num));
+            }
+            Err(PyValueError::new_err(
+                "Day must be an integer in range 1..=31",
+            ))
+        }
+    }
+
+    /// GitHub crontab month names, `JAN`..=`DEC` in order, matched case-insensitively.
+    const MONTH_NAMES: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    /// GitHub crontab weekday names, `SUN`..=`SAT` in order (`SUN` = 0), matched
+    /// case-insensitively.
+    const DAY_OF_WEEK_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+    fn month_name_to_num(s: &str) -> Option<u8> {
+        let lower = s.to_lowercase();
+        MONTH_NAMES
+            .iter()
+            .position(|name| *name == lower)
+            .map(|i| i as u8 + 1)
+    }
+
+    fn day_of_week_name_to_num(s: &str) -> Option<u8> {
+        let lower = s.to_lowercase();
+        DAY_OF_WEEK_NAMES
+            .iter()
+            .position(|name| *name == lower)
+            .map(|i| i as u8)
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct CronMonth(u8);
+
+    impl<'py> FromPyObject<'py> for CronMonth {
+        fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+            let msg = "Month must be an integer in range 1..=12 or a name like 'JAN'";
+            if let Ok(num) = obj.extract::<u8>()
+                && (1..=12).contains(&num)
+            {
+                return Ok(CronMonth(num));
+            }
+            if let Ok(name) = obj.extract::<String>()
+                && let Some(num) = month_name_to_num(&name)
+            {
+                return Ok(CronMonth(num));
+            }
+            Err(PyValueError::new_err(msg))
         }
     }
-    impl MaybeYamlable for &MergeGroupEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.checks_requested {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("checks_requested", self.checks_requested);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct CronDayOfWeek(u8);
+
+    impl<'py> FromPyObject<'py> for CronDayOfWeek {
+        fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+            let msg = "Day of week must be an integer in range 0..=6 (0=Sunday) or a name like 'MON'";
+            if let Ok(num) = obj.extract::<u8>()
+                && num <= 6
+            {
+                return Ok(CronDayOfWeek(num));
             }
+            if let Ok(name) = obj.extract::<String>()
+                && let Some(num) = day_of_week_name_to_num(&name)
+            {
+                return Ok(CronDayOfWeek(num));
+            }
+            Err(PyValueError::new_err(msg))
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct MilestoneEvent {
-        created: bool,
-        closed: bool,
-        opened: bool,
-        edited: bool,
-        deleted: bool,
+    struct Minute(CronStepType);
+    impl Display for Minute {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
     }
     #[pymethods]
-    impl MilestoneEvent {
+    impl Minute {
         #[new]
-        #[pyo3(signature = (*, created=false, closed=false, opened=false, edited=false, deleted=false))]
-        fn new(created: bool, closed: bool, opened: bool, edited: bool, deleted: bool) -> Self {
-            Self {
-                created,
-                closed,
-                opened,
-                edited,
-                deleted,
+        fn new(minute: &Bound<PyAny>) -> PyResult<Self> {
+            if let Ok(l) = minute.extract::<Bound<PyList>>() {
+                let mut res = Vec::new();
+                for item in l.iter() {
+                    let item = item.extract::<CronMinute>()?;
+                    res.push(item.0);
+                }
+                return Ok(Self(CronStepType::List(res)));
             }
+            let minute = minute.extract::<CronMinute>()?;
+            Ok(Self(CronStepType::Value(minute.0)))
         }
-
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        #[staticmethod]
+        fn between(start: &Bound<PyAny>, end: &Bound<PyAny>) -> PyResult<Self> {
+            let min = start.extract::<CronMinute>()?;
+            let max = end.extract::<CronMinute>()?;
+            Ok(Self(CronStepType::Range(min.0, max.0)))
         }
-    }
-    impl MaybeYamlable for &MilestoneEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created || self.closed || self.opened || self.edited || self.deleted {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("closed", self.closed);
-                arr.push_yaml_cond("opened", self.opened);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
-            }
+        #[staticmethod]
+        #[pyo3(signature = (interval, *, start = None))]
+        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
+            let start = start
+                .map(|a| a.extract::<CronMinute>())
+                .transpose()?
+                .map(|s| s.0);
+            let interval = interval.extract::<CronMinute>()?;
+            Ok(Self(CronStepType::Step {
+                start,
+                step: interval.0,
+            }))
+        }
+        #[staticmethod]
+        fn between_every(
+            start: &Bound<PyAny>,
+            end: &Bound<PyAny>,
+            interval: &Bound<PyAny>,
+        ) -> PyResult<Self> {
+            let start = start.extract::<CronMinute>()?;
+            let end = end.extract::<CronMinute>()?;
+            let interval = interval.extract::<CronMinute>()?;
+            Ok(Self(CronStepType::RangeStep {
+                start: start.0,
+                end: end.0,
+                step: interval.0,
+            }))
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct PullRequestEvent {
-        assigned: bool,
-        unassigned: bool,
-        labeled: bool,
-        unlabeled: bool,
-        opened: bool,
-        edited: bool,
-        closed: bool,
-        reopened: bool,
-        synchronize: bool,
-        converted_to_draft: bool,
-        locked: bool,
-        unlocked: bool,
-        enqueued: bool,
-        dequeued: bool,
-        milestoned: bool,
-        demilestoned: bool,
-        ready_for_review: bool,
-        review_requested: bool,
-        review_request_removed: bool,
-        auto_merge_enabled: bool,
-        auto_merge_disabled: bool,
-        branches: Option<Vec<String>>,
-        branches_ignore: Option<Vec<String>>,
-        paths: Option<Vec<String>>,
-        paths_ignore: Option<Vec<String>>,
+    struct Hour(CronStepType);
+    impl Display for Hour {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
     }
     #[pymethods]
-    impl PullRequestEvent {
+    impl Hour {
         #[new]
-        #[pyo3(signature = (*, branches=None, branches_ignore=None, paths=None, paths_ignore=None, assigned=false, unassigned=false, labeled=false, unlabeled=false, opened=false, edited=false, closed=false, reopened=false, synchronize=false, converted_to_draft=false, locked=false, unlocked=false, enqueued=false, dequeued=false, milestoned=false, demilestoned=false, ready_for_review=false, review_requested=false, review_request_removed=false, auto_merge_enabled=false, auto_merge_disabled=false))]
-        fn new(
-            branches: Option<Vec<String>>,
-            branches_ignore: Option<Vec<String>>,
-            paths: Option<Vec<String>>,
-            paths_ignore: Option<Vec<String>>,
-            assigned: bool,
-            unassigned: bool,
-            labeled: bool,
-            unlabeled: bool,
-            opened: bool,
-            edited: bool,
-            closed: bool,
-            reopened: bool,
-            synchronize: bool,
-            converted_to_draft: bool,
-            locked: bool,
-            unlocked: bool,
-            enqueued: bool,
-            dequeued: bool,
-            milestoned: bool,
-            demilestoned: bool,
-            ready_for_review: bool,
-            review_requested: bool,
-            review_request_removed: bool,
-            auto_merge_enabled: bool,
-            auto_merge_disabled: bool,
-        ) -> Self {
-            let branches = branches.filter(|v| !v.is_empty());
-            let branches_ignore = branches_ignore.filter(|v| !v.is_empty());
-            let paths = paths.filter(|v| !v.is_empty());
-            let paths_ignore = paths_ignore.filter(|v| !v.is_empty());
-            Self {
-                assigned,
-                unassigned,
-                labeled,
-                unlabeled,
-                opened,
-                edited,
-                closed,
-                reopened,
-                synchronize,
-                converted_to_draft,
-                locked,
-                unlocked,
-                enqueued,
-                dequeued,
-                milestoned,
-                demilestoned,
-                ready_for_review,
-                review_requested,
-                review_request_removed,
-                auto_merge_enabled,
-                auto_merge_disabled,
-                branches,
-                branches_ignore,
-                paths,
-                paths_ignore,
+        fn new(hour: &Bound<PyAny>) -> PyResult<Self> {
+            if let Ok(l) = hour.extract::<Bound<PyList>>() {
+                let mut res = Vec::new();
+                for item in l.iter() {
+                    let item = item.extract::<CronHour>()?;
+                    res.push(item.0);
+                }
+                return Ok(Self(CronStepType::List(res)));
             }
+            let hour = hour.extract::<CronHour>()?;
+            Ok(Self(CronStepType::Value(hour.0)))
         }
-
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        #[staticmethod]
+        fn between(start: &Bound<PyAny>, end: &Bound<PyAny>) -> PyResult<Self> {
+            let min = start.extract::<CronHour>()?;
+            let max = end.extract::<CronHour>()?;
+            Ok(Self(CronStepType::Range(min.0, max.0)))
         }
-    }
-    impl MaybeYamlable for &PullRequestEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut out = Hash::new();
-            out.insert_yaml_opt("branches", self.branches.as_ref());
-            out.insert_yaml_opt("branches-ignore", self.branches_ignore.as_ref());
-            out.insert_yaml_opt("paths", self.paths.as_ref());
-            out.insert_yaml_opt("paths-ignore", self.paths_ignore.as_ref());
-            if self.assigned
-                || self.unassigned
-                || self.labeled
-                || self.unlabeled
-                || self.opened
-                || self.edited
-                || self.closed
-                || self.reopened
-                || self.synchronize
-                || self.converted_to_draft
-                || self.locked
-                || self.unlocked
-                || self.enqueued
-                || self.dequeued
-                || self.milestoned
-                || self.demilestoned
-                || self.ready_for_review
-                || self.review_requested
-                || self.review_request_removed
-                || self.auto_merge_enabled
-                || self.auto_merge_disabled
-            {
-                let mut arr = Array::new();
-                arr.push_yaml_cond("assigned", self.assigned);
-                arr.push_yaml_cond("unassigned", self.unassigned);
-                arr.push_yaml_cond("labeled", self.labeled);
-                arr.push_yaml_cond("unlabeled", self.unlabeled);
-                arr.push_yaml_cond("opened", self.opened);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("closed", self.closed);
-                arr.push_yaml_cond("reopened", self.reopened);
-                arr.push_yaml_cond("synchronize", self.synchronize);
-                arr.push_yaml_cond("converted_to_draft", self.converted_to_draft);
-                arr.push_yaml_cond("locked", self.locked);
-                arr.push_yaml_cond("unlocked", self.unlocked);
-                arr.push_yaml_cond("enqueued", self.enqueued);
-                arr.push_yaml_cond("dequeued", self.dequeued);
-                arr.push_yaml_cond("milestoned", self.milestoned);
-                arr.push_yaml_cond("demilestoned", self.demilestoned);
-                arr.push_yaml_cond("ready_for_review", self.ready_for_review);
-                arr.push_yaml_cond("review_requested", self.review_requested);
-                arr.push_yaml_cond("review_request_removed", self.review_request_removed);
-                arr.push_yaml_cond("auto_merge_enabled", self.auto_merge_enabled);
-                arr.push_yaml_cond("auto_merge_disabled", self.auto_merge_disabled);
-                out.insert_yaml("types", Yaml::Array(arr));
-            }
-            if out.is_empty() {
-                None
-            } else {
-                Some(Yaml::Hash(out))
-            }
+        #[staticmethod]
+        #[pyo3(signature = (interval, *, start = None))]
+        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
+            let start = start
+                .map(|a| a.extract::<CronHour>())
+                .transpose()?
+                .map(|s| s.0);
+            let interval = interval.extract::<CronHour>()?;
+            Ok(Self(CronStepType::Step {
+                start,
+                step: interval.0,
+            }))
+        }
+        #[staticmethod]
+        fn between_every(
+            start: &Bound<PyAny>,
+            end: &Bound<PyAny>,
+            interval: &Bound<PyAny>,
+        ) -> PyResult<Self> {
+            let start = start.extract::<CronHour>()?;
+            let end = end.extract::<CronHour>()?;
+            let interval = interval.extract::<CronHour>()?;
+            Ok(Self(CronStepType::RangeStep {
+                start: start.0,
+                end: end.0,
+                step: interval.0,
+            }))
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct PullRequestReviewEvent {
-        submitted: bool,
-        edited: bool,
-        dismissed: bool,
+    struct Day(CronStepType);
+    impl Display for Day {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
     }
     #[pymethods]
-    impl PullRequestReviewEvent {
+    impl Day {
         #[new]
-        #[pyo3(signature = (*, submitted=false, edited=false, dismissed=false))]
-        fn new(submitted: bool, edited: bool, dismissed: bool) -> Self {
-            Self {
-                submitted,
-                edited,
-                dismissed,
+        fn new(day: &Bound<PyAny>) -> PyResult<Self> {
+            if let Ok(l) = day.extract::<Bound<PyList>>() {
+                let mut res = Vec::new();
+                for item in l.iter() {
+                    let item = item.extract::<CronDay>()?;
+                    res.push(item.0);
+                }
+                return Ok(Self(CronStepType::List(res)));
             }
+            let day = day.extract::<CronDay>()?;
+            Ok(Self(CronStepType::Value(day.0)))
         }
-
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        #[staticmethod]
+        fn between(min: &Bound<PyAny>, max: &Bound<PyAny>) -> PyResult<Self> {
+            let min = min.extract::<CronDay>()?;
+            let max = max.extract::<CronDay>()?;
+            Ok(Self(CronStepType::Range(min.0, max.0)))
         }
-    }
-    impl MaybeYamlable for &PullRequestReviewEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.submitted || self.edited || self.dismissed {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("submitted", self.submitted);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("dismissed", self.dismissed);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
-            }
+        #[staticmethod]
+        #[pyo3(signature = (interval, *, start = None))]
+        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
+            let start = start
+                .map(|a| a.extract::<CronDay>())
+                .transpose()?
+                .map(|s| s.0);
+            let interval = interval.extract::<CronDay>()?;
+            Ok(Self(CronStepType::Step {
+                start,
+                step: interval.0,
+            }))
+        }
+        #[staticmethod]
+        fn between_every(
+            start: &Bound<PyAny>,
+            end: &Bound<PyAny>,
+            interval: &Bound<PyAny>,
+        ) -> PyResult<Self> {
+            let start = start.extract::<CronDay>()?;
+            let end = end.extract::<CronDay>()?;
+            let interval = interval.extract::<CronDay>()?;
+            Ok(Self(CronStepType::RangeStep {
+                start: start.0,
+                end: end.0,
+                step: interval.0,
+            }))
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct PullRequestReviewCommentEvent {
-        created: bool,
-        edited: bool,
-        deleted: bool,
-    }
-    #[pymethods]
-    impl PullRequestReviewCommentEvent {
-        #[new]
-        #[pyo3(signature = (*, created=false,edited=false, deleted=false))]
-        fn new(created: bool, edited: bool, deleted: bool) -> Self {
-            Self {
-                created,
-                edited,
-                deleted,
-            }
-        }
-
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
-        }
-    }
-    impl MaybeYamlable for &PullRequestReviewCommentEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.created || self.edited || self.deleted {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
-            }
+    struct Month(CronStepType);
+    impl Display for Month {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
         }
     }
-
-    #[pyclass]
-    #[derive(Clone)]
-    struct PushEvent {
-        branches: Option<Vec<String>>,
-        branches_ignore: Option<Vec<String>>,
-        tags: Option<Vec<String>>,
-        tags_ignore: Option<Vec<String>>,
-        paths: Option<Vec<String>>,
-        paths_ignore: Option<Vec<String>>,
-    }
     #[pymethods]
-    impl PushEvent {
+    impl Month {
         #[new]
-        #[pyo3(signature = (*, branches=None, branches_ignore=None, tags=None, tags_ignore=None, paths=None, paths_ignore=None))]
-        fn new(
-            branches: Option<Vec<String>>,
-            branches_ignore: Option<Vec<String>>,
-            tags: Option<Vec<String>>,
-            tags_ignore: Option<Vec<String>>,
-            paths: Option<Vec<String>>,
-            paths_ignore: Option<Vec<String>>,
-        ) -> Self {
-            Self {
-                branches,
-                branches_ignore,
-                tags,
-                tags_ignore,
-                paths,
-                paths_ignore,
+        fn new(month: &Bound<PyAny>) -> PyResult<Self> {
+            if let Ok(l) = month.extract::<Bound<PyList>>() {
+                let mut res = Vec::new();
+                for item in l.iter() {
+                    let item = item.extract::<CronMonth>()?;
+                    res.push(item.0);
+                }
+                return Ok(Self(CronStepType::List(res)));
             }
+            let month = month.extract::<CronMonth>()?;
+            Ok(Self(CronStepType::Value(month.0)))
         }
-
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        #[staticmethod]
+        fn between(min: &Bound<PyAny>, max: &Bound<PyAny>) -> PyResult<Self> {
+            let min = min.extract::<CronMonth>()?;
+            let max = max.extract::<CronMonth>()?;
+            Ok(Self(CronStepType::Range(min.0, max.0)))
         }
-    }
-    impl MaybeYamlable for &PushEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut out = Hash::new();
-            out.insert_yaml_opt("branches", self.branches.as_ref());
-            out.insert_yaml_opt("branches-ignore", self.branches_ignore.as_ref());
-            out.insert_yaml_opt("tags", self.tags.as_ref());
-            out.insert_yaml_opt("tags-ignore", self.tags_ignore.as_ref());
-            out.insert_yaml_opt("paths", self.paths.as_ref());
-            out.insert_yaml_opt("paths-ignore", self.paths_ignore.as_ref());
-            if out.is_empty() {
-                None
-            } else {
-                Some(Yaml::Hash(out))
-            }
+        #[staticmethod]
+        #[pyo3(signature = (interval, *, start = None))]
+        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
+            let start = start
+                .map(|a| a.extract::<CronMonth>())
+                .transpose()?
+                .map(|s| s.0);
+            let interval = interval.extract::<CronMonth>()?;
+            Ok(Self(CronStepType::Step {
+                start,
+                step: interval.0,
+            }))
+        }
+        #[staticmethod]
+        fn between_every(
+            start: &Bound<PyAny>,
+            end: &Bound<PyAny>,
+            interval: &Bound<PyAny>,
+        ) -> PyResult<Self> {
+            let start = start.extract::<CronMonth>()?;
+            let end = end.extract::<CronMonth>()?;
+            let interval = interval.extract::<CronMonth>()?;
+            Ok(Self(CronStepType::RangeStep {
+                start: start.0,
+                end: end.0,
+                step: interval.0,
+            }))
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct RegistryPackageEvent {
-        published: bool,
-        updated: bool,
+    struct DayOfWeek(CronStepType);
+    impl Display for DayOfWeek {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
     }
     #[pymethods]
-    impl RegistryPackageEvent {
+    impl DayOfWeek {
         #[new]
-        #[pyo3(signature = (*, published=false, updated=false))]
-        fn new(published: bool, updated: bool) -> Self {
-            Self { published, updated }
+        fn new(day_of_week: &Bound<PyAny>) -> PyResult<Self> {
+            if let Ok(l) = day_of_week.extract::<Bound<PyList>>() {
+                let mut res = Vec::new();
+                for item in l.iter() {
+                    let item = item.extract::<CronDayOfWeek>()?;
+                    res.push(item.0);
+                }
+                return Ok(Self(CronStepType::List(res)));
+            }
+            let day_of_week = day_of_week.extract::<CronDayOfWeek>()?;
+            Ok(Self(CronStepType::Value(day_of_week.0)))
         }
-
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        #[staticmethod]
+        fn between(min: &Bound<PyAny>, max: &Bound<PyAny>) -> PyResult<Self> {
+            let min = min.extract::<CronDayOfWeek>()?;
+            let max = max.extract::<CronDayOfWeek>()?;
+            Ok(Self(CronStepType::Range(min.0, max.0)))
         }
-    }
-    impl MaybeYamlable for &RegistryPackageEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.published || self.updated {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("published", self.published);
-                arr.push_yaml_cond("updated", self.updated);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
-            }
+        #[staticmethod]
+        #[pyo3(signature = (interval, *, start = None))]
+        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
+            let start = start
+                .map(|a| a.extract::<CronDayOfWeek>())
+                .transpose()?
+                .map(|s| s.0);
+            let interval = interval.extract::<CronDayOfWeek>()?;
+            Ok(Self(CronStepType::Step {
+                start,
+                step: interval.0,
+            }))
+        }
+        #[staticmethod]
+        fn between_every(
+            start: &Bound<PyAny>,
+            end: &Bound<PyAny>,
+            interval: &Bound<PyAny>,
+        ) -> PyResult<Self> {
+            let start = start.extract::<CronDayOfWeek>()?;
+            let end = end.extract::<CronDayOfWeek>()?;
+            let interval = interval.extract::<CronDayOfWeek>()?;
+            Ok(Self(CronStepType::RangeStep {
+                start: start.0,
+                end: end.0,
+                step: interval.0,
+            }))
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct ReleaseEvent {
-        published: bool,
-        unpublished: bool,
-        created: bool,
-        edited: bool,
-        deleted: bool,
-        prereleased: bool,
-        released: bool,
+    struct Cron {
+        minute: Option<Minute>,
+        hour: Option<Hour>,
+        day: Option<Day>,
+        month: Option<Month>,
+        day_of_week: Option<DayOfWeek>,
     }
     #[pymethods]
-    impl ReleaseEvent {
+    impl Cron {
         #[new]
-        #[pyo3(signature = (*, published=false, unpublished=false, created=false, edited=false, deleted=false, prereleased=false, released=false))]
+        #[pyo3(signature = (*, minute = None, hour = None, day = None, month = None, day_of_week = None))]
         fn new(
-            published: bool,
-            unpublished: bool,
-            created: bool,
-            edited: bool,
-            deleted: bool,
-            prereleased: bool,
-            released: bool,
+            minute: Option<Minute>,
+            hour: Option<Hour>,
+            day: Option<Day>,
+            month: Option<Month>,
+            day_of_week: Option<DayOfWeek>,
         ) -> Self {
             Self {
-                published,
-                unpublished,
-                created,
-                edited,
-                deleted,
-                prereleased,
-                released,
+                minute,
+                hour,
+                day,
+                month,
+                day_of_week,
             }
         }
 
         fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
-        }
-    }
-    impl MaybeYamlable for &ReleaseEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.published
-                || self.unpublished
-                || self.created
-                || self.edited
-                || self.deleted
-                || self.prereleased
-                || self.released
-            {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("published", self.published);
-                arr.push_yaml_cond("unpublished", self.unpublished);
-                arr.push_yaml_cond("created", self.created);
-                arr.push_yaml_cond("edited", self.edited);
-                arr.push_yaml_cond("deleted", self.deleted);
-                arr.push_yaml_cond("prereleased", self.prereleased);
-                arr.push_yaml_cond("released", self.released);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
-                None
-            }
+            self.as_yaml_string()
         }
-    }
 
-    #[pyclass]
-    #[derive(Clone)]
-    struct RepositoryDispatchEvent {
-        types: Option<Vec<String>>,
-    }
-    #[pymethods]
-    impl RepositoryDispatchEvent {
-        #[new]
-        #[pyo3(signature = (*, types=None))]
-        fn new(types: Option<Vec<String>>) -> Self {
-            let types = types.filter(|v| !v.is_empty());
-            Self { types }
+        /// The next `count` UTC firing times of this cron expression strictly after the minute
+        /// containing `start_utc`,
+        /// as a list of `datetime.datetime`. `start_utc` is assumed to already be in UTC (this
+        /// crate doesn't convert time zones); the returned datetimes carry whatever `tzinfo`
+        /// `start_utc` had.
+        fn next_runs(
+            &self,
+            py: Python<'_>,
+            start_utc: &Bound<'_, PyDateTime>,
+            count: usize,
+        ) -> PyResult<Vec<Py<PyDateTime>>> {
+            let mut clock = CronClock::from_py_datetime(start_utc)?.next_minute();
+            let fields = self.expand_fields();
+            let tzinfo = start_utc.get_tzinfo();
+            let mut out = Vec::with_capacity(count);
+            while out.len() < count {
+                clock = fields.advance_to_next_match(clock)?;
+                out.push(clock.to_py_datetime(py, tzinfo.as_ref())?.unbind());
+                clock = clock.next_minute();
+            }
+            Ok(out)
+        }
+
+        /// An infinite generator of this cron's UTC firing times starting from `start_utc`,
+        /// yielding one `datetime.datetime` at a time via `__next__` instead of materializing a
+        /// fixed-size list up front the way `next_runs` does.
+        fn iter_runs(&self, start_utc: &Bound<'_, PyDateTime>) -> PyResult<CronRunIter> {
+            Ok(CronRunIter {
+                fields: self.expand_fields(),
+                clock: CronClock::from_py_datetime(start_utc)?.next_minute(),
+                tzinfo: start_utc.get_tzinfo().map(|t| t.unbind()),
+            })
         }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        /// A human-readable English summary of this cron, e.g. "at minute 0, every 2 hours, at
+        /// day of week Mon", derived from each field's `CronStepType` rather than its expanded
+        /// value set.
+        fn describe(&self) -> String {
+            let mut parts = vec![
+                describe_cron_field(self.minute.as_ref().map(|m| &m.0), "minute", "minutes", None, 0),
+                describe_cron_field(self.hour.as_ref().map(|h| &h.0), "hour", "hours", None, 0),
+            ];
+            if let Some(day) = &self.day {
+                parts.push(describe_cron_field(
+                    Some(&day.0),
+                    "day of month",
+                    "days of month",
+                    None,
+                    0,
+                ));
+            }
+            if let Some(month) = &self.month {
+                parts.push(describe_cron_field(
+                    Some(&month.0),
+                    "month",
+                    "months",
+                    Some(&MONTH_NAMES),
+                    1,
+                ));
+            }
+            if let Some(day_of_week) = &self.day_of_week {
+                parts.push(describe_cron_field(
+                    Some(&day_of_week.0),
+                    "day of week",
+                    "days of week",
+                    Some(&DAY_OF_WEEK_NAMES),
+                    0,
+                ));
+            }
+            parts.join(", ")
         }
     }
-    impl MaybeYamlable for &RepositoryDispatchEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
+    impl Yamlable for &Cron {
+        fn as_yaml(&self) -> Yaml {
             let mut out = Hash::new();
-            out.insert_yaml_opt("types", self.types.as_ref());
-            if out.is_empty() {
-                None
-            } else {
-                Some(Yaml::Hash(out))
-            }
+            let s = format!(
+                "{} {} {} {} {}",
+                self.minute
+                    .clone()
+                    .map_or("*".to_string(), |s| s.to_string()),
+                self.hour.clone().map_or("*".to_string(), |s| s.to_string()),
+                self.day.clone().map_or("*".to_string(), |s| s.to_string()),
+                self.month
+                    .clone()
+                    .map_or("*".to_string(), |s| s.to_string()),
+                self.day_of_week
+                    .clone()
+                    .map_or("*".to_string(), |s| s.to_string())
+            );
+            out.insert_yaml("cron", s);
+            Yaml::Hash(out)
         }
     }
 
-    #[derive(Clone)]
-    enum CronStepType {
-        Value(u8),
-        List(Vec<u8>),
-        Range(u8, u8),
-        Step { start: Option<u8>, step: u8 },
-    }
-    impl Display for CronStepType {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(
-                f,
-                "{}",
-                match self {
-                    Self::Value(v) => v.to_string(),
-                    Self::List(items) => items
-                        .iter()
-                        .map(std::string::ToString::to_string)
-                        .collect::<Vec<String>>()
-                        .join(","),
-
-                    Self::Range(min, max) => format!("{min}-{max}"),
-                    Self::Step { start, step } => format!(
-                        "{}/{}",
-                        start.map(|s| s.to_string()).unwrap_or("*".to_string()),
-                        step
-                    ),
+    /// Render one cron field value as a word, capitalizing a name abbreviation (`Jan`, `Mon`) if
+    /// `names` covers it, falling back to the bare number otherwise. `offset` is the field's
+    /// minimum value, since `names` is always zero-indexed.
+    fn describe_cron_value(value: u8, names: Option<&[&str]>, offset: u8) -> String {
+        if let Some(name) = names.and_then(|names| names.get((value - offset) as usize)) {
+            let mut chars = name.chars();
+            return match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => (*name).to_string(),
+            };
+        }
+        value.to_string()
+    }
+
+    /// Render one cron field's `CronStepType` (or `None` for `*`) as an English clause, e.g.
+    /// "every 2 hours" or "at months Jan, Jul".
+    fn describe_cron_field(
+        step_type: Option<&CronStepType>,
+        singular: &str,
+        plural: &str,
+        names: Option<&[&str]>,
+        offset: u8,
+    ) -> String {
+        let Some(step_type) = step_type else {
+            return format!("every {singular}");
+        };
+        match step_type {
+            CronStepType::Value(v) => {
+                format!("at {singular} {}", describe_cron_value(*v, names, offset))
+            }
+            CronStepType::List(items) => format!(
+                "at {plural} {}",
+                items
+                    .iter()
+                    .map(|v| describe_cron_value(*v, names, offset))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            CronStepType::Range(lo, hi) => format!(
+                "every {singular} from {} to {}",
+                describe_cron_value(*lo, names, offset),
+                describe_cron_value(*hi, names, offset)
+            ),
+            CronStepType::Step { start: None, step } => format!("every {step} {plural}"),
+            CronStepType::Step {
+                start: Some(start),
+                step,
+            } => format!(
+                "every {step} {plural} starting at {}",
+                describe_cron_value(*start, names, offset)
+            ),
+            CronStepType::RangeStep { start, end, step } => format!(
+                "every {step} {plural} from {} to {}",
+                describe_cron_value(*start, names, offset),
+                describe_cron_value(*end, names, offset)
+            ),
+        }
+    }
+
+    /// One numeric field's allowed values expanded out of its `CronStepType`, or `None` for the
+    /// wildcard `*` (every value in `min..=max` matches).
+    fn expand_cron_step_type(step_type: Option<&CronStepType>, min: u8, max: u8) -> Option<Vec<u8>> {
+        step_type.map(|st| {
+            let mut values: Vec<u8> = match st {
+                CronStepType::Value(v) => vec![*v],
+                CronStepType::List(items) => items.clone(),
+                CronStepType::Range(lo, hi) => (*lo..=*hi).collect(),
+                CronStepType::Step { start, step } => {
+                    let step = (*step).max(1);
+                    let mut v = Vec::new();
+                    let mut cur = start.unwrap_or(min);
+                    while cur <= max {
+                        v.push(cur);
+                        cur += step;
+                    }
+                    v
                 }
-            )
-        }
+                CronStepType::RangeStep { start, end, step } => {
+                    let step = (*step).max(1);
+                    let mut v = Vec::new();
+                    let mut cur = *start;
+                    while cur <= *end {
+                        v.push(cur);
+                        cur += step;
+                    }
+                    v
+                }
+            };
+            values.sort_unstable();
+            values.dedup();
+            values
+        })
     }
 
-    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-    struct CronMinute(u8);
+    /// The five cron fields expanded into concrete allowed-value sets (`None` meaning "every
+    /// value"), computed once per `next_runs`/`iter_runs` call instead of per candidate minute.
+    struct CronFields {
+        minute: Option<Vec<u8>>,
+        hour: Option<Vec<u8>>,
+        day: Option<Vec<u8>>,
+        month: Option<Vec<u8>>,
+        day_of_week: Option<Vec<u8>>,
+    }
+    impl CronFields {
+        fn minute_ok(&self, minute: u8) -> bool {
+            self.minute.as_ref().is_none_or(|v| v.contains(&minute))
+        }
+        fn hour_ok(&self, hour: u8) -> bool {
+            self.hour.as_ref().is_none_or(|v| v.contains(&hour))
+        }
+        fn month_ok(&self, month: u8) -> bool {
+            self.month.as_ref().is_none_or(|v| v.contains(&month))
+        }
+        /// GitHub's day-matching rule: if both day-of-month and day-of-week are restricted
+        /// (non-`*`), a day matches when it's in *either* set (OR semantics); if only one is
+        /// restricted, only that one has to match; if neither is restricted, every day matches.
+        fn day_ok(&self, day: u8, weekday: u8) -> bool {
+            match (&self.day, &self.day_of_week) {
+                (Some(days), Some(weekdays)) => {
+                    days.contains(&day) || weekdays.contains(&weekday)
+                }
+                (Some(days), None) => days.contains(&day),
+                (None, Some(weekdays)) => weekdays.contains(&weekday),
+                (None, None) => true,
+            }
+        }
 
-    impl<'a, 'py> FromPyObject<'a, 'py> for CronMinute {
-        type Error = PyErr;
-        fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
-            if let Ok(num) = obj.extract::<u8>()
-                && num <= 59
-            {
-                return Ok(CronMinute(num));
+        /// Step `clock` minute-by-minute (fast-forwarding whenever month, day, or hour already
+        /// rule out every minute of the current hour/day/month) until every field matches,
+        /// bailing out rather than looping forever if no match turns up within a few years.
+        fn advance_to_next_match(&self, mut clock: CronClock) -> PyResult<CronClock> {
+            for _ in 0..CronClock::MAX_STEPS {
+                if !self.month_ok(clock.month) {
+                    clock = clock.next_month();
+                    continue;
+                }
+                if !self.day_ok(clock.day, clock.weekday()) {
+                    clock = clock.next_day();
+                    continue;
+                }
+                if !self.hour_ok(clock.hour) {
+                    clock = clock.next_hour();
+                    continue;
+                }
+                if !self.minute_ok(clock.minute) {
+                    clock = clock.next_minute();
+                    continue;
+                }
+                return Ok(clock);
             }
             Err(PyValueError::new_err(
-                "Minute must be an integer in range 0..=59",
+                "Cron expression never matches (checked several years of candidate firing times)",
             ))
         }
     }
 
-    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-    struct CronHour(u8);
+    /// A naive (time-zone-less) UTC calendar clock used to enumerate cron firing times one
+    /// minute at a time. Calendar math (days-per-month, leap years, weekday) is done by hand
+    /// since this crate otherwise has no date/time dependency.
+    #[derive(Clone, Copy)]
+    struct CronClock {
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+    }
+    impl CronClock {
+        /// Upper bound on minute-by-minute advances `advance_to_next_match` will try before
+        /// giving up; a few years' worth, comfortably more than any real schedule needs.
+        const MAX_STEPS: u32 = 6 * 366 * 24 * 60;
+
+        fn is_leap_year(year: i32) -> bool {
+            (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+        }
+
+        fn days_in_month(year: i32, month: u8) -> u8 {
+            match month {
+                1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+                4 | 6 | 9 | 11 => 30,
+                2 => {
+                    if Self::is_leap_year(year) {
+                        29
+                    } else {
+                        28
+                    }
+                }
+                _ => 30,
+            }
+        }
 
-    impl<'a, 'py> FromPyObject<'a, 'py> for CronHour {
-        type Error = PyErr;
-        fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
-            if let Ok(num) = obj.extract::<u8>()
-                && num <= 23
-            {
-                return Ok(CronHour(num));
+        /// Sakamoto's algorithm; returns 0=Sunday..6=Saturday, matching `CronDayOfWeek`.
+        fn weekday(&self) -> u8 {
+            const T: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+            let mut y = self.year;
+            if self.month < 3 {
+                y -= 1;
             }
-            Err(PyValueError::new_err(
-                "Hour must be an integer in range 0..=23",
-            ))
+            let w = (y + y / 4 - y / 100 + y / 400
+                + T[(self.month - 1) as usize]
+                + i32::from(self.day))
+            .rem_euclid(7);
+            w as u8
         }
-    }
 
-    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-    struct CronDay(u8);
+        fn from_py_datetime(dt: &Bound<'_, PyDateTime>) -> PyResult<Self> {
+            Ok(Self {
+                year: dt.get_year(),
+                month: dt.get_month(),
+                day: dt.get_day(),
+                hour: dt.get_hour(),
+                minute: dt.get_minute(),
+            })
+        }
 
-    impl<'a, 'py> FromPyObject<'a, 'py> for CronDay {
-        type Error = PyErr;
-        fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
-            if let Ok(num) = obj.extract::<u8>()
-                && (1..=31).contains(&num)
-            {
-                return Ok(CronDay(num));
+        fn to_py_datetime<'py>(
+            &self,
+            py: Python<'py>,
+            tzinfo: Option<&Bound<'py, PyTzInfo>>,
+        ) -> PyResult<Bound<'py, PyDateTime>> {
+            PyDateTime::new(
+                py, self.year, self.month, self.day, self.hour, self.minute, 0, 0, tzinfo,
+            )
+        }
+
+        /// Advance to the start of the next whole minute; `next_runs`/`iter_runs` apply it to
+        /// `start_utc` (whose seconds/microseconds `from_py_datetime` drops) before
+        /// `advance_to_next_match`, so a firing in `start_utc`'s own minute is not returned.
+        fn next_minute(mut self) -> Self {
+            self.minute += 1;
+            if self.minute > 59 {
+                self.minute = 0;
+                self = self.next_hour_raw();
             }
-            Err(PyValueError::new_err(
-                "Hour must be an integer in range 1..=31",
-            ))
+            self
         }
-    }
 
-    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-    struct CronMonth(u8);
+        fn next_hour_raw(mut self) -> Self {
+            self.hour += 1;
+            if self.hour > 23 {
+                self.hour = 0;
+                self = self.next_day_raw();
+            }
+            self
+        }
 
-    impl<'a, 'py> FromPyObject<'a, 'py> for CronMonth {
-        type Error = PyErr;
-        fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
-            let msg = "Month must be an integer in range 1..=12";
-            if let Ok(num) = obj.extract::<u8>()
-                && (1..=12).contains(&num)
-            {
-                return Ok(CronMonth(num));
+        fn next_day_raw(mut self) -> Self {
+            self.day += 1;
+            if self.day > Self::days_in_month(self.year, self.month) {
+                self.day = 1;
+                self.month += 1;
+                if self.month > 12 {
+                    self.month = 1;
+                    self.year += 1;
+                }
             }
-            Err(PyValueError::new_err(msg))
+            self
+        }
+
+        /// Jump to the first minute of the next hour, used when the current hour can't possibly
+        /// satisfy the cron's `hour` field.
+        fn next_hour(mut self) -> Self {
+            self.minute = 0;
+            self.next_hour_raw()
+        }
+
+        /// Jump to the first minute of the next day, used when the current day can't possibly
+        /// satisfy the cron's `day`/`day_of_week` fields.
+        fn next_day(mut self) -> Self {
+            self.minute = 0;
+            self.hour = 0;
+            self.next_day_raw()
+        }
+
+        /// Jump to the first minute of the next month, used when the current month can't
+        /// possibly satisfy the cron's `month` field.
+        fn next_month(mut self) -> Self {
+            self.minute = 0;
+            self.hour = 0;
+            self.day = 1;
+            self.month += 1;
+            if self.month > 12 {
+                self.month = 1;
+                self.year += 1;
+            }
+            self
+        }
+
+        /// Days since the civil epoch (1970-01-01) for this clock's date, via Howard Hinnant's
+        /// `days_from_civil` algorithm; used only to measure the gap between two `CronClock`s in
+        /// minutes, so the epoch itself is arbitrary.
+        fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+            let y = if month <= 2 { year - 1 } else { year };
+            let era = if y >= 0 { y } else { y - 399 } / 400;
+            let yoe = y - era * 400;
+            let mp = (month + 9) % 12;
+            let doy = (153 * mp + 2) / 5 + day - 1;
+            let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+            era * 146097 + doe - 719468
+        }
+
+        /// Minutes since the civil epoch, used to measure the gap between two `CronClock`s.
+        fn minutes_since_epoch(&self) -> i64 {
+            let days = Self::days_from_civil(self.year as i64, self.month as i64, self.day as i64);
+            days * 24 * 60 + self.hour as i64 * 60 + self.minute as i64
+        }
+    }
+
+    #[cfg(test)]
+    mod cron_clock_tests {
+        use super::*;
+
+        #[test]
+        fn leap_years_follow_the_gregorian_rule() {
+            assert!(CronClock::is_leap_year(2000));
+            assert!(CronClock::is_leap_year(2024));
+            assert!(!CronClock::is_leap_year(1900));
+            assert!(!CronClock::is_leap_year(2023));
+        }
+
+        #[test]
+        fn days_in_month_accounts_for_leap_february() {
+            assert_eq!(CronClock::days_in_month(2024, 2), 29);
+            assert_eq!(CronClock::days_in_month(2023, 2), 28);
+            assert_eq!(CronClock::days_in_month(2024, 4), 30);
+            assert_eq!(CronClock::days_in_month(2024, 1), 31);
+        }
+
+        #[test]
+        fn weekday_matches_known_dates() {
+            // 2024-01-01 was a Monday.
+            let clock = CronClock {
+                year: 2024,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+            };
+            assert_eq!(clock.weekday(), 1);
+            // 2000-01-01 was a Saturday.
+            let clock = CronClock {
+                year: 2000,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+            };
+            assert_eq!(clock.weekday(), 6);
+        }
+
+        #[test]
+        fn next_minute_rolls_over_hour_day_month_and_year_boundaries() {
+            let clock = CronClock {
+                year: 2023,
+                month: 12,
+                day: 31,
+                hour: 23,
+                minute: 59,
+            };
+            let next = clock.next_minute();
+            assert_eq!(next.year, 2024);
+            assert_eq!(next.month, 1);
+            assert_eq!(next.day, 1);
+            assert_eq!(next.hour, 0);
+            assert_eq!(next.minute, 0);
+        }
+
+        #[test]
+        fn next_day_rolls_over_end_of_leap_february() {
+            let clock = CronClock {
+                year: 2024,
+                month: 2,
+                day: 29,
+                hour: 12,
+                minute: 30,
+            };
+            let next = clock.next_day();
+            assert_eq!(next.year, 2024);
+            assert_eq!(next.month, 3);
+            assert_eq!(next.day, 1);
+            assert_eq!(next.hour, 0);
+            assert_eq!(next.minute, 0);
+        }
+
+        #[test]
+        fn minutes_since_epoch_is_monotonic_across_a_day_boundary() {
+            let before = CronClock {
+                year: 2024,
+                month: 1,
+                day: 1,
+                hour: 23,
+                minute: 59,
+            };
+            let after = CronClock {
+                year: 2024,
+                month: 1,
+                day: 2,
+                hour: 0,
+                minute: 1,
+            };
+            assert_eq!(after.minutes_since_epoch() - before.minutes_since_epoch(), 2);
         }
     }
 
-    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-    struct CronDayOfWeek(u8);
-
-    impl<'a, 'py> FromPyObject<'a, 'py> for CronDayOfWeek {
-        type Error = PyErr;
-        fn extract(obj: Borrowed<'a, 'py, PyAny>) -> Result<Self, Self::Error> {
-            let msg = "Day of week must be an integer in range 0..=6 (0=Sunday)";
-            if let Ok(num) = obj.extract::<u8>()
-                && num <= 6
-            {
-                return Ok(CronDayOfWeek(num));
+    impl Cron {
+        fn expand_fields(&self) -> CronFields {
+            CronFields {
+                minute: expand_cron_step_type(self.minute.as_ref().map(|m| &m.0), 0, 59),
+                hour: expand_cron_step_type(self.hour.as_ref().map(|h| &h.0), 0, 23),
+                day: expand_cron_step_type(self.day.as_ref().map(|d| &d.0), 1, 31),
+                month: expand_cron_step_type(self.month.as_ref().map(|m| &m.0), 1, 12),
+                day_of_week: expand_cron_step_type(
+                    self.day_of_week.as_ref().map(|d| &d.0),
+                    0,
+                    6,
+                ),
             }
-            Err(PyValueError::new_err(msg))
+        }
+
+        /// The gap, in minutes, between this cron's first two firing times after an arbitrary
+        /// fixed reference instant. Used to flag schedules GitHub will silently throttle: it
+        /// doesn't guarantee firings closer together than every 5 minutes.
+        fn min_interval_minutes(&self) -> PyResult<i64> {
+            let fields = self.expand_fields();
+            let start = CronClock {
+                year: 2020,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+            };
+            let first = fields.advance_to_next_match(start)?;
+            let second = fields.advance_to_next_match(first.next_minute())?;
+            Ok(second.minutes_since_epoch() - first.minutes_since_epoch())
         }
     }
 
+    /// An infinite iterator over a `Cron`'s UTC firing times, returned by `Cron.iter_runs`.
+    /// Implements Python's iterator protocol directly rather than materializing a list, so it
+    /// can be consumed lazily (e.g. `itertools.islice(cron.iter_runs(start), 100)`).
     #[pyclass]
-    #[derive(Clone)]
-    struct Minute(CronStepType);
-    impl Display for Minute {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{}", self.0)
-        }
+    struct CronRunIter {
+        fields: CronFields,
+        clock: CronClock,
+        tzinfo: Option<Py<PyTzInfo>>,
     }
     #[pymethods]
-    impl Minute {
-        #[new]
-        fn new(minute: &Bound<PyAny>) -> PyResult<Self> {
-            if let Ok(l) = minute.extract::<Bound<PyList>>() {
-                let mut res = Vec::new();
-                for item in l.iter() {
-                    let item = item.extract::<CronMinute>()?;
-                    res.push(item.0);
-                }
-                return Ok(Self(CronStepType::List(res)));
+    impl CronRunIter {
+        fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+            slf
+        }
+        fn __next__(&mut self, py: Python<'_>) -> PyResult<Py<PyDateTime>> {
+            self.clock = self.fields.advance_to_next_match(self.clock)?;
+            let result = self
+                .clock
+                .to_py_datetime(py, self.tzinfo.as_ref().map(|t| t.bind(py)))?
+                .unbind();
+            self.clock = self.clock.next_minute();
+            Ok(result)
+        }
+    }
+
+    /// Parse one numeric cron field (`minute`, `hour`, ...) back into a `CronStepType`, returning
+    /// `None` for the wildcard `*`. For the `cron.month` and `cron.day-of-week` fields, a token
+    /// also accepts a case-insensitive name abbreviation (`JAN`, `MON`, ...) anywhere a bare
+    /// number would be accepted, and a combined range+step like `1-10/2` parses into
+    /// `CronStepType::RangeStep`.
+    fn parse_cron_step_type(
+        field: &str,
+        what: &str,
+        min: u8,
+        max: u8,
+    ) -> PyResult<Option<CronStepType>> {
+        if field == "*" {
+            return Ok(None);
+        }
+        let name_to_num = match what {
+            "cron.month" => Some(month_name_to_num as fn(&str) -> Option<u8>),
+            "cron.day-of-week" => Some(day_of_week_name_to_num as fn(&str) -> Option<u8>),
+            _ => None,
+        };
+        let parse_num = |s: &str| -> PyResult<u8> {
+            if let Some(n) = s.parse::<u8>().ok().filter(|n| (min..=max).contains(n)) {
+                return Ok(n);
             }
-            let minute = minute.extract::<CronMinute>()?;
-            Ok(Self(CronStepType::Value(minute.0)))
-        }
-        #[staticmethod]
-        fn between(start: &Bound<PyAny>, end: &Bound<PyAny>) -> PyResult<Self> {
-            let min = start.extract::<CronMinute>()?;
-            let max = end.extract::<CronMinute>()?;
-            Ok(Self(CronStepType::Range(min.0, max.0)))
-        }
-        #[staticmethod]
-        #[pyo3(signature = (interval, *, start = None))]
-        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
-            let start = start
-                .map(|a| a.extract::<CronMinute>())
-                .transpose()?
-                .map(|s| s.0);
-            let interval = interval.extract::<CronMinute>()?;
-            Ok(Self(CronStepType::Step {
-                start,
-                step: interval.0,
-            }))
-        }
+            if let Some(n) = name_to_num.and_then(|f| f(s)) {
+                return Ok(n);
+            }
+            Err(PyValueError::new_err(format!(
+                "Expected '{what}' to be an integer in range {min}..={max}, got '{s}'"
+            )))
+        };
+        if let Some((range, step)) = field.split_once('/') {
+            let step = parse_num(step)?;
+            if let Some((lo, hi)) = range.split_once('-') {
+                return Ok(Some(CronStepType::RangeStep {
+                    start: parse_num(lo)?,
+                    end: parse_num(hi)?,
+                    step,
+                }));
+            }
+            let start = if range == "*" {
+                None
+            } else {
+                Some(parse_num(range)?)
+            };
+            return Ok(Some(CronStepType::Step { start, step }));
+        }
+        if let Some((lo, hi)) = field.split_once('-') {
+            return Ok(Some(CronStepType::Range(parse_num(lo)?, parse_num(hi)?)));
+        }
+        if field.contains(',') {
+            return Ok(Some(CronStepType::List(
+                field
+                    .split(',')
+                    .map(parse_num)
+                    .collect::<PyResult<Vec<_>>>()?,
+            )));
+        }
+        Ok(Some(CronStepType::Value(parse_num(field)?)))
+    }
+
+    /// Parse a space-joined `"min hour day month dow"` cron expression, the inverse of `Cron`'s
+    /// `Yamlable` impl.
+    fn cron_from_str(s: &str) -> PyResult<Cron> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let [minute, hour, day, month, day_of_week] = fields.as_slice() else {
+            return Err(PyValueError::new_err(format!(
+                "Expected a cron expression with 5 space-separated fields, got '{s}'"
+            )));
+        };
+        Ok(Cron {
+            minute: parse_cron_step_type(minute, "cron.minute", 0, 59)?.map(Minute),
+            hour: parse_cron_step_type(hour, "cron.hour", 0, 23)?.map(Hour),
+            day: parse_cron_step_type(day, "cron.day", 1, 31)?.map(Day),
+            month: parse_cron_step_type(month, "cron.month", 1, 12)?.map(Month),
+            day_of_week: parse_cron_step_type(day_of_week, "cron.day-of-week", 0, 6)?
+                .map(DayOfWeek),
+        })
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct Hour(CronStepType);
-    impl Display for Hour {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{}", self.0)
-        }
+    struct ScheduleEvent {
+        crons: Option<Vec<Cron>>,
     }
     #[pymethods]
-    impl Hour {
+    impl ScheduleEvent {
         #[new]
-        fn new(hour: &Bound<PyAny>) -> PyResult<Self> {
-            if let Ok(l) = hour.extract::<Bound<PyList>>() {
-                let mut res = Vec::new();
-                for item in l.iter() {
-                    let item = item.extract::<CronHour>()?;
-                    res.push(item.0);
+        #[pyo3(signature = (*, crons=None))]
+        fn new(crons: Option<Vec<Cron>>) -> PyResult<Self> {
+            let crons = crons.filter(|v| !v.is_empty());
+            if let Some(crons) = &crons {
+                for cron in crons {
+                    let gap = cron.min_interval_minutes()?;
+                    if gap < 5 {
+                        return Err(PyValueError::new_err(format!(
+                            "cron '{}' fires every {gap} minute(s), but GitHub does not \
+                             guarantee schedules faster than every 5 minutes",
+                            cron.describe()
+                        )));
+                    }
                 }
-                return Ok(Self(CronStepType::List(res)));
             }
-            let hour = hour.extract::<CronHour>()?;
-            Ok(Self(CronStepType::Value(hour.0)))
+            Ok(Self { crons })
         }
-        #[staticmethod]
-        fn between(start: &Bound<PyAny>, end: &Bound<PyAny>) -> PyResult<Self> {
-            let min = start.extract::<CronHour>()?;
-            let max = end.extract::<CronHour>()?;
-            Ok(Self(CronStepType::Range(min.0, max.0)))
+
+        fn __str__(&self) -> PyResult<String> {
+            self.maybe_as_yaml_string()
         }
+
         #[staticmethod]
-        #[pyo3(signature = (interval, *, start = None))]
-        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
-            let start = start
-                .map(|a| a.extract::<CronHour>())
-                .transpose()?
-                .map(|s| s.0);
-            let interval = interval.extract::<CronHour>()?;
-            Ok(Self(CronStepType::Step {
-                start,
-                step: interval.0,
-            }))
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            schedule_event_from_yaml(&parse_yaml_document(yaml)?)
+        }
+    }
+    impl MaybeYamlable for &ScheduleEvent {
+        fn maybe_as_yaml(&self) -> Option<Yaml> {
+            let mut out = Array::new();
+            if let Some(crons) = &self.crons {
+                for cron in crons {
+                    out.push_yaml(cron);
+                }
+                Some(Yaml::Array(out))
+            } else {
+                None
+            }
+        }
+    }
+    fn schedule_event_from_yaml(yaml: &Yaml) -> PyResult<ScheduleEvent> {
+        let crons = match yaml {
+            Yaml::Array(arr) => arr
+                .iter()
+                .map(|entry| {
+                    let mut hash = expect_hash(entry, "schedule")?.clone();
+                    let cron = hash_take(&mut hash, "cron").ok_or_else(|| {
+                        PyValueError::new_err("Expected 'schedule' entry to have a 'cron' key")
+                    })?;
+                    let s = yaml_scalar_to_string(&cron, "schedule.cron")?;
+                    reject_unknown_keys(&hash, "schedule")?;
+                    cron_from_str(&s)
+                })
+                .collect::<PyResult<Vec<_>>>()?,
+            Yaml::BadValue | Yaml::Null => Vec::new(),
+            _ => return Err(PyValueError::new_err("Expected 'schedule' to be a list")),
+        };
+        ScheduleEvent::new((!crons.is_empty()).then_some(crons))
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum WatchActivity {
+        Started,
+    }
+    impl ActivityKind for WatchActivity {
+        const ALL: &'static [Self] = &[Self::Started];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Started => "started",
+            }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct Day(CronStepType);
-    impl Display for Day {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{}", self.0)
-        }
+    struct WatchEvent {
+        types: ActivityTypes<WatchActivity>,
     }
     #[pymethods]
-    impl Day {
+    impl WatchEvent {
         #[new]
-        fn new(day: &Bound<PyAny>) -> PyResult<Self> {
-            if let Ok(l) = day.extract::<Bound<PyList>>() {
-                let mut res = Vec::new();
-                for item in l.iter() {
-                    let item = item.extract::<CronDay>()?;
-                    res.push(item.0);
-                }
-                return Ok(Self(CronStepType::List(res)));
+        #[pyo3(signature = (*, started=false))]
+        fn new(started: bool) -> Self {
+            Self {
+                types: ActivityTypes::from_flags([(WatchActivity::Started, started)]),
             }
-            let day = day.extract::<CronDay>()?;
-            Ok(Self(CronStepType::Value(day.0)))
         }
+
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
         #[staticmethod]
-        fn between(min: &Bound<PyAny>, max: &Bound<PyAny>) -> PyResult<Self> {
-            let min = min.extract::<CronDay>()?;
-            let max = max.extract::<CronDay>()?;
-            Ok(Self(CronStepType::Range(min.0, max.0)))
+        fn allowed_types() -> Vec<&'static str> {
+            WatchActivity::ALL.iter().map(|k| k.as_str()).collect()
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.maybe_as_yaml_string()
         }
+
         #[staticmethod]
-        #[pyo3(signature = (interval, *, start = None))]
-        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
-            let start = start
-                .map(|a| a.extract::<CronDay>())
-                .transpose()?
-                .map(|s| s.0);
-            let interval = interval.extract::<CronDay>()?;
-            Ok(Self(CronStepType::Step {
-                start,
-                step: interval.0,
-            }))
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            watch_event_from_hash(expect_hash_or_empty(&parse_yaml_document(yaml)?, "watch")?)
+        }
+
+        /// What activity types `other` turned on or off relative to `self`, keyed
+        /// `"enabled"`/`"disabled"`.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            diff_activity_types(py, &self.types, &other.types)
+        }
+
+        /// The union of `self` and `other`'s enabled activity types.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+            }
+        }
+    }
+    impl ActivityEvent for WatchEvent {
+        type Kind = WatchActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
         }
     }
+    fn watch_event_from_hash(mut hash: Hash) -> PyResult<WatchEvent> {
+        let types = ActivityTypes::parse(&mut hash, "watch")?;
+        reject_unknown_keys(&hash, "watch")?;
+        Ok(WatchEvent { types })
+    }
 
-    #[pyclass]
     #[derive(Clone)]
-    struct Month(CronStepType);
-    impl Display for Month {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{}", self.0)
-        }
+    enum WorkflowInputType {
+        Boolean { default: Option<BoolLike> },
+        Choice { options: Vec<String>, default: Option<String> },
+        Environment { default: Option<String> },
+        Number { default: Option<IntLike> },
+        String { default: Option<StringLike> },
     }
-    #[pymethods]
-    impl Month {
-        #[new]
-        fn new(month: &Bound<PyAny>) -> PyResult<Self> {
-            if let Ok(l) = month.extract::<Bound<PyList>>() {
-                let mut res = Vec::new();
-                for item in l.iter() {
-                    let item = item.extract::<CronMonth>()?;
-                    res.push(item.0);
+    impl WorkflowInputType {
+        fn get_type(&self) -> Yaml {
+            match self {
+                Self::Boolean { .. } => Yaml::from_str("boolean"),
+                Self::Choice { .. } => Yaml::from_str("choice"),
+                Self::Environment { .. } => Yaml::from_str("environment"),
+                Self::Number { .. } => Yaml::from_str("number"),
+                Self::String { .. } => Yaml::from_str("string"),
+            }
+        }
+        fn get_default(&self) -> Option<Yaml> {
+            match self {
+                Self::Boolean { default } => default.clone().map(|b| b.as_yaml()),
+                Self::Choice { default, .. } | Self::Environment { default } => {
+                    default.clone().map(|s| s.as_yaml())
                 }
-                return Ok(Self(CronStepType::List(res)));
+                Self::Number { default } => default.clone().map(|n| n.as_yaml()),
+                Self::String { default } => default.clone().map(|s| s.as_yaml()),
             }
-            let month = month.extract::<CronMonth>()?;
-            Ok(Self(CronStepType::Value(month.0)))
         }
-        #[staticmethod]
-        fn between(min: &Bound<PyAny>, max: &Bound<PyAny>) -> PyResult<Self> {
-            let min = min.extract::<CronMonth>()?;
-            let max = max.extract::<CronMonth>()?;
-            Ok(Self(CronStepType::Range(min.0, max.0)))
+        fn get_options(&self) -> Option<Yaml> {
+            match self {
+                Self::Choice { options, .. } => Some(options.as_yaml()),
+                _ => None,
+            }
         }
-        #[staticmethod]
-        #[pyo3(signature = (interval, *, start = None))]
-        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
-            let start = start
-                .map(|a| a.extract::<CronMonth>())
-                .transpose()?
-                .map(|s| s.0);
-            let interval = interval.extract::<CronMonth>()?;
-            Ok(Self(CronStepType::Step {
-                start,
-                step: interval.0,
-            }))
+    }
+
+    /// Check that a `choice` input's `default`, if given, is one of its own `options`, the
+    /// shared rule behind both `WorkflowInput.choice` and its `from_yaml` round-trip.
+    fn validate_choice_default(options: &[String], default: Option<&String>) -> PyResult<()> {
+        if let Some(default) = default
+            && !options.iter().any(|o| o == default)
+        {
+            return Err(PyValueError::new_err(format!(
+                "'default' ({default:?}) must be one of 'options' ({options:?})"
+            )));
         }
+        Ok(())
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct DayOfWeek(CronStepType);
-    impl Display for DayOfWeek {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{}", self.0)
-        }
+    struct WorkflowInput {
+        description: Option<String>,
+        input_type: WorkflowInputType,
+        required: Option<bool>,
     }
     #[pymethods]
-    impl DayOfWeek {
-        #[new]
-        fn new(day_of_week: &Bound<PyAny>) -> PyResult<Self> {
-            if let Ok(l) = day_of_week.extract::<Bound<PyList>>() {
-                let mut res = Vec::new();
-                for item in l.iter() {
-                    let item = item.extract::<CronDayOfWeek>()?;
-                    res.push(item.0);
-                }
-                return Ok(Self(CronStepType::List(res)));
+    impl WorkflowInput {
+        #[staticmethod]
+        #[pyo3(signature = (*, description=None, default=None, required=None))]
+        fn boolean(
+            description: Option<String>,
+            default: Option<BoolLike>,
+            required: Option<bool>,
+        ) -> PyResult<Self> {
+            if let Some(default) = &default {
+                validate_bool_like(default, ALLOWED_WORKFLOW_CALL_INPUT_DEFAULT)?;
             }
-            let day_of_week = day_of_week.extract::<CronMonth>()?;
-            Ok(Self(CronStepType::Value(day_of_week.0)))
+            Ok(Self {
+                description,
+                input_type: WorkflowInputType::Boolean { default },
+                required,
+            })
         }
         #[staticmethod]
-        fn between(min: &Bound<PyAny>, max: &Bound<PyAny>) -> PyResult<Self> {
-            let min = min.extract::<CronDayOfWeek>()?;
-            let max = max.extract::<CronDayOfWeek>()?;
-            Ok(Self(CronStepType::Range(min.0, max.0)))
+        #[pyo3(signature = (options, *, description=None, default=None, required=None))]
+        fn choice(
+            options: Vec<String>,
+            description: Option<String>,
+            default: Option<String>,
+            required: Option<bool>,
+        ) -> PyResult<Self> {
+            validate_choice_default(&options, default.as_ref())?;
+            Ok(Self {
+                description,
+                input_type: WorkflowInputType::Choice { options, default },
+                required,
+            })
         }
         #[staticmethod]
-        #[pyo3(signature = (interval, *, start = None))]
-        fn every(interval: &Bound<PyAny>, start: Option<Bound<PyAny>>) -> PyResult<Self> {
-            let start = start
-                .map(|a| a.extract::<CronDayOfWeek>())
-                .transpose()?
-                .map(|s| s.0);
-            let interval = interval.extract::<CronDayOfWeek>()?;
-            Ok(Self(CronStepType::Step {
-                start,
-                step: interval.0,
-            }))
+        #[pyo3(signature = (*, description=None, default=None, required=None))]
+        fn environment(
+            description: Option<String>,
+            default: Option<String>,
+            required: Option<bool>,
+        ) -> Self {
+            Self {
+                description,
+                input_type: WorkflowInputType::Environment { default },
+                required,
+            }
+        }
+        #[staticmethod]
+        #[pyo3(signature = (*, description=None, default=None, required=None))]
+        fn number(
+            description: Option<String>,
+            default: Option<IntLike>,
+            required: Option<bool>,
+        ) -> PyResult<Self> {
+            if let Some(default) = &default {
+                validate_int_like(default, ALLOWED_WORKFLOW_CALL_INPUT_DEFAULT)?;
+            }
+            Ok(Self {
+                description,
+                input_type: WorkflowInputType::Number { default },
+                required,
+            })
+        }
+        #[staticmethod]
+        #[pyo3(signature = (*, description=None, default=None, required=None))]
+        fn string(
+            description: Option<String>,
+            default: Option<StringLike>,
+            required: Option<bool>,
+        ) -> PyResult<Self> {
+            if let Some(default) = &default {
+                validate_string_like(default, ALLOWED_WORKFLOW_CALL_INPUT_DEFAULT)?;
+            }
+            Ok(Self {
+                description,
+                input_type: WorkflowInputType::String { default },
+                required,
+            })
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            workflow_input_from_hash(
+                expect_hash(&parse_yaml_document(yaml)?, "workflow_call.inputs.<input_id>")?
+                    .clone(),
+                "workflow_call.inputs.<input_id>",
+            )
+        }
+    }
+    impl Yamlable for &WorkflowInput {
+        fn as_yaml(&self) -> Yaml {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("description", &self.description);
+            out.insert_yaml("type", self.input_type.get_type());
+            out.insert_yaml_opt("required", self.required);
+            out.insert_yaml_opt("default", self.input_type.get_default());
+            out.insert_yaml_opt("options", self.input_type.get_options());
+            Yaml::Hash(out)
         }
     }
+    fn workflow_input_from_hash(mut hash: Hash, what: &str) -> PyResult<WorkflowInput> {
+        let description = hash_take(&mut hash, "description")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.description")))
+            .transpose()?;
+        let required = hash_take(&mut hash, "required")
+            .map(|y| yaml_as_bool(&y, &format!("{what}.required")))
+            .transpose()?;
+        let type_yaml = hash_take(&mut hash, "type")
+            .ok_or_else(|| PyValueError::new_err(format!("Expected '{what}' to have a 'type' key")))?;
+        let type_str = yaml_scalar_to_string(&type_yaml, &format!("{what}.type"))?;
+        let default = hash_take(&mut hash, "default");
+        let input_type = match type_str.as_str() {
+            "boolean" => WorkflowInputType::Boolean {
+                default: default
+                    .map(|y| parse_bool_like(&y, &format!("{what}.default")))
+                    .transpose()?,
+            },
+            "choice" => {
+                let options = hash_take(&mut hash, "options")
+                    .map(|y| parse_string_vec(&y, &format!("{what}.options")))
+                    .transpose()?
+                    .unwrap_or_default();
+                let default = default
+                    .map(|y| yaml_scalar_to_string(&y, &format!("{what}.default")))
+                    .transpose()?;
+                validate_choice_default(&options, default.as_ref())?;
+                WorkflowInputType::Choice { options, default }
+            }
+            "environment" => WorkflowInputType::Environment {
+                default: default
+                    .map(|y| yaml_scalar_to_string(&y, &format!("{what}.default")))
+                    .transpose()?,
+            },
+            "number" => WorkflowInputType::Number {
+                default: default
+                    .map(|y| parse_int_like(&y, &format!("{what}.default")))
+                    .transpose()?,
+            },
+            "string" => WorkflowInputType::String {
+                default: default
+                    .map(|y| parse_string_like(&y, &format!("{what}.default")))
+                    .transpose()?,
+            },
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown '{what}.type' '{other}'; expected one of: boolean, choice, \
+                     environment, number, string"
+                )));
+            }
+        };
+        reject_unknown_keys(&hash, what)?;
+        Ok(WorkflowInput {
+            description,
+            input_type,
+            required,
+        })
+    }
 
     #[pyclass]
     #[derive(Clone)]
-    struct Cron {
-        minute: Option<Minute>,
-        hour: Option<Hour>,
-        day: Option<Day>,
-        month: Option<Month>,
-        day_of_week: Option<DayOfWeek>,
+    struct WorkflowOutput {
+        description: Option<String>,
+        value: StringLike,
     }
     #[pymethods]
-    impl Cron {
+    impl WorkflowOutput {
         #[new]
-        #[pyo3(signature = (*, minute = None, hour = None, day = None, month = None, day_of_week = None))]
-        fn new(
-            minute: Option<Minute>,
-            hour: Option<Hour>,
-            day: Option<Day>,
-            month: Option<Month>,
-            day_of_week: Option<DayOfWeek>,
-        ) -> Self {
-            Self {
-                minute,
-                hour,
-                day,
-                month,
-                day_of_week,
-            }
+        #[pyo3(signature = (value, *, description=None))]
+        fn new(value: StringLike, description: Option<String>) -> PyResult<Self> {
+            validate_string_like(&value, ALLOWED_WORKFLOW_CALL_OUTPUT_VALUE)?;
+            Ok(Self { description, value })
         }
 
         fn __str__(&self) -> PyResult<String> {
             self.as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            workflow_output_from_hash(
+                expect_hash(
+                    &parse_yaml_document(yaml)?,
+                    "workflow_call.outputs.<output_id>",
+                )?
+                .clone(),
+            )
+        }
     }
-    impl Yamlable for &Cron {
+    impl Yamlable for &WorkflowOutput {
         fn as_yaml(&self) -> Yaml {
             let mut out = Hash::new();
-            let s = format!(
-                "{} {} {} {} {}",
-                self.minute
-                    .clone()
-                    .map_or("*".to_string(), |s| s.to_string()),
-                self.hour.clone().map_or("*".to_string(), |s| s.to_string()),
-                self.day.clone().map_or("*".to_string(), |s| s.to_string()),
-                self.month
-                    .clone()
-                    .map_or("*".to_string(), |s| s.to_string()),
-                self.day_of_week
-                    .clone()
-                    .map_or("*".to_string(), |s| s.to_string())
-            );
-            out.insert_yaml("cron", s);
+            out.insert_yaml_opt("description", &self.description);
+            out.insert_yaml("value", &self.value);
             Yaml::Hash(out)
         }
     }
+    fn workflow_output_from_hash(mut hash: Hash) -> PyResult<WorkflowOutput> {
+        let what = "workflow_call.outputs.<output_id>";
+        let description = hash_take(&mut hash, "description")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.description")))
+            .transpose()?;
+        let value = hash_take(&mut hash, "value")
+            .ok_or_else(|| PyValueError::new_err(format!("Expected '{what}' to have a 'value' key")))
+            .and_then(|y| parse_string_like(&y, &format!("{what}.value")))?;
+        reject_unknown_keys(&hash, what)?;
+        Ok(WorkflowOutput { description, value })
+    }
 
     #[pyclass]
     #[derive(Clone)]
-    struct ScheduleEvent {
-        crons: Option<Vec<Cron>>,
+    struct WorkflowSecret {
+        description: Option<String>,
+        required: Option<bool>,
     }
     #[pymethods]
-    impl ScheduleEvent {
+    impl WorkflowSecret {
         #[new]
-        #[pyo3(signature = (*, crons=None))]
-        fn new(crons: Option<Vec<Cron>>) -> Self {
-            let crons = crons.filter(|v| !v.is_empty());
-            Self { crons }
+        #[pyo3(signature = (*, description=None, required=None))]
+        fn new(description: Option<String>, required: Option<bool>) -> Self {
+            Self {
+                description,
+                required,
+            }
         }
 
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            workflow_secret_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "workflow_call.secrets.<secret_id>",
+            )?)
+        }
     }
-    impl MaybeYamlable for &ScheduleEvent {
+    impl MaybeYamlable for &WorkflowSecret {
         fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut out = Array::new();
-            if let Some(crons) = &self.crons {
-                for cron in crons {
-                    out.push_yaml(cron);
-                }
-                Some(Yaml::Array(out))
-            } else {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("description", &self.description);
+            out.insert_yaml_opt("required", self.required);
+            if out.is_empty() {
                 None
+            } else {
+                Some(Yaml::Hash(out))
             }
         }
     }
+    fn workflow_secret_from_hash(mut hash: Hash) -> PyResult<WorkflowSecret> {
+        let what = "workflow_call.secrets.<secret_id>";
+        let description = hash_take(&mut hash, "description")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.description")))
+            .transpose()?;
+        let required = hash_take(&mut hash, "required")
+            .map(|y| yaml_as_bool(&y, &format!("{what}.required")))
+            .transpose()?;
+        reject_unknown_keys(&hash, what)?;
+        Ok(WorkflowSecret {
+            description,
+            required,
+        })
+    }
 
     #[pyclass]
     #[derive(Clone)]
-    struct WatchEvent {
-        started: bool,
+    struct WorkflowCallEvent {
+        inputs: PyMap<String, WorkflowInput>,
+        outputs: PyMap<String, WorkflowOutput>,
+        secrets: PyMap<String, WorkflowSecret>,
     }
     #[pymethods]
-    impl WatchEvent {
+    impl WorkflowCallEvent {
         #[new]
-        #[pyo3(signature = (*, started=false))]
-        fn new(started: bool) -> Self {
-            Self { started }
+        #[pyo3(signature = (*, inputs=None, outputs=None, secrets=None))]
+        fn new(
+            inputs: Option<PyMap<String, WorkflowInput>>,
+            outputs: Option<PyMap<String, WorkflowOutput>>,
+            secrets: Option<PyMap<String, WorkflowSecret>>,
+        ) -> Self {
+            Self {
+                inputs: inputs.unwrap_or_default(),
+                outputs: outputs.unwrap_or_default(),
+                secrets: secrets.unwrap_or_default(),
+            }
         }
 
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            workflow_call_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "workflow_call",
+            )?)
+        }
     }
-    impl MaybeYamlable for &WatchEvent {
+    impl MaybeYamlable for WorkflowCallEvent {
         fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if self.started {
-                let mut out = Hash::new();
-                let mut arr = Array::new();
-                arr.push_yaml_cond("started", self.started);
-                out.insert_yaml("types", Yaml::Array(arr));
-                Some(Yaml::Hash(out))
-            } else {
+            let mut out = Hash::new();
+            let mut inputs = Hash::new();
+            for (k, v) in self.inputs.iter() {
+                inputs.insert_yaml(k, v);
+            }
+            if !inputs.is_empty() {
+                out.insert_yaml("inputs", Yaml::Hash(inputs));
+            }
+            let mut outputs = Hash::new();
+            for (k, v) in self.outputs.iter() {
+                outputs.insert_yaml(k, v);
+            }
+            if !outputs.is_empty() {
+                out.insert_yaml("outputs", Yaml::Hash(outputs));
+            }
+            let mut secrets = Hash::new();
+            for (k, v) in self.secrets.iter() {
+                secrets.insert_yaml(k, v.maybe_as_yaml().unwrap_or(Yaml::Null));
+            }
+            if !secrets.is_empty() {
+                out.insert_yaml("secrets", Yaml::Hash(secrets));
+            }
+            if out.is_empty() {
                 None
+            } else {
+                Some(Yaml::Hash(out))
             }
         }
     }
+    fn workflow_call_event_from_hash(mut hash: Hash) -> PyResult<WorkflowCallEvent> {
+        let inputs = hash_take(&mut hash, "inputs")
+            .map(|y| {
+                expect_hash(&y, "workflow_call.inputs")?
+                    .iter()
+                    .map(|(k, v)| {
+                        let id = yaml_scalar_to_string(k, "workflow_call.inputs")?;
+                        let input = workflow_input_from_hash(
+                            expect_hash(v, &format!("workflow_call.inputs.{id}"))?.clone(),
+                            &format!("workflow_call.inputs.{id}"),
+                        )?;
+                        Ok((id, input))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+                    .map(|pairs| pairs.into_iter().collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let outputs = hash_take(&mut hash, "outputs")
+            .map(|y| {
+                expect_hash(&y, "workflow_call.outputs")?
+                    .iter()
+                    .map(|(k, v)| {
+                        let id = yaml_scalar_to_string(k, "workflow_call.outputs")?;
+                        let output =
+                            workflow_output_from_hash(expect_hash(v, "workflow_call.outputs")?.clone())?;
+                        Ok((id, output))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+                    .map(|pairs| pairs.into_iter().collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let secrets = hash_take(&mut hash, "secrets")
+            .map(|y| {
+                expect_hash(&y, "workflow_call.secrets")?
+                    .iter()
+                    .map(|(k, v)| {
+                        let id = yaml_scalar_to_string(k, "workflow_call.secrets")?;
+                        let secret = workflow_secret_from_hash(expect_hash_or_empty(
+                            v,
+                            "workflow_call.secrets",
+                        )?)?;
+                        Ok((id, secret))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+                    .map(|pairs| pairs.into_iter().collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+        reject_unknown_keys(&hash, "workflow_call")?;
+        Ok(WorkflowCallEvent {
+            inputs,
+            outputs,
+            secrets,
+        })
+    }
 
     #[derive(Clone)]
-    enum WorkflowInputType {
-        Boolean { default: Option<BoolLike> },
-        Number { default: Option<IntLike> },
-        String { default: Option<StringLike> },
+    enum WorkflowDispatchInputType {
+        Boolean {
+            default: Option<bool>,
+        },
+        Choice {
+            default: Option<String>,
+            options: Vec<String>,
+        },
+        Number {
+            default: Option<i64>,
+        },
+        Environment,
+        String {
+            default: Option<String>,
+        },
     }
-    impl WorkflowInputType {
+    impl WorkflowDispatchInputType {
         fn get_type(&self) -> Yaml {
             match self {
                 Self::Boolean { .. } => Yaml::from_str("boolean"),
+                Self::Choice { .. } => Yaml::from_str("choice"),
                 Self::Number { .. } => Yaml::from_str("number"),
+                Self::Environment => Yaml::from_str("environment"),
                 Self::String { .. } => Yaml::from_str("string"),
             }
         }
         fn get_default(&self) -> Option<Yaml> {
             match self {
-                Self::Boolean { default } => default.clone().map(|b| b.as_yaml()),
-                Self::Number { default } => default.clone().map(|n| n.as_yaml()),
-                Self::String { default } => default.clone().map(|s| s.as_yaml()),
+                Self::Boolean { default } => default.map(Yaml::Boolean),
+                Self::Choice { default, .. } | Self::String { default } => {
+                    default.clone().map(Yaml::String)
+                }
+                Self::Number { default } => default.map(Yaml::Integer),
+                Self::Environment => None, // TODO: check if environment can have a default
+            }
+        }
+        fn get_options(&self) -> Option<Yaml> {
+            if let Self::Choice { options, .. } = self {
+                Some(Yaml::Array(
+                    options.iter().map(|s| Yaml::String(s.clone())).collect(),
+                ))
+            } else {
+                None
             }
         }
     }
 
     #[pyclass]
     #[derive(Clone)]
-    struct WorkflowInput {
+    struct WorkflowDispatchInput {
         description: Option<String>,
-        input_type: WorkflowInputType,
+        input_type: WorkflowDispatchInputType,
         required: Option<bool>,
     }
     #[pymethods]
-    impl WorkflowInput {
+    impl WorkflowDispatchInput {
         #[staticmethod]
         #[pyo3(signature = (*, description=None, default=None, required=None))]
         fn boolean(
             description: Option<String>,
-            default: Option<BoolLike>,
+            default: Option<bool>,
+            required: Option<bool>,
+        ) -> Self {
+            Self {
+                description,
+                input_type: WorkflowDispatchInputType::Boolean { default },
+                required,
+            }
+        }
+        #[staticmethod]
+        #[pyo3(signature = (options, *, description=None, default=None, required=None))]
+        fn choice(
+            options: Vec<String>,
+            description: Option<String>,
+            default: Option<String>,
             required: Option<bool>,
         ) -> PyResult<Self> {
-            if let Some(default) = &default {
-                validate_bool_like(default, ALLOWED_WORKFLOW_CALL_INPUT_DEFAULT)?;
+            if options.is_empty() {
+                return Err(PyValueError::new_err(
+                    "'options' must be non-empty for a 'choice' workflow_dispatch input",
+                ));
             }
+            validate_choice_default(&options, default.as_ref())?;
             Ok(Self {
                 description,
-                input_type: WorkflowInputType::Boolean { default },
+                input_type: WorkflowDispatchInputType::Choice { default, options },
                 required,
             })
         }
@@ -5455,902 +11718,2205 @@ mod yamloom {
         #[pyo3(signature = (*, description=None, default=None, required=None))]
         fn number(
             description: Option<String>,
-            default: Option<IntLike>,
+            default: Option<i64>,
             required: Option<bool>,
-        ) -> PyResult<Self> {
-            if let Some(default) = &default {
-                validate_int_like(default, ALLOWED_WORKFLOW_CALL_INPUT_DEFAULT)?;
+        ) -> Self {
+            Self {
+                description,
+                input_type: WorkflowDispatchInputType::Number { default },
+                required,
             }
-            Ok(Self {
+        }
+        #[staticmethod]
+        #[pyo3(signature = (*, description=None, required=None))]
+        fn environment(description: Option<String>, required: Option<bool>) -> Self {
+            Self {
                 description,
-                input_type: WorkflowInputType::Number { default },
+                input_type: WorkflowDispatchInputType::Environment,
                 required,
-            })
+            }
         }
         #[staticmethod]
         #[pyo3(signature = (*, description=None, default=None, required=None))]
         fn string(
             description: Option<String>,
-            default: Option<StringLike>,
+            default: Option<String>,
             required: Option<bool>,
-        ) -> PyResult<Self> {
-            if let Some(default) = &default {
-                validate_string_like(default, ALLOWED_WORKFLOW_CALL_INPUT_DEFAULT)?;
-            }
-            Ok(Self {
+        ) -> Self {
+            Self {
                 description,
-                input_type: WorkflowInputType::String { default },
+                input_type: WorkflowDispatchInputType::String { default },
                 required,
-            })
+            }
         }
 
         fn __str__(&self) -> PyResult<String> {
             self.as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            workflow_dispatch_input_from_hash(
+                expect_hash(
+                    &parse_yaml_document(yaml)?,
+                    "workflow_dispatch.inputs.<input_id>",
+                )?
+                .clone(),
+                "workflow_dispatch.inputs.<input_id>",
+            )
+        }
     }
-    impl Yamlable for &WorkflowInput {
+    impl Yamlable for &WorkflowDispatchInput {
         fn as_yaml(&self) -> Yaml {
             let mut out = Hash::new();
             out.insert_yaml_opt("description", &self.description);
             out.insert_yaml("type", self.input_type.get_type());
             out.insert_yaml_opt("required", self.required);
             out.insert_yaml_opt("default", self.input_type.get_default());
+            out.insert_yaml_opt("options", self.input_type.get_options());
             Yaml::Hash(out)
         }
     }
+    fn workflow_dispatch_input_from_hash(
+        mut hash: Hash,
+        what: &str,
+    ) -> PyResult<WorkflowDispatchInput> {
+        let description = hash_take(&mut hash, "description")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.description")))
+            .transpose()?;
+        let required = hash_take(&mut hash, "required")
+            .map(|y| yaml_as_bool(&y, &format!("{what}.required")))
+            .transpose()?;
+        let type_yaml = hash_take(&mut hash, "type")
+            .ok_or_else(|| PyValueError::new_err(format!("Expected '{what}' to have a 'type' key")))?;
+        let type_str = yaml_scalar_to_string(&type_yaml, &format!("{what}.type"))?;
+        let default = hash_take(&mut hash, "default");
+        let input_type = match type_str.as_str() {
+            "boolean" => WorkflowDispatchInputType::Boolean {
+                default: default
+                    .map(|y| yaml_as_bool(&y, &format!("{what}.default")))
+                    .transpose()?,
+            },
+            "choice" => {
+                let options = hash_take(&mut hash, "options")
+                    .map(|y| parse_string_vec(&y, &format!("{what}.options")))
+                    .transpose()?
+                    .unwrap_or_default();
+                if options.is_empty() {
+                    return Err(PyValueError::new_err(format!(
+                        "'{what}.options' must be non-empty for a 'choice' workflow_dispatch input"
+                    )));
+                }
+                let default = default
+                    .map(|y| yaml_scalar_to_string(&y, &format!("{what}.default")))
+                    .transpose()?;
+                validate_choice_default(&options, default.as_ref())?;
+                WorkflowDispatchInputType::Choice { default, options }
+            }
+            "number" => WorkflowDispatchInputType::Number {
+                default: default
+                    .map(|y| {
+                        y.as_i64().ok_or_else(|| {
+                            PyValueError::new_err(format!(
+                                "Expected '{what}.default' to be an integer"
+                            ))
+                        })
+                    })
+                    .transpose()?,
+            },
+            "environment" => WorkflowDispatchInputType::Environment,
+            "string" => WorkflowDispatchInputType::String {
+                default: default
+                    .map(|y| yaml_scalar_to_string(&y, &format!("{what}.default")))
+                    .transpose()?,
+            },
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown '{what}.type' '{other}'; expected one of: boolean, choice, number, environment, string"
+                )));
+            }
+        };
+        reject_unknown_keys(&hash, what)?;
+        Ok(WorkflowDispatchInput {
+            description,
+            input_type,
+            required,
+        })
+    }
+
+    /// GitHub's hard cap on the number of inputs a `workflow_dispatch` event may declare.
+    const MAX_WORKFLOW_DISPATCH_INPUTS: usize = 10;
+
+    fn validate_workflow_dispatch_input_count(inputs: &PyMap<String, WorkflowDispatchInput>) -> PyResult<()> {
+        let count = inputs.iter().count();
+        if count > MAX_WORKFLOW_DISPATCH_INPUTS {
+            return Err(PyValueError::new_err(format!(
+                "'workflow_dispatch' accepts at most {MAX_WORKFLOW_DISPATCH_INPUTS} inputs, but {count} were given"
+            )));
+        }
+        Ok(())
+    }
 
     #[pyclass]
     #[derive(Clone)]
-    struct WorkflowOutput {
-        description: Option<String>,
-        value: StringLike,
+    struct WorkflowDispatchEvent {
+        inputs: Option<PyMap<String, WorkflowDispatchInput>>,
     }
     #[pymethods]
-    impl WorkflowOutput {
+    impl WorkflowDispatchEvent {
         #[new]
-        #[pyo3(signature = (value, *, description=None))]
-        fn new(value: StringLike, description: Option<String>) -> PyResult<Self> {
-            validate_string_like(&value, ALLOWED_WORKFLOW_CALL_OUTPUT_VALUE)?;
-            Ok(Self { description, value })
+        #[pyo3(signature = (*, inputs=None))]
+        fn new(inputs: Option<PyMap<String, WorkflowDispatchInput>>) -> PyResult<Self> {
+            if let Some(inputs) = &inputs {
+                validate_workflow_dispatch_input_count(inputs)?;
+            }
+            Ok(Self {
+                inputs: inputs.filter(|i| !i.is_empty()),
+            })
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            workflow_dispatch_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "workflow_dispatch",
+            )?)
+        }
+
+        /// Check this event's inputs against a specific CI platform's capabilities, raising a
+        /// clear error naming the first input that uses a type the target doesn't support.
+        /// Forgejo and Gitea don't support the `environment` `workflow_dispatch` input type.
+        ///
+        /// Parameters
+        /// ----------
+        /// target
+        ///     One of `"github-actions"`, `"forgejo"`, or `"gitea"`.
+        fn validate_target(&self, target: &str) -> PyResult<()> {
+            Dialect::from_str(target)?.validate_workflow_dispatch(self)
+        }
+    }
+    impl MaybeYamlable for &WorkflowDispatchEvent {
+        fn maybe_as_yaml(&self) -> Option<Yaml> {
+            if let Some(inputs) = &self.inputs {
+                let mut out = Hash::new();
+                for (k, v) in inputs.iter() {
+                    out.insert_yaml(k, v);
+                }
+                Some(Yaml::Hash(out))
+            } else {
+                None
+            }
+        }
+    }
+    /// `maybe_as_yaml` above flattens `inputs` directly into `workflow_dispatch:` without an
+    /// `inputs:` wrapper key, so this mirrors that by reading every remaining key as an input id.
+    fn workflow_dispatch_event_from_hash(hash: Hash) -> PyResult<WorkflowDispatchEvent> {
+        let inputs = hash
+            .iter()
+            .map(|(k, v)| {
+                let id = yaml_scalar_to_string(k, "workflow_dispatch")?;
+                let input = workflow_dispatch_input_from_hash(
+                    expect_hash(v, &format!("workflow_dispatch.{id}"))?.clone(),
+                    &format!("workflow_dispatch.{id}"),
+                )?;
+                Ok((id, input))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let inputs: Option<PyMap<String, WorkflowDispatchInput>> =
+            (!inputs.is_empty()).then(|| inputs.into_iter().collect());
+        if let Some(inputs) = &inputs {
+            validate_workflow_dispatch_input_count(inputs)?;
+        }
+        Ok(WorkflowDispatchEvent { inputs })
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum WorkflowRunActivity {
+        Completed,
+        Requested,
+        InProgress,
+    }
+    impl ActivityKind for WorkflowRunActivity {
+        const ALL: &'static [Self] = &[Self::Completed, Self::Requested, Self::InProgress];
+        fn as_str(self) -> &'static str {
+            match self {
+                Self::Completed => "completed",
+                Self::Requested => "requested",
+                Self::InProgress => "in_progress",
+            }
+        }
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct WorkflowRunEvent {
+        types: ActivityTypes<WorkflowRunActivity>,
+        workflows: Option<Vec<String>>,
+        branches: Option<Vec<String>>,
+        branches_ignore: Option<Vec<String>>,
+    }
+    #[pymethods]
+    impl WorkflowRunEvent {
+        #[new]
+        #[pyo3(signature = (*, workflows=None, completed=false, requested=false, in_progress=false, branches=None, branches_ignore=None))]
+        fn new(
+            workflows: Option<Vec<String>>,
+            completed: bool,
+            requested: bool,
+            in_progress: bool,
+            branches: Option<Vec<String>>,
+            branches_ignore: Option<Vec<String>>,
+        ) -> PyResult<Self> {
+            let workflows = workflows.filter(|w| !w.is_empty());
+            let branches = branches.filter(|b| !b.is_empty());
+            let branches_ignore = branches_ignore.filter(|b| !b.is_empty());
+            validate_filter_conflict(
+                branches.as_ref(),
+                branches_ignore.as_ref(),
+                "workflow_run",
+                "branches",
+                "branches-ignore",
+            )?;
+            Ok(Self {
+                types: ActivityTypes::from_flags([
+                    (WorkflowRunActivity::Completed, completed),
+                    (WorkflowRunActivity::Requested, requested),
+                    (WorkflowRunActivity::InProgress, in_progress),
+                ]),
+                workflows,
+                branches,
+                branches_ignore,
+            })
+        }
+
+        /// The `types:` activity-type strings GitHub accepts for this event, the same
+        /// table `from_yaml` validates an incoming `types:` array against.
+        #[staticmethod]
+        fn allowed_types() -> Vec<&'static str> {
+            WorkflowRunActivity::ALL.iter().map(|k| k.as_str()).collect()
         }
 
         fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
+            self.maybe_as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            workflow_run_event_from_hash(expect_hash_or_empty(
+                &parse_yaml_document(yaml)?,
+                "workflow_run",
+            )?)
+        }
+
+        /// Whether `ref_name` (e.g. `"main"`) would satisfy this event's `branches`/
+        /// `branches-ignore` filter.
+        fn matches_ref(&self, ref_name: &str) -> PyResult<bool> {
+            matches_filter(ref_name, &self.branches, &self.branches_ignore)
+        }
+
+        /// What activity types `other` turned on or off relative to `self` (keyed
+        /// `"enabled"`/`"disabled"`), plus which `workflows`/`branches`/`branches-ignore`
+        /// entries were added or removed.
+        fn diff(&self, py: Python<'_>, other: &Self) -> PyResult<Py<PyDict>> {
+            let dict = diff_activity_types(py, &self.types, &other.types)?;
+            let dict = dict.into_bound(py);
+            let (workflows_added, workflows_removed) =
+                diff_filter_list(&self.workflows, &other.workflows);
+            dict.set_item("workflows_added", workflows_added)?;
+            dict.set_item("workflows_removed", workflows_removed)?;
+            let (branches_added, branches_removed) = diff_filter_list(&self.branches, &other.branches);
+            dict.set_item("branches_added", branches_added)?;
+            dict.set_item("branches_removed", branches_removed)?;
+            let (branches_ignore_added, branches_ignore_removed) =
+                diff_filter_list(&self.branches_ignore, &other.branches_ignore);
+            dict.set_item("branches_ignore_added", branches_ignore_added)?;
+            dict.set_item("branches_ignore_removed", branches_ignore_removed)?;
+            Ok(dict.unbind())
+        }
+
+        /// The union of `self` and `other`'s enabled activity types and filter lists.
+        fn merge(&self, other: &Self) -> Self {
+            Self {
+                types: merge_activity_types(&self.types, &other.types),
+                workflows: merge_filter_list(&self.workflows, &other.workflows),
+                branches: merge_filter_list(&self.branches, &other.branches),
+                branches_ignore: merge_filter_list(&self.branches_ignore, &other.branches_ignore),
+            }
         }
     }
-    impl Yamlable for &WorkflowOutput {
-        fn as_yaml(&self) -> Yaml {
-            let mut out = Hash::new();
-            out.insert_yaml_opt("description", &self.description);
-            out.insert_yaml("value", &self.value);
-            Yaml::Hash(out)
+    impl ActivityEvent for WorkflowRunEvent {
+        type Kind = WorkflowRunActivity;
+        fn activity_types(&self) -> &ActivityTypes<Self::Kind> {
+            &self.types
+        }
+        fn extra_yaml(&self, out: &mut Hash) {
+            out.insert_yaml_opt("workflows", &self.workflows);
+            out.insert_yaml_opt("branches", &self.branches);
+            out.insert_yaml_opt("branches-ignore", &self.branches_ignore);
         }
     }
+    fn workflow_run_event_from_hash(mut hash: Hash) -> PyResult<WorkflowRunEvent> {
+        let workflows = take_string_vec(&mut hash, "workflows", "workflow_run")?;
+        let types = ActivityTypes::parse(&mut hash, "workflow_run")?;
+        let branches = take_string_vec(&mut hash, "branches", "workflow_run")?;
+        let branches_ignore = take_string_vec(&mut hash, "branches-ignore", "workflow_run")?;
+        validate_filter_conflict(
+            branches.as_ref(),
+            branches_ignore.as_ref(),
+            "workflow_run",
+            "branches",
+            "branches-ignore",
+        )?;
+        reject_unknown_keys(&hash, "workflow_run")?;
+        Ok(WorkflowRunEvent {
+            types,
+            workflows,
+            branches,
+            branches_ignore,
+        })
+    }
 
-    #[pyclass]
-    #[derive(Clone)]
-    struct WorkflowSecret {
-        description: Option<String>,
-        required: Option<bool>,
+    /// Which GitHub-Actions-compatible CI platform a workflow's YAML targets. Forgejo and Gitea
+    /// read `.forgejo/workflows`/`.gitea/workflows` respectively and consume the same trigger
+    /// grammar as GitHub Actions, but with a reduced surface: several GitHub-only events
+    /// (`branch_protection_rule`, `image_version`, `merge_group`, `registry_package`) and the
+    /// `workflow_dispatch` `environment` input type have no equivalent there.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Dialect {
+        GitHubActions,
+        Forgejo,
+        Gitea,
     }
-    #[pymethods]
-    impl WorkflowSecret {
-        #[new]
-        #[pyo3(signature = (*, description=None, required=None))]
-        fn new(description: Option<String>, required: Option<bool>) -> Self {
-            Self {
-                description,
-                required,
+    impl FromStr for Dialect {
+        type Err = PyErr;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.to_lowercase().as_str() {
+                "github-actions" | "github" => Ok(Self::GitHubActions),
+                "forgejo" => Ok(Self::Forgejo),
+                "gitea" => Ok(Self::Gitea),
+                _ => Err(PyValueError::new_err(
+                    "Invalid dialect, expected 'github-actions', 'forgejo', or 'gitea'",
+                )),
+            }
+        }
+    }
+    impl Dialect {
+        fn name(self) -> &'static str {
+            match self {
+                Self::GitHubActions => "github-actions",
+                Self::Forgejo => "forgejo",
+                Self::Gitea => "gitea",
             }
         }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        /// The directory this platform scans for workflow files, relative to the repository root.
+        fn workflows_dir(self) -> &'static str {
+            match self {
+                Self::GitHubActions => ".github/workflows",
+                Self::Forgejo => ".forgejo/workflows",
+                Self::Gitea => ".gitea/workflows",
+            }
         }
-    }
-    impl MaybeYamlable for &WorkflowSecret {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut out = Hash::new();
-            out.insert_yaml_opt("description", &self.description);
-            out.insert_yaml_opt("required", self.required);
-            if out.is_empty() {
-                None
-            } else {
-                Some(Yaml::Hash(out))
+
+        fn unsupported_trigger(self, trigger: &str) -> PyErr {
+            PyValueError::new_err(format!(
+                "The '{trigger}' trigger is not supported by {}; remove it or target \
+                 'github-actions' instead",
+                self.name()
+            ))
+        }
+
+        fn validate_workflow_dispatch(self, event: &WorkflowDispatchEvent) -> PyResult<()> {
+            if self == Self::GitHubActions {
+                return Ok(());
+            }
+            let Some(inputs) = &event.inputs else {
+                return Ok(());
+            };
+            for (id, input) in inputs.iter() {
+                if matches!(input.input_type, WorkflowDispatchInputType::Environment) {
+                    return Err(PyValueError::new_err(format!(
+                        "workflow_dispatch input '{id}' has type 'environment', which is not \
+                         supported by {}; remove it or target 'github-actions' instead",
+                        self.name()
+                    )));
+                }
             }
+            Ok(())
         }
     }
 
+    /// The conventional directory a given CI platform scans for workflow files, e.g.
+    /// `".github/workflows"` for `"github-actions"` or `".forgejo/workflows"` for `"forgejo"`.
+    #[pyfunction]
+    fn workflows_directory(target: &str) -> PyResult<String> {
+        Ok(Dialect::from_str(target)?.workflows_dir().to_string())
+    }
+
     #[pyclass]
     #[derive(Clone)]
-    struct WorkflowCallEvent {
-        inputs: PyMap<String, WorkflowInput>,
-        outputs: PyMap<String, WorkflowOutput>,
-        secrets: PyMap<String, WorkflowSecret>,
+    struct Events {
+        branch_protection_rule: Option<BranchProtectionRuleEvent>,
+        check_run: Option<CheckRunEvent>,
+        check_suite: Option<CheckSuiteEvent>,
+        create: bool,
+        delete: bool,
+        deployment: bool,
+        deployment_status: bool,
+        discussion: Option<DiscussionEvent>,
+        discussion_comment: Option<DiscussionCommentEvent>,
+        fork: bool,
+        gollum: bool,
+        image_version: Option<ImageVersionEvent>,
+        issue_comment: Option<IssueCommentEvent>,
+        issues: Option<IssuesEvent>,
+        label: Option<LabelEvent>,
+        merge_group: Option<MergeGroupEvent>,
+        milestone: Option<MilestoneEvent>,
+        page_build: bool,
+        public: bool,
+        pull_request: Option<PullRequestEvent>,
+        pull_request_review: Option<PullRequestReviewEvent>,
+        pull_request_review_comment: Option<PullRequestReviewCommentEvent>,
+        pull_request_target: Option<PullRequestEvent>,
+        push: Option<PushEvent>,
+        registry_package: Option<RegistryPackageEvent>,
+        release: Option<ReleaseEvent>,
+        schedule: Option<ScheduleEvent>,
+        status: bool,
+        watch: Option<WatchEvent>,
+        workflow_call: Option<WorkflowCallEvent>,
+        workflow_dispatch: Option<WorkflowDispatchEvent>,
+        workflow_run: Option<WorkflowRunEvent>,
     }
     #[pymethods]
-    impl WorkflowCallEvent {
+    impl Events {
+        /// A set of events which may trigger a Workflow.
+        ///
+        /// Parameters
+        /// ----------
+        /// branch_protection_rule
+        ///     Triggers when the branch protection rules for the repository are changed.
+        /// check_run
+        ///     Triggers when activity related to a check run occurs.
+        /// check_suite
+        ///     Triggers when activity related to a check suite occurs.
+        /// create
+        ///     Triggers when someone creates a new branch or tag (but not if more than three tags are made at once).
+        /// delete
+        ///     Triggers when someone deletes a new branch or tag
+        /// deployment
+        ///     Triggers when a deployment is created.
+        /// deployment_status
+        ///     Triggers when a third party service provides a deployment status (unlesss deployment status's state is set to ``inactive``).
+        /// discussion
+        ///     Triggers when a discussion is created or modified.
+        /// discussion_comment
+        ///     Triggers on a comment on a discussion.
+        /// fork
+        ///     Triggers when someone forks a repository.
+        /// gollum
+        ///     Triggers when someone creates/edits a Wiki page.
+        /// image_version
+        ///     Triggers when a new version of a specified image becomes available.
+        /// issue_comment
+        ///     Triggers when an issue or pull request comment is created, edited, or deleted.
+        /// issues
+        ///     Triggers when an issue is created or modified.
+        /// label
+        ///     Triggers when a label is created or modified.
+        /// merge_group
+        ///     Triggers when a pull request is added to a merge queue which adds the pull request
+        ///     to a merge group.
+        /// milestone
+        ///     Triggers when a milestone is created or modified.
+        /// page_build
+        ///     Triggers on pushes to a branch which is the publishing source for GitHub Pages.
+        /// public
+        ///     Triggers when the repository visibility is changed from private to public.
+        /// pull_request
+        ///     Triggers on activity related to a pull request
+        /// pull_request_review
+        ///     Triggers on actions related to a pull request review.
+        /// pull_request_review_comment
+        ///     Triggers when a pull request review comment is modified.
+        /// pull_request_target
+        ///     Triggers when some activity occurs on a pull request. This runs in the context of
+        ///     the default branch of the repository rather than the context of the merge commit
+        ///     (use the ``pull_request`` argument for that).
+        /// push
+        ///     Triggers when a commit or tag is pushed (also when a repository is created from a
+        ///     template).
+        /// registry_package
+        ///     Triggers on activity related to GitHub Packages
+        /// release
+        ///     Triggers on release activity.
+        /// repository_dispatch
+        ///     Triggers when the GitHub API is useed to trigger a webhook event called
+        ///     ``repository_dispatch`` (used to trigger a workflow for activity that happens
+        ///     outside of GitHub).
+        /// schedule
+        ///     Triggers on a fixed time schedule (cronjob).
+        /// status
+        ///     Triggers when the status of a commit changes.
+        /// watch
+        ///     Triggers when the repository is starred.
+        /// workflow_call
+        ///     Triggers when the workflow is called by another workflow.
+        /// workflow_dispatch
+        ///     Allows the workflow to be triggered manually through the GitHub API, CLI, or UI.
+        /// workflow_run
+        ///     Triggers when a workflow run is requested or completed.
+        ///
+        /// Notes
+        /// -----
+        /// See `the documentation on GitHub <https://docs.github.com/en/actions/reference/workflows-and-actions/events-that-trigger-workflows#branch_protection_rule>`_ for more details.
         #[new]
-        #[pyo3(signature = (*, inputs=None, outputs=None, secrets=None))]
+        #[pyo3(signature = (*, branch_protection_rule=None, check_run=None, check_suite=None, create=false, delete=false, deployment=false, deployment_status=false, discussion=None, discussion_comment=None, fork=false, gollum=false, image_version=None, issue_comment=None, issues=None, label=None, merge_group=None, milestone=None, page_build=false, public=false, pull_request=None, pull_request_review=None, pull_request_review_comment=None, pull_request_target=None, push=None, registry_package=None, release=None, schedule=None, status=false, watch=None, workflow_call=None, workflow_dispatch=None, workflow_run=None))]
         fn new(
-            inputs: Option<PyMap<String, WorkflowInput>>,
-            outputs: Option<PyMap<String, WorkflowOutput>>,
-            secrets: Option<PyMap<String, WorkflowSecret>>,
+            branch_protection_rule: Option<BranchProtectionRuleEvent>,
+            check_run: Option<CheckRunEvent>,
+            check_suite: Option<CheckSuiteEvent>,
+            create: bool,
+            delete: bool,
+            deployment: bool,
+            deployment_status: bool,
+            discussion: Option<DiscussionEvent>,
+            discussion_comment: Option<DiscussionCommentEvent>,
+            fork: bool,
+            gollum: bool,
+            image_version: Option<ImageVersionEvent>,
+            issue_comment: Option<IssueCommentEvent>,
+            issues: Option<IssuesEvent>,
+            label: Option<LabelEvent>,
+            merge_group: Option<MergeGroupEvent>,
+            milestone: Option<MilestoneEvent>,
+            page_build: bool,
+            public: bool,
+            pull_request: Option<PullRequestEvent>,
+            pull_request_review: Option<PullRequestReviewEvent>,
+            pull_request_review_comment: Option<PullRequestReviewCommentEvent>,
+            pull_request_target: Option<PullRequestEvent>,
+            push: Option<PushEvent>,
+            registry_package: Option<RegistryPackageEvent>,
+            release: Option<ReleaseEvent>,
+            schedule: Option<ScheduleEvent>,
+            status: bool,
+            watch: Option<WatchEvent>,
+            workflow_call: Option<WorkflowCallEvent>,
+            workflow_dispatch: Option<WorkflowDispatchEvent>,
+            workflow_run: Option<WorkflowRunEvent>,
         ) -> Self {
             Self {
-                inputs: inputs.unwrap_or_default(),
-                outputs: outputs.unwrap_or_default(),
-                secrets: secrets.unwrap_or_default(),
+                branch_protection_rule,
+                check_run,
+                check_suite,
+                create,
+                delete,
+                deployment,
+                deployment_status,
+                discussion,
+                discussion_comment,
+                fork,
+                gollum,
+                image_version,
+                issue_comment,
+                issues,
+                label,
+                merge_group,
+                milestone,
+                page_build,
+                public,
+                pull_request,
+                pull_request_review,
+                pull_request_review_comment,
+                pull_request_target,
+                push,
+                registry_package,
+                release,
+                schedule,
+                status,
+                watch,
+                workflow_call,
+                workflow_dispatch,
+                workflow_run,
             }
         }
 
         fn __str__(&self) -> PyResult<String> {
             self.maybe_as_yaml_string()
         }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            events_from_yaml(&parse_yaml_document(yaml)?)
+        }
+
+        /// Read an existing workflow file (e.g. `.github/workflows/ci.yml`) from disk and parse
+        /// just its top-level `on:` block back into an `Events`, the same way `from_yaml` parses
+        /// one already held in memory.
+        #[staticmethod]
+        fn from_yaml_file(path: &Bound<PyAny>) -> PyResult<Self> {
+            let path = if let Ok(p) = path.extract::<PathBuf>() {
+                p
+            } else if let Ok(s) = path.extract::<String>() {
+                PathBuf::from(s)
+            } else {
+                return Err(PyValueError::new_err("Invalid path"));
+            };
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                PyValueError::new_err(format!("Failed to read '{}': {e}", path.display()))
+            })?;
+            let mut hash = expect_hash(&parse_yaml_document(&contents)?, "workflow")?.clone();
+            let on = hash_take(&mut hash, "on").ok_or_else(|| {
+                PyValueError::new_err("Expected 'workflow' mapping to have an 'on' key")
+            })?;
+            events_from_yaml(&on)
+        }
+
+        /// Check this trigger set against a specific CI platform's capabilities, raising a clear
+        /// error naming the first unsupported trigger (or `workflow_dispatch` input type) instead
+        /// of letting the runner silently ignore it. Lets a single `Events` definition be
+        /// validated for portability before it's emitted for a self-hosted Forgejo/Gitea forge.
+        ///
+        /// Parameters
+        /// ----------
+        /// target
+        ///     One of `"github-actions"`, `"forgejo"`, or `"gitea"`.
+        fn validate_target(&self, target: &str) -> PyResult<()> {
+            let dialect = Dialect::from_str(target)?;
+            if dialect == Dialect::GitHubActions {
+                return Ok(());
+            }
+            if self.branch_protection_rule.is_some() {
+                return Err(dialect.unsupported_trigger("branch_protection_rule"));
+            }
+            if self.image_version.is_some() {
+                return Err(dialect.unsupported_trigger("image_version"));
+            }
+            if self.merge_group.is_some() {
+                return Err(dialect.unsupported_trigger("merge_group"));
+            }
+            if self.registry_package.is_some() {
+                return Err(dialect.unsupported_trigger("registry_package"));
+            }
+            if let Some(workflow_dispatch) = &self.workflow_dispatch {
+                dialect.validate_workflow_dispatch(workflow_dispatch)?;
+            }
+            Ok(())
+        }
     }
-    impl MaybeYamlable for WorkflowCallEvent {
+    impl MaybeYamlable for &Events {
         fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut out = Hash::new();
-            let mut inputs = Hash::new();
-            for (k, v) in self.inputs.iter() {
-                inputs.insert_yaml(k, v);
+            let mut configured = Hash::new();
+            let mut simple_names: Vec<&str> = Vec::new();
+
+            if let Some(event) = &self.branch_protection_rule {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("branch_protection_rule", yaml);
+                } else {
+                    simple_names.push("branch_protection_rule");
+                }
             }
-            if !inputs.is_empty() {
-                out.insert_yaml("inputs", Yaml::Hash(inputs));
+            if let Some(event) = &self.check_run {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("check_run", yaml);
+                } else {
+                    simple_names.push("check_run");
+                }
             }
-            let mut outputs = Hash::new();
-            for (k, v) in self.outputs.iter() {
-                outputs.insert_yaml(k, v);
+            if let Some(event) = &self.check_suite {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("check_suite", yaml);
+                } else {
+                    simple_names.push("check_suite");
+                }
             }
-            if !outputs.is_empty() {
-                out.insert_yaml("outputs", Yaml::Hash(outputs));
+            if let Some(event) = &self.discussion {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("discussion", yaml);
+                } else {
+                    simple_names.push("discussion");
+                }
             }
-            let mut secrets = Hash::new();
-            for (k, v) in self.secrets.iter() {
-                secrets.insert_yaml(k, v.maybe_as_yaml().unwrap_or(Yaml::Null));
+            if let Some(event) = &self.discussion_comment {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("discussion_comment", yaml);
+                } else {
+                    simple_names.push("discussion_comment");
+                }
             }
-            if !secrets.is_empty() {
-                out.insert_yaml("secrets", Yaml::Hash(secrets));
+
+            if let Some(event) = &self.image_version {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("image_version", yaml);
+                } else {
+                    simple_names.push("image_version");
+                }
             }
-            if out.is_empty() {
-                None
-            } else {
-                Some(Yaml::Hash(out))
+            if let Some(event) = &self.issue_comment {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("issue_comment", yaml);
+                } else {
+                    simple_names.push("issue_comment");
+                }
+            }
+            if let Some(event) = &self.issues {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("issues", yaml);
+                } else {
+                    simple_names.push("issues");
+                }
+            }
+            if let Some(event) = &self.label {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("label", yaml);
+                } else {
+                    simple_names.push("label");
+                }
+            }
+            if let Some(event) = &self.merge_group {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("merge_group", yaml);
+                } else {
+                    simple_names.push("merge_group");
+                }
+            }
+            if let Some(event) = &self.milestone {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("milestone", yaml);
+                } else {
+                    simple_names.push("milestone");
+                }
+            }
+            if let Some(event) = &self.pull_request {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("pull_request", yaml);
+                } else {
+                    simple_names.push("pull_request");
+                }
+            }
+            if let Some(event) = &self.pull_request_review {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("pull_request_review", yaml);
+                } else {
+                    simple_names.push("pull_request_review");
+                }
+            }
+            if let Some(event) = &self.pull_request_review_comment {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("pull_request_review_comment", yaml);
+                } else {
+                    simple_names.push("pull_request_review_comment");
+                }
+            }
+            if let Some(event) = &self.pull_request_target {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("pull_request_target", yaml);
+                } else {
+                    simple_names.push("pull_request_target");
+                }
+            }
+            if let Some(event) = &self.push {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("push", yaml);
+                } else {
+                    simple_names.push("push");
+                }
+            }
+            if let Some(event) = &self.registry_package {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("registry_package", yaml);
+                } else {
+                    simple_names.push("registry_package");
+                }
+            }
+            if let Some(event) = &self.release {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("release", yaml);
+                } else {
+                    simple_names.push("release");
+                }
+            }
+            if let Some(event) = &self.schedule {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("schedule", yaml);
+                } else {
+                    simple_names.push("schedule");
+                }
+            }
+            if let Some(event) = &self.watch {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("watch", yaml);
+                } else {
+                    simple_names.push("watch");
+                }
+            }
+            if let Some(event) = &self.workflow_call {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("workflow_call", yaml);
+                } else {
+                    simple_names.push("workflow_call");
+                }
+            }
+            if let Some(event) = &self.workflow_dispatch {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("workflow_dispatch", yaml);
+                } else {
+                    simple_names.push("workflow_dispatch");
+                }
+            }
+            if let Some(event) = &self.workflow_run {
+                if let Some(yaml) = event.maybe_as_yaml() {
+                    configured.insert_yaml("workflow_run", yaml);
+                } else {
+                    simple_names.push("workflow_run");
+                }
             }
-        }
-    }
 
-    #[derive(Clone)]
-    enum WorkflowDispatchInputType {
-        Boolean {
-            default: Option<bool>,
-        },
-        Choice {
-            default: Option<String>,
-            options: Vec<String>,
-        },
-        Number {
-            default: Option<i64>,
-        },
-        Environment,
-        String {
-            default: Option<String>,
-        },
-    }
-    impl WorkflowDispatchInputType {
-        fn get_type(&self) -> Yaml {
-            match self {
-                Self::Boolean { .. } => Yaml::from_str("boolean"),
-                Self::Choice { .. } => Yaml::from_str("choice"),
-                Self::Number { .. } => Yaml::from_str("number"),
-                Self::Environment => Yaml::from_str("environment"),
-                Self::String { .. } => Yaml::from_str("string"),
+            if self.create {
+                simple_names.push("create");
+            }
+            if self.delete {
+                simple_names.push("delete");
+            }
+            if self.deployment {
+                simple_names.push("deployment");
+            }
+            if self.deployment_status {
+                simple_names.push("deployment_status");
+            }
+            if self.fork {
+                simple_names.push("fork");
+            }
+            if self.gollum {
+                simple_names.push("gollum");
+            }
+            if self.page_build {
+                simple_names.push("page_build");
+            }
+            if self.public {
+                simple_names.push("public");
+            }
+            if self.status {
+                simple_names.push("status");
             }
-        }
-        fn get_default(&self) -> Option<Yaml> {
-            match self {
-                Self::Boolean { default } => default.map(Yaml::Boolean),
-                Self::Choice { default, .. } | Self::String { default } => {
-                    default.clone().map(Yaml::String)
+
+            if configured.is_empty() {
+                match simple_names.len() {
+                    0 => None,
+                    1 => Some(simple_names[0].as_yaml()),
+                    _ => {
+                        let mut arr = Array::new();
+                        for name in simple_names {
+                            arr.push_yaml(name);
+                        }
+                        Some(Yaml::Array(arr))
+                    }
                 }
-                Self::Number { default } => default.map(Yaml::Integer),
-                Self::Environment => None, // TODO: check if environment can have a default
-            }
-        }
-        fn get_options(&self) -> Option<Yaml> {
-            if let Self::Choice { options, .. } = self {
-                Some(Yaml::Array(
-                    options.iter().map(|s| Yaml::String(s.clone())).collect(),
-                ))
             } else {
-                None
+                for name in simple_names {
+                    configured.insert_yaml(name, Yaml::Null);
+                }
+                Some(Yaml::Hash(configured))
             }
         }
     }
-
-    #[pyclass]
-    #[derive(Clone)]
-    struct WorkflowDispatchInput {
-        description: Option<String>,
-        input_type: WorkflowDispatchInputType,
-        required: Option<bool>,
-    }
-    #[pymethods]
-    impl WorkflowDispatchInput {
-        #[staticmethod]
-        #[pyo3(signature = (*, description=None, default=None, required=None))]
-        fn boolean(
-            description: Option<String>,
-            default: Option<bool>,
-            required: Option<bool>,
-        ) -> Self {
-            Self {
-                description,
-                input_type: WorkflowDispatchInputType::Boolean { default },
-                required,
-            }
-        }
-        #[staticmethod]
-        #[pyo3(signature = (options, *, description=None, default=None, required=None))]
-        fn choice(
-            options: Vec<String>,
-            description: Option<String>,
-            default: Option<String>,
-            required: Option<bool>,
-        ) -> Self {
-            Self {
-                description,
-                input_type: WorkflowDispatchInputType::Choice { default, options },
-                required,
-            }
-        }
-        #[staticmethod]
-        #[pyo3(signature = (*, description=None, default=None, required=None))]
-        fn number(
-            description: Option<String>,
-            default: Option<i64>,
-            required: Option<bool>,
-        ) -> Self {
-            Self {
-                description,
-                input_type: WorkflowDispatchInputType::Number { default },
-                required,
+    /// Parse a workflow's `on:` block, the inverse of `Events`'s `MaybeYamlable` impl: GitHub
+    /// accepts this node as a bare scalar (`on: push`), a list of scalars (`on: [push,
+    /// pull_request]`), or a mapping from trigger name to either `null`/omitted (defaults) or a
+    /// trigger-specific configuration hash, so this normalizes all three shapes into one hash of
+    /// trigger name to configuration node before dispatching to each trigger's own parser.
+    fn events_from_yaml(yaml: &Yaml) -> PyResult<Events> {
+        let mut hash = match yaml {
+            Yaml::String(name) => {
+                let mut hash = Hash::new();
+                hash.insert(Yaml::String(name.clone()), Yaml::Null);
+                hash
+            }
+            Yaml::Array(names) => {
+                let mut hash = Hash::new();
+                for name in names {
+                    hash.insert(
+                        Yaml::String(yaml_scalar_to_string(name, "on")?),
+                        Yaml::Null,
+                    );
+                }
+                hash
             }
-        }
-        #[staticmethod]
-        #[pyo3(signature = (*, description=None, required=None))]
-        fn environment(description: Option<String>, required: Option<bool>) -> Self {
-            Self {
-                description,
-                input_type: WorkflowDispatchInputType::Environment,
-                required,
+            Yaml::Hash(_) => expect_hash(yaml, "on")?.clone(),
+            Yaml::BadValue | Yaml::Null => Hash::new(),
+            _ => {
+                return Err(PyValueError::new_err(
+                    "Expected 'on' to be a scalar, a list, or a mapping",
+                ));
             }
-        }
-        #[staticmethod]
-        #[pyo3(signature = (*, description=None, default=None, required=None))]
-        fn string(
-            description: Option<String>,
-            default: Option<String>,
-            required: Option<bool>,
-        ) -> Self {
-            Self {
-                description,
-                input_type: WorkflowDispatchInputType::String { default },
-                required,
+        };
+
+        let branch_protection_rule = hash_take(&mut hash, "branch_protection_rule")
+            .map(|y| {
+                branch_protection_rule_event_from_hash(expect_hash_or_empty(
+                    &y,
+                    "branch_protection_rule",
+                )?)
+            })
+            .transpose()?;
+        let check_run = hash_take(&mut hash, "check_run")
+            .map(|y| check_run_event_from_hash(expect_hash_or_empty(&y, "check_run")?))
+            .transpose()?;
+        let check_suite = hash_take(&mut hash, "check_suite")
+            .map(|y| check_suite_event_from_hash(expect_hash_or_empty(&y, "check_suite")?))
+            .transpose()?;
+        let discussion = hash_take(&mut hash, "discussion")
+            .map(|y| discussion_event_from_hash(expect_hash_or_empty(&y, "discussion")?))
+            .transpose()?;
+        let discussion_comment = hash_take(&mut hash, "discussion_comment")
+            .map(|y| {
+                discussion_comment_event_from_hash(expect_hash_or_empty(
+                    &y,
+                    "discussion_comment",
+                )?)
+            })
+            .transpose()?;
+        let image_version = hash_take(&mut hash, "image_version")
+            .map(|y| image_version_event_from_hash(expect_hash_or_empty(&y, "image_version")?))
+            .transpose()?;
+        let issue_comment = hash_take(&mut hash, "issue_comment")
+            .map(|y| issue_comment_event_from_hash(expect_hash_or_empty(&y, "issue_comment")?))
+            .transpose()?;
+        let issues = hash_take(&mut hash, "issues")
+            .map(|y| issues_event_from_hash(expect_hash_or_empty(&y, "issues")?))
+            .transpose()?;
+        let label = hash_take(&mut hash, "label")
+            .map(|y| label_event_from_hash(expect_hash_or_empty(&y, "label")?))
+            .transpose()?;
+        let merge_group = hash_take(&mut hash, "merge_group")
+            .map(|y| merge_group_event_from_hash(expect_hash_or_empty(&y, "merge_group")?))
+            .transpose()?;
+        let milestone = hash_take(&mut hash, "milestone")
+            .map(|y| milestone_event_from_hash(expect_hash_or_empty(&y, "milestone")?))
+            .transpose()?;
+        let pull_request = hash_take(&mut hash, "pull_request")
+            .map(|y| {
+                pull_request_event_from_hash(
+                    expect_hash_or_empty(&y, "pull_request")?,
+                    "pull_request",
+                )
+            })
+            .transpose()?;
+        let pull_request_review = hash_take(&mut hash, "pull_request_review")
+            .map(|y| {
+                pull_request_review_event_from_hash(expect_hash_or_empty(
+                    &y,
+                    "pull_request_review",
+                )?)
+            })
+            .transpose()?;
+        let pull_request_review_comment = hash_take(&mut hash, "pull_request_review_comment")
+            .map(|y| {
+                pull_request_review_comment_event_from_hash(expect_hash_or_empty(
+                    &y,
+                    "pull_request_review_comment",
+                )?)
+            })
+            .transpose()?;
+        let pull_request_target = hash_take(&mut hash, "pull_request_target")
+            .map(|y| {
+                pull_request_event_from_hash(
+                    expect_hash_or_empty(&y, "pull_request_target")?,
+                    "pull_request_target",
+                )
+            })
+            .transpose()?;
+        let push = hash_take(&mut hash, "push")
+            .map(|y| push_event_from_hash(expect_hash_or_empty(&y, "push")?))
+            .transpose()?;
+        let registry_package = hash_take(&mut hash, "registry_package")
+            .map(|y| registry_package_event_from_hash(expect_hash_or_empty(&y, "registry_package")?))
+            .transpose()?;
+        let release = hash_take(&mut hash, "release")
+            .map(|y| release_event_from_hash(expect_hash_or_empty(&y, "release")?))
+            .transpose()?;
+        let schedule = hash_take(&mut hash, "schedule")
+            .map(|y| schedule_event_from_yaml(&y))
+            .transpose()?;
+        let watch = hash_take(&mut hash, "watch")
+            .map(|y| watch_event_from_hash(expect_hash_or_empty(&y, "watch")?))
+            .transpose()?;
+        let workflow_call = hash_take(&mut hash, "workflow_call")
+            .map(|y| workflow_call_event_from_hash(expect_hash_or_empty(&y, "workflow_call")?))
+            .transpose()?;
+        let workflow_dispatch = hash_take(&mut hash, "workflow_dispatch")
+            .map(|y| {
+                workflow_dispatch_event_from_hash(expect_hash_or_empty(&y, "workflow_dispatch")?)
+            })
+            .transpose()?;
+        let workflow_run = hash_take(&mut hash, "workflow_run")
+            .map(|y| workflow_run_event_from_hash(expect_hash_or_empty(&y, "workflow_run")?))
+            .transpose()?;
+
+        let create = hash_take(&mut hash, "create").is_some();
+        let delete = hash_take(&mut hash, "delete").is_some();
+        let deployment = hash_take(&mut hash, "deployment").is_some();
+        let deployment_status = hash_take(&mut hash, "deployment_status").is_some();
+        let fork = hash_take(&mut hash, "fork").is_some();
+        let gollum = hash_take(&mut hash, "gollum").is_some();
+        let page_build = hash_take(&mut hash, "page_build").is_some();
+        let public = hash_take(&mut hash, "public").is_some();
+        let status = hash_take(&mut hash, "status").is_some();
+
+        reject_unknown_keys(&hash, "on")?;
+        Ok(Events {
+            branch_protection_rule,
+            check_run,
+            check_suite,
+            create,
+            delete,
+            deployment,
+            deployment_status,
+            discussion,
+            discussion_comment,
+            fork,
+            gollum,
+            image_version,
+            issue_comment,
+            issues,
+            label,
+            merge_group,
+            milestone,
+            page_build,
+            public,
+            pull_request,
+            pull_request_review,
+            pull_request_review_comment,
+            pull_request_target,
+            push,
+            registry_package,
+            release,
+            schedule,
+            status,
+            watch,
+            workflow_call,
+            workflow_dispatch,
+            workflow_run,
+        })
+    }
+
+    /// Validate the `needs` graph of a job collection and return it as an ordered execution
+    /// layering (jobs grouped by dependency depth), via Kahn's algorithm: seed a queue with every
+    /// job that has no unresolved dependency, repeatedly drain a layer and decrement the
+    /// remaining dependency count of its dependents, and raise if any job is left over once the
+    /// queue runs dry, naming either the nonexistent dependency or the cycle members.
+    fn resolve_job_dependencies(jobs: &PyMap<String, Job>) -> PyResult<Vec<Vec<String>>> {
+        let names: Vec<&String> = jobs.iter().map(|(name, _)| name).collect();
+        let index_of: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect();
+
+        let mut remaining = vec![0usize; names.len()];
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); names.len()];
+        for (i, (name, job)) in jobs.iter().enumerate() {
+            let Some(needs) = &job.needs else { continue };
+            remaining[i] = needs.len();
+            for dep in needs {
+                let Some(&dep_index) = index_of.get(dep.as_str()) else {
+                    return Err(PyValueError::new_err(format!(
+                        "Job '{name}' needs nonexistent job '{dep}'"
+                    )));
+                };
+                dependents[dep_index].push(i);
+            }
+        }
+
+        let mut layers = Vec::new();
+        let mut current: Vec<usize> = (0..names.len()).filter(|&i| remaining[i] == 0).collect();
+        let mut emitted = 0;
+        while !current.is_empty() {
+            emitted += current.len();
+            layers.push(current.iter().map(|&i| names[i].clone()).collect());
+            let mut next = Vec::new();
+            for &i in &current {
+                for &dependent in &dependents[i] {
+                    remaining[dependent] -= 1;
+                    if remaining[dependent] == 0 {
+                        next.push(dependent);
+                    }
+                }
             }
+            current = next;
         }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.as_yaml_string()
+        if emitted < names.len() {
+            let cycle: Vec<&str> = (0..names.len())
+                .filter(|&i| remaining[i] > 0)
+                .map(|i| names[i].as_str())
+                .collect();
+            return Err(PyValueError::new_err(format!(
+                "Workflow jobs contain a 'needs' dependency cycle among: {}",
+                cycle.join(", ")
+            )));
         }
+
+        Ok(layers)
     }
-    impl Yamlable for &WorkflowDispatchInput {
-        fn as_yaml(&self) -> Yaml {
-            let mut out = Hash::new();
-            out.insert_yaml_opt("description", &self.description);
-            out.insert_yaml("type", self.input_type.get_type());
-            out.insert_yaml_opt("required", self.required);
-            out.insert_yaml_opt("default", self.input_type.get_default());
-            out.insert_yaml_opt("options", self.input_type.get_options());
-            Yaml::Hash(out)
+
+    /// Collect the structural key (see `yaml_structural_key`) of every job's steps that were
+    /// assigned from a `StepsAnchor`, mapped to the anchor's declared name, in `jobs` iteration
+    /// order. Used by `Yamlable for &Workflow`'s `as_yaml_string` to fold repeated steps lists
+    /// onto real YAML anchor/alias syntax instead of repeating them.
+    fn named_step_anchors(jobs: &PyMap<String, Job>) -> HashMap<String, String> {
+        let mut named = HashMap::new();
+        for (_, job) in jobs.iter() {
+            if let (Some(name), Some(steps)) = (&job.steps_anchor, &job.steps) {
+                named
+                    .entry(yaml_structural_key(&steps.as_yaml()))
+                    .or_insert_with(|| name.clone());
+            }
         }
+        named
     }
 
+    /// One schema violation surfaced by `Workflow.validate`/`Action.validate`, reported instead of
+    /// stopping at the first failure so callers can see every problem in a large generated
+    /// document at once and map each back to the offending job/step.
     #[pyclass]
     #[derive(Clone)]
-    struct WorkflowDispatchEvent {
-        inputs: Option<PyMap<String, WorkflowDispatchInput>>,
+    struct ValidationIssue {
+        instance_path: String,
+        schema_path: String,
+        message: String,
     }
     #[pymethods]
-    impl WorkflowDispatchEvent {
-        #[new]
-        #[pyo3(signature = (*, inputs=None))]
-        fn new(inputs: Option<PyMap<String, WorkflowDispatchInput>>) -> Self {
-            Self {
-                inputs: inputs.filter(|i| !i.is_empty()),
-            }
+    impl ValidationIssue {
+        /// JSON Pointer (e.g. `/jobs/build/steps/2/uses`) to the value that failed validation.
+        fn instance_path(&self) -> &str {
+            &self.instance_path
         }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        /// JSON Pointer to the schema rule this value violated.
+        fn schema_path(&self) -> &str {
+            &self.schema_path
+        }
+
+        /// The validator's human-readable description of the violation.
+        fn message(&self) -> &str {
+            &self.message
+        }
+
+        fn __str__(&self) -> String {
+            format!("{}: {}", self.instance_path, self.message)
+        }
+
+        fn __repr__(&self) -> String {
+            format!(
+                "ValidationIssue(instance_path={:?}, schema_path={:?}, message={:?})",
+                self.instance_path, self.schema_path, self.message
+            )
         }
     }
-    impl MaybeYamlable for &WorkflowDispatchEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            if let Some(inputs) = &self.inputs {
-                let mut out = Hash::new();
-                for (k, v) in inputs.iter() {
-                    out.insert_yaml(k, v);
-                }
-                Some(Yaml::Hash(out))
-            } else {
-                None
-            }
+
+    /// Raise a `ValidationError` carrying every issue in `issues` as a `ValidationIssue`, or do
+    /// nothing if `issues` is empty.
+    fn raise_validation_issues(issues: Vec<(String, String, String)>, py: Python<'_>) -> PyResult<()> {
+        if issues.is_empty() {
+            return Ok(());
         }
+        let summary = issues
+            .iter()
+            .map(|(instance_path, _, message)| format!("{instance_path}: {message}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let issues = issues
+            .into_iter()
+            .map(|(instance_path, schema_path, message)| {
+                Py::new(
+                    py,
+                    ValidationIssue {
+                        instance_path,
+                        schema_path,
+                        message,
+                    },
+                )
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Err(ValidationError::new_err((summary, issues)))
+    }
+
+    /// Run `schema` against `json`, collecting every violation instead of stopping at the first,
+    /// and raise a `ValidationError` carrying the full `ValidationIssue` list if any are found.
+    fn validate_against_schema(
+        schema: &Validator,
+        json: &serde_json::Value,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        raise_validation_issues(collect_schema_issues(schema, json), py)
     }
 
     #[pyclass]
-    #[derive(Clone)]
-    struct WorkflowRunEvent {
-        workflows: Option<Vec<String>>,
-        completed: bool,
-        requested: bool,
-        in_progress: bool,
-        branches: Option<Vec<String>>,
-        branches_ignore: Option<Vec<String>>,
+    struct Workflow {
+        name: Option<String>,
+        run_name: Option<StringLike>,
+        on: Events,
+        permissions: Option<Permissions>,
+        env: Option<PyMap<String, StringLike>>,
+        defaults: Option<Defaults>,
+        concurrency: Option<Concurrency>,
+        jobs: PyMap<String, Job>,
     }
     #[pymethods]
-    impl WorkflowRunEvent {
+    impl Workflow {
+        /// A configurable automated process made up of one or more jobs.
+        ///
+        /// Workflows are the primary entrypoint for ``yamloom``. Typical actions include constructing
+        /// workflows and then writing them to a file with ``Workflow.dump('path/to/file.yml')``.
+        ///
+        /// Parameters
+        /// ----------
+        /// jobs
+        ///     Jobs to run (in parallel by default).
+        /// on
+        ///     Events which may trigger the workflow.
+        /// name
+        ///     The name of the workflow.
+        /// run_name
+        ///     The name given to a particular run of the workflow.
+        /// permissions
+        ///     The default permissions granted to the ``GITHUB_TOKEN``.
+        /// env
+        ///     Global environment variables available at any step of any job in the workflow.
+        /// defaults
+        ///     Default settings which are applied to all jobs.
+        /// concurrency
+        ///     Settings to ensure only a single workflow of the given concurrency group runs at a time.
+        ///
+        /// Returns
+        /// -------
+        /// Workflow
+        ///
         #[new]
-        #[pyo3(signature = (*, workflows=None, completed=false, requested=false, in_progress=false, branches=None, branches_ignore=None))]
+        #[pyo3(signature = (*, jobs, on, name = None, run_name = None, permissions = None, env = None, defaults = None, concurrency = None))]
         fn new(
-            workflows: Option<Vec<String>>,
-            completed: bool,
-            requested: bool,
-            in_progress: bool,
-            branches: Option<Vec<String>>,
-            branches_ignore: Option<Vec<String>>,
-        ) -> Self {
-            let workflows = workflows.filter(|w| !w.is_empty());
-            let branches = branches.filter(|b| !b.is_empty());
-            let branches_ignore = branches_ignore.filter(|b| !b.is_empty());
-            Self {
-                workflows,
-                completed,
-                requested,
-                in_progress,
-                branches,
-                branches_ignore,
+            jobs: PyMap<String, Job>,
+            on: Events,
+            name: Option<String>,
+            run_name: Option<StringLike>,
+            permissions: Option<Permissions>,
+            env: Option<PyMap<String, StringLike>>,
+            defaults: Option<Defaults>,
+            concurrency: Option<Concurrency>,
+        ) -> PyResult<Self> {
+            if let Some(run_name) = &run_name {
+                validate_string_like(run_name, ALLOWED_WORKFLOW_RUN_NAME)?;
+            }
+            if let Some(env) = &env {
+                validate_string_map(env, ALLOWED_WORKFLOW_ENV)?;
             }
+            if let Some(concurrency) = &concurrency {
+                validate_concurrency(concurrency, ALLOWED_WORKFLOW_CONCURRENCY)?;
+            }
+            resolve_job_dependencies(&jobs)?;
+            Ok(Self {
+                name,
+                run_name,
+                on,
+                permissions,
+                env,
+                defaults,
+                concurrency,
+                jobs,
+            })
         }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        /// Declare a named, reusable fragment of a job's `steps:` list. Assign the returned
+        /// `StepsAnchor` to more than one `Job`'s ``steps`` argument to de-duplicate them in the
+        /// emitted YAML: the first job (in `jobs` iteration order) that uses it renders its steps
+        /// tagged ``&name``, and every later job sharing it renders a bare ``*name`` alias instead
+        /// of repeating the steps. `Workflow.validate`/`is_valid` are unaffected, since every job
+        /// still stores its own fully resolved steps regardless of how the rendered YAML folds
+        /// them.
+        fn anchor(&self, name: String, steps: Vec<Step>) -> StepsAnchor {
+            StepsAnchor { name, steps }
         }
-    }
-    impl MaybeYamlable for &WorkflowRunEvent {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut out = Hash::new();
-            out.insert_yaml_opt("workflows", &self.workflows);
-            if self.completed || self.requested || self.in_progress {
-                let mut types = Array::new();
-                types.push_yaml_cond("completed", self.completed);
-                types.push_yaml_cond("requested", self.requested);
-                types.push_yaml_cond("in_progress", self.in_progress);
-                out.insert_yaml("types", Yaml::Array(types));
-            }
-            out.insert_yaml_opt("branches", &self.branches);
-            out.insert_yaml_opt("branches-ignore", &self.branches_ignore);
-            if out.is_empty() {
-                None
-            } else {
-                Some(Yaml::Hash(out))
-            }
+
+        /// Run validation against the schemastore JSON schema for GitHub Workflows, raising a
+        /// `ValidationError` listing every violation (not just the first) if any are found.
+        fn validate(&self, py: Python<'_>) -> PyResult<()> {
+            let workflow_yaml = self.as_yaml();
+            let workflow_json = yaml_to_json(&workflow_yaml)?;
+            validate_against_schema(&WORKFLOW_SCHEMA, &workflow_json, py)
         }
-    }
 
-    #[pyclass]
-    #[derive(Clone)]
-    struct Events {
-        branch_protection_rule: Option<BranchProtectionRuleEvent>,
-        check_run: Option<CheckRunEvent>,
-        check_suite: Option<CheckSuiteEvent>,
-        create: bool,
-        delete: bool,
-        deployment: bool,
-        deployment_status: bool,
-        discussion: Option<DiscussionEvent>,
-        discussion_comment: Option<DiscussionCommentEvent>,
-        fork: bool,
-        gollum: bool,
-        image_version: Option<ImageVersionEvent>,
-        issue_comment: Option<IssueCommentEvent>,
-        issues: Option<IssuesEvent>,
-        label: Option<LabelEvent>,
-        merge_group: Option<MergeGroupEvent>,
-        milestone: Option<MilestoneEvent>,
-        page_build: bool,
-        public: bool,
-        pull_request: Option<PullRequestEvent>,
-        pull_request_review: Option<PullRequestReviewEvent>,
-        pull_request_review_comment: Option<PullRequestReviewCommentEvent>,
-        pull_request_target: Option<PullRequestEvent>,
-        push: Option<PushEvent>,
-        registry_package: Option<RegistryPackageEvent>,
-        release: Option<ReleaseEvent>,
-        schedule: Option<ScheduleEvent>,
-        status: bool,
-        watch: Option<WatchEvent>,
-        workflow_call: Option<WorkflowCallEvent>,
-        workflow_dispatch: Option<WorkflowDispatchEvent>,
-        workflow_run: Option<WorkflowRunEvent>,
-    }
-    #[pymethods]
-    impl Events {
-        /// A set of events which may trigger a Workflow.
+        /// Check if the workflow is valid YAML according to the schemastore JSON schema for GitHub
+        /// Workflows.
+        fn is_valid(&self, py: Python<'_>) -> bool {
+            self.validate(py).is_ok()
+        }
+
+        /// The jobs of this workflow grouped into an ordered execution layering by `needs`
+        /// dependency depth: layer 0 has no dependencies, layer 1 depends only on jobs in layer
+        /// 0, and so on. This is the same resolution pass `__new__` already runs to reject a
+        /// missing dependency or a `needs` cycle, exposed here for inspection.
+        fn job_layers(&self) -> PyResult<Vec<Vec<String>>> {
+            resolve_job_dependencies(&self.jobs)
+        }
+
+        /// Evaluate every `${{ ... }}` expression in this workflow's rendered YAML against a
+        /// concrete `context` dict (the same shape `evaluate.evaluate` takes, keyed by root like
+        /// `github`, `inputs`, `matrix`, `env`, `needs`, ...), returning the fully resolved YAML
+        /// as a string instead of the templated form. Useful for asserting on the materialized
+        /// output for a specific event payload in tests.
+        fn render_with_context(&self, context: &Bound<'_, PyDict>) -> PyResult<String> {
+            render_yaml_with_context(&self.as_yaml(), context)?.as_yaml_string()
+        }
+
+        /// Write the YAML representation of the workflow to a file.
         ///
         /// Parameters
         /// ----------
-        /// branch_protection_rule
-        ///     Triggers when the branch protection rules for the repository are changed.
-        /// check_run
-        ///     Triggers when activity related to a check run occurs.
-        /// check_suite
-        ///     Triggers when activity related to a check suite occurs.
-        /// create
-        ///     Triggers when someone creates a new branch or tag (but not if more than three tags are made at once).
-        /// delete
-        ///     Triggers when someone deletes a new branch or tag
-        /// deployment
-        ///     Triggers when a deployment is created.
-        /// deployment_status
-        ///     Triggers when a third party service provides a deployment status (unlesss deployment status's state is set to ``inactive``).
-        /// discussion
-        ///     Triggers when a discussion is created or modified.
-        /// discussion_comment
-        ///     Triggers on a comment on a discussion.
-        /// fork
-        ///     Triggers when someone forks a repository.
-        /// gollum
-        ///     Triggers when someone creates/edits a Wiki page.
-        /// image_version
-        ///     Triggers when a new version of a specified image becomes available.
-        /// issue_comment
-        ///     Triggers when an issue or pull request comment is created, edited, or deleted.
-        /// issues
-        ///     Triggers when an issue is created or modified.
-        /// label
-        ///     Triggers when a label is created or modified.
-        /// merge_group
-        ///     Triggers when a pull request is added to a merge queue which adds the pull request
-        ///     to a merge group.
-        /// milestone
-        ///     Triggers when a milestone is created or modified.
-        /// page_build
-        ///     Triggers on pushes to a branch which is the publishing source for GitHub Pages.
-        /// public
-        ///     Triggers when the repository visibility is changed from private to public.
-        /// pull_request
-        ///     Triggers on activity related to a pull request
-        /// pull_request_review
-        ///     Triggers on actions related to a pull request review.
-        /// pull_request_review_comment
-        ///     Triggers when a pull request review comment is modified.
-        /// pull_request_target
-        ///     Triggers when some activity occurs on a pull request. This runs in the context of
-        ///     the default branch of the repository rather than the context of the merge commit
-        ///     (use the ``pull_request`` argument for that).
-        /// push
-        ///     Triggers when a commit or tag is pushed (also when a repository is created from a
-        ///     template).
-        /// registry_package
-        ///     Triggers on activity related to GitHub Packages
-        /// release
-        ///     Triggers on release activity.
-        /// repository_dispatch
-        ///     Triggers when the GitHub API is useed to trigger a webhook event called
-        ///     ``repository_dispatch`` (used to trigger a workflow for activity that happens
-        ///     outside of GitHub).
-        /// schedule
-        ///     Triggers on a fixed time schedule (cronjob).
-        /// status
-        ///     Triggers when the status of a commit changes.
-        /// watch
-        ///     Triggers when the repository is starred.
-        /// workflow_call
-        ///     Triggers when the workflow is called by another workflow.
-        /// workflow_dispatch
-        ///     Allows the workflow to be triggered manually through the GitHub API, CLI, or UI.
-        /// workflow_run
-        ///     Triggers when a workflow run is requested or completed.
+        /// path
+        ///     The path of the file to which the YAML is written.
+        /// overwrite
+        ///     If True, the file is overwritten if it already exists, otherwise nothing will happen.
+        /// validate
+        ///     If True, perform validation against the schemastore JSON schema for GitHub
+        ///     Workflows.
+        /// mode
+        ///     Either ``"generate"`` (the default) to write the file, or ``"check"`` to instead
+        ///     verify that the file on disk already matches the generated output, raising a
+        ///     RuntimeError naming the stale file otherwise. Useful as a CI step that fails when a
+        ///     committed workflow no longer matches its Python source of truth.
+        /// header
+        ///     An optional comment block prepended to the emitted YAML, e.g. a
+        ///     "automatically generated, DO NOT EDIT" banner, so committed files advertise that
+        ///     they must be regenerated rather than hand-edited.
         ///
-        /// Notes
-        /// -----
-        /// See `the documentation on GitHub <https://docs.github.com/en/actions/reference/workflows-and-actions/events-that-trigger-workflows#branch_protection_rule>`_ for more details.
-        #[new]
-        #[pyo3(signature = (*, branch_protection_rule=None, check_run=None, check_suite=None, create=false, delete=false, deployment=false, deployment_status=false, discussion=None, discussion_comment=None, fork=false, gollum=false, image_version=None, issue_comment=None, issues=None, label=None, merge_group=None, milestone=None, page_build=false, public=false, pull_request=None, pull_request_review=None, pull_request_review_comment=None, pull_request_target=None, push=None, registry_package=None, release=None, schedule=None, status=false, watch=None, workflow_call=None, workflow_dispatch=None, workflow_run=None))]
-        fn new(
-            branch_protection_rule: Option<BranchProtectionRuleEvent>,
-            check_run: Option<CheckRunEvent>,
-            check_suite: Option<CheckSuiteEvent>,
-            create: bool,
-            delete: bool,
-            deployment: bool,
-            deployment_status: bool,
-            discussion: Option<DiscussionEvent>,
-            discussion_comment: Option<DiscussionCommentEvent>,
-            fork: bool,
-            gollum: bool,
-            image_version: Option<ImageVersionEvent>,
-            issue_comment: Option<IssueCommentEvent>,
-            issues: Option<IssuesEvent>,
-            label: Option<LabelEvent>,
-            merge_group: Option<MergeGroupEvent>,
-            milestone: Option<MilestoneEvent>,
-            page_build: bool,
-            public: bool,
-            pull_request: Option<PullRequestEvent>,
-            pull_request_review: Option<PullRequestReviewEvent>,
-            pull_request_review_comment: Option<PullRequestReviewCommentEvent>,
-            pull_request_target: Option<PullRequestEvent>,
-            push: Option<PushEvent>,
-            registry_package: Option<RegistryPackageEvent>,
-            release: Option<ReleaseEvent>,
-            schedule: Option<ScheduleEvent>,
-            status: bool,
-            watch: Option<WatchEvent>,
-            workflow_call: Option<WorkflowCallEvent>,
-            workflow_dispatch: Option<WorkflowDispatchEvent>,
-            workflow_run: Option<WorkflowRunEvent>,
-        ) -> Self {
-            Self {
-                branch_protection_rule,
-                check_run,
-                check_suite,
-                create,
-                delete,
-                deployment,
-                deployment_status,
-                discussion,
-                discussion_comment,
-                fork,
-                gollum,
-                image_version,
-                issue_comment,
-                issues,
-                label,
-                merge_group,
-                milestone,
-                page_build,
-                public,
-                pull_request,
-                pull_request_review,
-                pull_request_review_comment,
-                pull_request_target,
-                push,
-                registry_package,
-                release,
-                schedule,
-                status,
-                watch,
-                workflow_call,
-                workflow_dispatch,
-                workflow_run,
+        #[pyo3(signature = (path, *, overwrite = true, validate = true, mode = None, header = None))]
+        fn dump(
+            &self,
+            py: Python<'_>,
+            path: &Bound<PyAny>,
+            overwrite: bool,
+            validate: bool,
+            mode: Option<String>,
+            header: Option<String>,
+        ) -> PyResult<()> {
+            if validate {
+                self.validate(py)?;
+            }
+            let mode = mode
+                .map(|m| m.parse())
+                .transpose()?
+                .unwrap_or(WriteMode::Generate);
+            if let Ok(p) = path.extract::<PathBuf>() {
+                self.write_to_file_with_mode(p, overwrite, mode, header.as_deref())
+            } else if let Ok(s) = path.extract::<String>() {
+                self.write_to_file_with_mode(s, overwrite, mode, header.as_deref())
+            } else {
+                Err(PyValueError::new_err("Invalid path"))
+            }
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        /// Parse an existing workflow YAML file back into a `Workflow`. Every top-level key is
+        /// reconstructed, including ``on`` (the bare scalar, array, and configured-mapping
+        /// shorthand forms, via `Events.from_yaml`). ``jobs`` is parsed entry-by-entry via
+        /// `Job::from_yaml`'s underlying parser.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            let mut hash = expect_hash(&parse_yaml_document(yaml)?, "workflow")?.clone();
+            let name = hash_take(&mut hash, "name")
+                .map(|y| yaml_scalar_to_string(&y, "name"))
+                .transpose()?;
+            let run_name = hash_take(&mut hash, "run-name")
+                .map(|y| parse_string_like(&y, "run-name"))
+                .transpose()?;
+            let on = hash_take(&mut hash, "on")
+                .ok_or_else(|| PyValueError::new_err("Expected 'workflow' mapping to have an 'on' key"))
+                .and_then(|y| events_from_yaml(&y))?;
+            let permissions = hash_take(&mut hash, "permissions")
+                .map(|y| permissions_from_yaml(&y))
+                .transpose()?;
+            let env = hash_take(&mut hash, "env")
+                .map(|y| parse_string_map(&y, "env"))
+                .transpose()?;
+            let defaults = hash_take(&mut hash, "defaults")
+                .map(|y| defaults_from_hash(expect_hash(&y, "defaults")?.clone()))
+                .transpose()?;
+            let concurrency = hash_take(&mut hash, "concurrency")
+                .map(|y| concurrency_from_yaml(&y))
+                .transpose()?;
+            let jobs = hash_take(&mut hash, "jobs")
+                .ok_or_else(|| {
+                    PyValueError::new_err("Expected 'workflow' mapping to have a 'jobs' key")
+                })?;
+            let jobs = expect_hash(&jobs, "jobs")?
+                .iter()
+                .map(|(k, v)| {
+                    Ok((
+                        yaml_scalar_to_string(k, "jobs")?,
+                        job_from_hash(expect_hash(v, "jobs")?.clone())?,
+                    ))
+                })
+                .collect::<PyResult<Vec<_>>>()?
+                .into_iter()
+                .collect();
+            reject_unknown_keys(&hash, "workflow")?;
+            resolve_job_dependencies(&jobs)?;
+            Ok(Self {
+                name,
+                run_name,
+                on,
+                permissions,
+                env,
+                defaults,
+                concurrency,
+                jobs,
+            })
+        }
+
+        /// Parse a workflow YAML document already held in memory back into a `Workflow`. An
+        /// alias for `from_yaml`, matching the `load`/`loads` naming convention other YAML
+        /// libraries use.
+        #[staticmethod]
+        fn loads(yaml: &str) -> PyResult<Self> {
+            Self::from_yaml(yaml)
+        }
+
+        /// Read an existing workflow file (e.g. `.github/workflows/ci.yml`) from disk and parse
+        /// it back into a `Workflow`, the same way `from_yaml`/`loads` parses one already held in
+        /// memory.
+        #[staticmethod]
+        fn load(path: &Bound<PyAny>) -> PyResult<Self> {
+            let path = if let Ok(p) = path.extract::<PathBuf>() {
+                p
+            } else if let Ok(s) = path.extract::<String>() {
+                PathBuf::from(s)
+            } else {
+                return Err(PyValueError::new_err("Invalid path"));
+            };
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                PyValueError::new_err(format!("Failed to read '{}': {e}", path.display()))
+            })?;
+            Self::from_yaml(&contents)
+        }
+    }
+    impl Yamlable for &Workflow {
+        fn as_yaml(&self) -> Yaml {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("name", &self.name);
+            out.insert_yaml_opt("run-name", &self.run_name);
+            out.insert_yaml_opt("on", (&self.on).maybe_as_yaml());
+            out.insert_yaml_opt("permissions", &self.permissions);
+            out.insert_yaml_opt("env", &self.env);
+            if let Some(defaults) = &self.defaults {
+                out.insert_yaml_opt("defaults", defaults.maybe_as_yaml());
             }
+            out.insert_yaml_opt("concurrency", &self.concurrency);
+            out.insert_yaml("jobs", &self.jobs);
+            Yaml::Hash(out)
         }
 
-        fn __str__(&self) -> PyResult<String> {
-            self.maybe_as_yaml_string()
+        /// Like the default `as_yaml_string`, but any job whose steps were assigned from a
+        /// `StepsAnchor` (see `Workflow.anchor`) folds onto real YAML anchor/alias syntax instead
+        /// of repeating them. `as_yaml` (and therefore `validate`/`is_valid`) always sees the
+        /// fully-expanded steps regardless, since the folding only happens in the rendered text.
+        fn as_yaml_string(&self) -> PyResult<String> {
+            let named = named_step_anchors(&self.jobs);
+            if named.is_empty() {
+                render_yaml_document(&self.as_yaml())
+            } else {
+                self.as_yaml_string_with_named_anchors(usize::MAX, &named)
+            }
         }
     }
-    impl MaybeYamlable for &Events {
-        fn maybe_as_yaml(&self) -> Option<Yaml> {
-            let mut configured = Hash::new();
-            let mut simple_names: Vec<&str> = Vec::new();
 
-            if let Some(event) = &self.branch_protection_rule {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("branch_protection_rule", yaml);
-                } else {
-                    simple_names.push("branch_protection_rule");
-                }
-            }
-            if let Some(event) = &self.check_run {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("check_run", yaml);
-                } else {
-                    simple_names.push("check_run");
-                }
-            }
-            if let Some(event) = &self.check_suite {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("check_suite", yaml);
-                } else {
-                    simple_names.push("check_suite");
-                }
-            }
-            if let Some(event) = &self.discussion {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("discussion", yaml);
-                } else {
-                    simple_names.push("discussion");
-                }
+    /// Merge `shared` and `own` env maps for `WorkflowSet.dump_all`, with `own`'s entries taking
+    /// priority over `shared`'s on key conflicts. Returns `None` only if both are `None`.
+    fn merge_env(
+        shared: Option<&PyMap<String, StringLike>>,
+        own: Option<&PyMap<String, StringLike>>,
+    ) -> Option<PyMap<String, StringLike>> {
+        if shared.is_none() && own.is_none() {
+            return None;
+        }
+        let mut merged: hashlink::LinkedHashMap<String, StringLike> =
+            hashlink::LinkedHashMap::new();
+        if let Some(shared) = shared {
+            for (k, v) in shared.iter() {
+                merged.insert(k.clone(), v.clone());
             }
-            if let Some(event) = &self.discussion_comment {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("discussion_comment", yaml);
-                } else {
-                    simple_names.push("discussion_comment");
-                }
+        }
+        if let Some(own) = own {
+            for (k, v) in own.iter() {
+                merged.insert(k.clone(), v.clone());
             }
+        }
+        Some(merged.into_iter().collect())
+    }
 
-            if let Some(event) = &self.image_version {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("image_version", yaml);
-                } else {
-                    simple_names.push("image_version");
-                }
-            }
-            if let Some(event) = &self.issue_comment {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("issue_comment", yaml);
-                } else {
-                    simple_names.push("issue_comment");
-                }
+    /// Like `Workflow::as_yaml`, but a member of a `WorkflowSet`: `default_permissions`/
+    /// `default_env` fall in for a workflow that doesn't set its own `permissions`, and are
+    /// merged into (with the workflow's own entries winning conflicts) a workflow's own `env`.
+    fn workflow_yaml_with_defaults(
+        workflow: &Workflow,
+        default_permissions: &Option<Permissions>,
+        default_env: &Option<PyMap<String, StringLike>>,
+    ) -> Yaml {
+        let mut out = Hash::new();
+        out.insert_yaml_opt("name", &workflow.name);
+        out.insert_yaml_opt("run-name", &workflow.run_name);
+        out.insert_yaml_opt("on", (&workflow.on).maybe_as_yaml());
+        let permissions = workflow.permissions.clone().or_else(|| default_permissions.clone());
+        out.insert_yaml_opt("permissions", &permissions);
+        let env = merge_env(default_env.as_ref(), workflow.env.as_ref());
+        out.insert_yaml_opt("env", &env);
+        if let Some(defaults) = &workflow.defaults {
+            out.insert_yaml_opt("defaults", defaults.maybe_as_yaml());
+        }
+        out.insert_yaml_opt("concurrency", &workflow.concurrency);
+        out.insert_yaml("jobs", &workflow.jobs);
+        Yaml::Hash(out)
+    }
+
+    /// A single member's fully-rendered YAML document, bundled with the named anchors it folds
+    /// onto real YAML anchor/alias syntax, so it can go through `Yamlable::write_to_file_with_mode`
+    /// (and so get directory creation, `overwrite`, and the generated-file header banner for free)
+    /// without re-running `workflow_yaml_with_defaults`'s merge-with-defaults pass on every write.
+    struct RenderedYaml {
+        yaml: Yaml,
+        named_anchors: HashMap<String, String>,
+    }
+    impl Yamlable for &RenderedYaml {
+        fn as_yaml(&self) -> Yaml {
+            self.yaml.clone()
+        }
+        fn as_yaml_string(&self) -> PyResult<String> {
+            if self.named_anchors.is_empty() {
+                render_yaml_document(&self.yaml)
+            } else {
+                self.as_yaml_string_with_named_anchors(usize::MAX, &self.named_anchors)
             }
-            if let Some(event) = &self.issues {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("issues", yaml);
-                } else {
-                    simple_names.push("issues");
-                }
+        }
+    }
+
+    /// A named collection of `Workflow`s that share defaults, as a single typed source of truth
+    /// for an entire `.github/workflows/` directory instead of scripting per-file
+    /// `Workflow.dump()` calls.
+    #[pyclass]
+    struct WorkflowSet {
+        workflows: PyMap<String, Py<Workflow>>,
+        permissions: Option<Permissions>,
+        env: Option<PyMap<String, StringLike>>,
+    }
+    impl WorkflowSet {
+        /// Render every member to its merged-with-defaults `Yaml` document, alongside the YAML
+        /// anchors its own `StepsAnchor`s fold onto real anchor/alias syntax.
+        fn rendered(&self, py: Python<'_>) -> Vec<(String, RenderedYaml)> {
+            self.workflows
+                .iter()
+                .map(|(filename, workflow)| {
+                    let workflow = workflow.borrow(py);
+                    let yaml =
+                        workflow_yaml_with_defaults(&workflow, &self.permissions, &self.env);
+                    let named_anchors = named_step_anchors(&workflow.jobs);
+                    (
+                        filename.clone(),
+                        RenderedYaml {
+                            yaml,
+                            named_anchors,
+                        },
+                    )
+                })
+                .collect()
+        }
+    }
+    #[pymethods]
+    impl WorkflowSet {
+        /// A mapping of filename to `Workflow` for an entire `.github/workflows/` directory,
+        /// generated and validated in one pass via `dump_all`.
+        ///
+        /// Parameters
+        /// ----------
+        /// workflows
+        ///     Mapping of filename (e.g. ``ci.yml``) to the `Workflow` that should be written
+        ///     there.
+        /// permissions
+        ///     Default permissions applied to any member workflow that doesn't set its own.
+        /// env
+        ///     Global environment variables merged into every member workflow's own `env`, with a
+        ///     member's own entries taking priority on key conflicts.
+        ///
+        /// Returns
+        /// -------
+        /// WorkflowSet
+        ///
+        #[new]
+        #[pyo3(signature = (workflows, *, permissions = None, env = None))]
+        fn new(
+            workflows: PyMap<String, Py<Workflow>>,
+            permissions: Option<Permissions>,
+            env: Option<PyMap<String, StringLike>>,
+        ) -> PyResult<Self> {
+            if let Some(env) = &env {
+                validate_string_map(env, ALLOWED_WORKFLOW_ENV)?;
             }
-            if let Some(event) = &self.label {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("label", yaml);
-                } else {
-                    simple_names.push("label");
-                }
+            Ok(Self {
+                workflows,
+                permissions,
+                env,
+            })
+        }
+
+        /// Run schema validation against every member's merged-with-defaults YAML, raising a
+        /// single `ValidationError` listing every violation across every member (not just the
+        /// first) if any are found, with each `ValidationIssue.instance_path` prefixed by the
+        /// filename it came from.
+        fn validate_all(&self, py: Python<'_>) -> PyResult<()> {
+            let mut issues = Vec::new();
+            for (filename, rendered) in self.rendered(py) {
+                let json = yaml_to_json(&rendered.yaml)?;
+                issues.extend(
+                    collect_schema_issues(&WORKFLOW_SCHEMA, &json)
+                        .into_iter()
+                        .map(|(instance_path, schema_path, message)| {
+                            (format!("{filename}:{instance_path}"), schema_path, message)
+                        }),
+                );
             }
-            if let Some(event) = &self.merge_group {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("merge_group", yaml);
-                } else {
-                    simple_names.push("merge_group");
-                }
+            raise_validation_issues(issues, py)
+        }
+
+        /// Check whether every member is valid YAML according to the schemastore JSON schema for
+        /// GitHub Workflows, with this set's shared `permissions`/`env` applied.
+        fn is_valid_all(&self, py: Python<'_>) -> bool {
+            self.validate_all(py).is_ok()
+        }
+
+        /// Write every member to `dir/<filename>`, creating `dir` if it doesn't exist yet.
+        ///
+        /// Parameters
+        /// ----------
+        /// dir
+        ///     Directory that should contain one file per member (e.g. `.github/workflows`).
+        /// overwrite
+        ///     Whether to overwrite a member's file if it already exists.
+        /// validate
+        ///     Whether to run `validate_all` before writing anything.
+        /// mode
+        ///     Either ``"generate"`` (the default) to write every member's file, or ``"check"`` to
+        ///     instead verify that each file on disk already matches its generated output, raising
+        ///     a RuntimeError naming the first stale file otherwise. Useful as a CI step that fails
+        ///     when a committed workflow no longer matches its Python source of truth.
+        /// header
+        ///     An optional comment block prepended to every member's emitted YAML, e.g. an
+        ///     "automatically generated, DO NOT EDIT" banner, so committed files advertise that
+        ///     they must be regenerated rather than hand-edited.
+        ///
+        /// Returns
+        /// -------
+        /// None
+        ///
+        #[pyo3(signature = (dir, *, overwrite = true, validate = true, mode = None, header = None))]
+        fn dump_all(
+            &self,
+            py: Python<'_>,
+            dir: &Bound<PyAny>,
+            overwrite: bool,
+            validate: bool,
+            mode: Option<String>,
+            header: Option<String>,
+        ) -> PyResult<()> {
+            if validate {
+                self.validate_all(py)?;
             }
-            if let Some(event) = &self.milestone {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("milestone", yaml);
-                } else {
-                    simple_names.push("milestone");
-                }
+            let mode = mode
+                .map(|m| m.parse())
+                .transpose()?
+                .unwrap_or(WriteMode::Generate);
+            let dir = if let Ok(p) = dir.extract::<PathBuf>() {
+                p
+            } else if let Ok(s) = dir.extract::<String>() {
+                PathBuf::from(s)
+            } else {
+                return Err(PyValueError::new_err("Invalid path"));
+            };
+            for (filename, rendered) in self.rendered(py) {
+                (&rendered).write_to_file_with_mode(
+                    dir.join(filename),
+                    overwrite,
+                    mode,
+                    header.as_deref(),
+                )?;
             }
-            if let Some(event) = &self.pull_request {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("pull_request", yaml);
-                } else {
-                    simple_names.push("pull_request");
-                }
+            Ok(())
+        }
+    }
+
+    /// The icon/color shown for an action on the GitHub Marketplace.
+    #[pyclass]
+    #[derive(Clone)]
+    struct ActionBranding {
+        icon: Option<String>,
+        color: Option<String>,
+    }
+    #[pymethods]
+    impl ActionBranding {
+        #[new]
+        #[pyo3(signature = (*, icon = None, color = None))]
+        fn new(icon: Option<String>, color: Option<String>) -> Self {
+            Self { icon, color }
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            action_branding_from_hash(
+                expect_hash(&parse_yaml_document(yaml)?, "action.branding")?.clone(),
+            )
+        }
+    }
+    impl Yamlable for &ActionBranding {
+        fn as_yaml(&self) -> Yaml {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("icon", &self.icon);
+            out.insert_yaml_opt("color", &self.color);
+            Yaml::Hash(out)
+        }
+    }
+    fn action_branding_from_hash(mut hash: Hash) -> PyResult<ActionBranding> {
+        let what = "action.branding";
+        let icon = hash_take(&mut hash, "icon")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.icon")))
+            .transpose()?;
+        let color = hash_take(&mut hash, "color")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.color")))
+            .transpose()?;
+        reject_unknown_keys(&hash, what)?;
+        Ok(ActionBranding { icon, color })
+    }
+
+    /// Action input defaults are rendered literally into `action.yml`: GitHub never evaluates
+    /// `${{ }}` expressions there, so no context is allowed.
+    const ALLOWED_ACTION_INPUT_DEFAULT: Allowed =
+        Allowed::new(Contexts::NONE, Funcs::NONE, "inputs.<input_id>.default");
+    /// A composite action's output `value` is evaluated the same way a step's fields are, against
+    /// the action's own inputs and its steps' outputs.
+    const ALLOWED_ACTION_OUTPUT_VALUE: Allowed =
+        Allowed::new(ctx!(GITHUB, INPUTS, STEPS), Funcs::NONE, "outputs.<output_id>.value");
+    /// A Docker action's `image`/`entrypoint`/`args`/`env` may reference the action's own inputs.
+    const ALLOWED_ACTION_RUNS_DOCKER: Allowed = Allowed::new(ctx!(GITHUB, INPUTS), Funcs::NONE, "runs");
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct ActionInput {
+        description: Option<String>,
+        required: Option<bool>,
+        default: Option<StringLike>,
+        deprecation_message: Option<String>,
+    }
+    #[pymethods]
+    impl ActionInput {
+        #[new]
+        #[pyo3(signature = (*, description = None, required = None, default = None, deprecation_message = None))]
+        fn new(
+            description: Option<String>,
+            required: Option<bool>,
+            default: Option<StringLike>,
+            deprecation_message: Option<String>,
+        ) -> PyResult<Self> {
+            if let Some(default) = &default {
+                validate_string_like(default, ALLOWED_ACTION_INPUT_DEFAULT)?;
             }
-            if let Some(event) = &self.pull_request_review {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("pull_request_review", yaml);
-                } else {
-                    simple_names.push("pull_request_review");
-                }
+            Ok(Self {
+                description,
+                required,
+                default,
+                deprecation_message,
+            })
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            action_input_from_hash(
+                expect_hash(&parse_yaml_document(yaml)?, "action.inputs.<input_id>")?.clone(),
+            )
+        }
+    }
+    impl Yamlable for &ActionInput {
+        fn as_yaml(&self) -> Yaml {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("description", &self.description);
+            out.insert_yaml_opt("required", self.required);
+            out.insert_yaml_opt("default", &self.default);
+            out.insert_yaml_opt("deprecationMessage", &self.deprecation_message);
+            Yaml::Hash(out)
+        }
+    }
+    fn action_input_from_hash(mut hash: Hash) -> PyResult<ActionInput> {
+        let what = "action.inputs.<input_id>";
+        let description = hash_take(&mut hash, "description")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.description")))
+            .transpose()?;
+        let required = hash_take(&mut hash, "required")
+            .map(|y| yaml_as_bool(&y, &format!("{what}.required")))
+            .transpose()?;
+        let default = hash_take(&mut hash, "default")
+            .map(|y| parse_string_like(&y, &format!("{what}.default")))
+            .transpose()?;
+        if let Some(default) = &default {
+            validate_string_like(default, ALLOWED_ACTION_INPUT_DEFAULT)?;
+        }
+        let deprecation_message = hash_take(&mut hash, "deprecationMessage")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.deprecationMessage")))
+            .transpose()?;
+        reject_unknown_keys(&hash, what)?;
+        Ok(ActionInput {
+            description,
+            required,
+            default,
+            deprecation_message,
+        })
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct ActionOutput {
+        description: Option<String>,
+        value: Option<StringLike>,
+    }
+    #[pymethods]
+    impl ActionOutput {
+        #[new]
+        #[pyo3(signature = (*, description = None, value = None))]
+        fn new(description: Option<String>, value: Option<StringLike>) -> PyResult<Self> {
+            if let Some(value) = &value {
+                validate_string_like(value, ALLOWED_ACTION_OUTPUT_VALUE)?;
             }
-            if let Some(event) = &self.pull_request_review_comment {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("pull_request_review_comment", yaml);
-                } else {
-                    simple_names.push("pull_request_review_comment");
-                }
+            Ok(Self { description, value })
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            action_output_from_hash(
+                expect_hash(&parse_yaml_document(yaml)?, "action.outputs.<output_id>")?.clone(),
+            )
+        }
+    }
+    impl Yamlable for &ActionOutput {
+        fn as_yaml(&self) -> Yaml {
+            let mut out = Hash::new();
+            out.insert_yaml_opt("description", &self.description);
+            out.insert_yaml_opt("value", &self.value);
+            Yaml::Hash(out)
+        }
+    }
+    fn action_output_from_hash(mut hash: Hash) -> PyResult<ActionOutput> {
+        let what = "action.outputs.<output_id>";
+        let description = hash_take(&mut hash, "description")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.description")))
+            .transpose()?;
+        let value = hash_take(&mut hash, "value")
+            .map(|y| parse_string_like(&y, &format!("{what}.value")))
+            .transpose()?;
+        if let Some(value) = &value {
+            validate_string_like(value, ALLOWED_ACTION_OUTPUT_VALUE)?;
+        }
+        reject_unknown_keys(&hash, what)?;
+        Ok(ActionOutput { description, value })
+    }
+
+    /// The three mutually exclusive ways an action can be executed, mirroring the `Runs` untagged
+    /// enum from GitHub's own `action.yml` schema.
+    #[derive(Clone)]
+    enum ActionRunsKind {
+        JavaScript {
+            using: String,
+            main: String,
+            pre: Option<String>,
+            post: Option<String>,
+        },
+        Docker {
+            image: StringLike,
+            entrypoint: Option<StringLike>,
+            args: Option<Vec<StringLike>>,
+            env: Option<PyMap<String, StringLike>>,
+        },
+        Composite {
+            steps: Vec<Step>,
+        },
+    }
+
+    #[pyclass]
+    #[derive(Clone)]
+    struct ActionRuns(ActionRunsKind);
+    #[pymethods]
+    impl ActionRuns {
+        /// Build the `runs:` block for a JavaScript action.
+        #[staticmethod]
+        #[pyo3(signature = (main, *, using = String::from("node20"), pre = None, post = None))]
+        fn javascript(main: String, using: String, pre: Option<String>, post: Option<String>) -> Self {
+            Self(ActionRunsKind::JavaScript {
+                using,
+                main,
+                pre,
+                post,
+            })
+        }
+
+        /// Build the `runs:` block for a Docker container action.
+        #[staticmethod]
+        #[pyo3(signature = (image, *, entrypoint = None, args = None, env = None))]
+        fn docker(
+            image: StringLike,
+            entrypoint: Option<StringLike>,
+            args: Option<Vec<StringLike>>,
+            env: Option<PyMap<String, StringLike>>,
+        ) -> PyResult<Self> {
+            validate_string_like(&image, ALLOWED_ACTION_RUNS_DOCKER)?;
+            if let Some(entrypoint) = &entrypoint {
+                validate_string_like(entrypoint, ALLOWED_ACTION_RUNS_DOCKER)?;
             }
-            if let Some(event) = &self.pull_request_target {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("pull_request_target", yaml);
-                } else {
-                    simple_names.push("pull_request_target");
-                }
+            if let Some(args) = &args {
+                validate_string_vec(args, ALLOWED_ACTION_RUNS_DOCKER)?;
             }
-            if let Some(event) = &self.push {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("push", yaml);
-                } else {
-                    simple_names.push("push");
-                }
+            if let Some(env) = &env {
+                validate_string_map(env, ALLOWED_ACTION_RUNS_DOCKER)?;
             }
-            if let Some(event) = &self.registry_package {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("registry_package", yaml);
-                } else {
-                    simple_names.push("registry_package");
+            Ok(Self(ActionRunsKind::Docker {
+                image,
+                entrypoint,
+                args,
+                env,
+            }))
+        }
+
+        /// Build the `runs:` block for a composite action out of a list of `Step`s.
+        #[staticmethod]
+        fn composite(steps: Vec<Step>) -> Self {
+            Self(ActionRunsKind::Composite { steps })
+        }
+
+        fn __str__(&self) -> PyResult<String> {
+            self.as_yaml_string()
+        }
+    }
+    impl Yamlable for &ActionRuns {
+        fn as_yaml(&self) -> Yaml {
+            let mut out = Hash::new();
+            match &self.0 {
+                ActionRunsKind::JavaScript {
+                    using,
+                    main,
+                    pre,
+                    post,
+                } => {
+                    out.insert_yaml("using", using.as_str());
+                    out.insert_yaml("main", main.as_str());
+                    out.insert_yaml_opt("pre", pre);
+                    out.insert_yaml_opt("post", post);
                 }
-            }
-            if let Some(event) = &self.release {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("release", yaml);
-                } else {
-                    simple_names.push("release");
+                ActionRunsKind::Docker {
+                    image,
+                    entrypoint,
+                    args,
+                    env,
+                } => {
+                    out.insert_yaml("using", "docker");
+                    out.insert_yaml("image", image);
+                    out.insert_yaml_opt("entrypoint", entrypoint);
+                    out.insert_yaml_opt("args", args);
+                    out.insert_yaml_opt("env", env);
                 }
-            }
-            if let Some(event) = &self.schedule {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("schedule", yaml);
-                } else {
-                    simple_names.push("schedule");
+                ActionRunsKind::Composite { steps } => {
+                    out.insert_yaml("using", "composite");
+                    out.insert_yaml("steps", steps);
                 }
             }
-            if let Some(event) = &self.watch {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("watch", yaml);
-                } else {
-                    simple_names.push("watch");
+            Yaml::Hash(out)
+        }
+    }
+    fn action_runs_from_hash(mut hash: Hash) -> PyResult<ActionRuns> {
+        let what = "action.runs";
+        let using = hash_take(&mut hash, "using")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.using")))
+            .transpose()?
+            .ok_or_else(|| {
+                PyValueError::new_err(format!("Expected '{what}' to have a 'using' key"))
+            })?;
+        let kind = match using.as_str() {
+            "docker" => {
+                let image = hash_take(&mut hash, "image")
+                    .map(|y| parse_string_like(&y, &format!("{what}.image")))
+                    .transpose()?
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!("Expected '{what}' to have an 'image' key"))
+                    })?;
+                let entrypoint = hash_take(&mut hash, "entrypoint")
+                    .map(|y| parse_string_like(&y, &format!("{what}.entrypoint")))
+                    .transpose()?;
+                let args = hash_take(&mut hash, "args")
+                    .map(|y| {
+                        y.as_vec()
+                            .ok_or_else(|| {
+                                PyValueError::new_err(format!("Expected '{what}.args' to be a list"))
+                            })?
+                            .iter()
+                            .map(|a| parse_string_like(a, &format!("{what}.args")))
+                            .collect::<PyResult<Vec<_>>>()
+                    })
+                    .transpose()?;
+                let env = hash_take(&mut hash, "env")
+                    .map(|y| parse_string_map(&y, &format!("{what}.env")))
+                    .transpose()?;
+                validate_string_like(&image, ALLOWED_ACTION_RUNS_DOCKER)?;
+                if let Some(entrypoint) = &entrypoint {
+                    validate_string_like(entrypoint, ALLOWED_ACTION_RUNS_DOCKER)?;
                 }
-            }
-            if let Some(event) = &self.workflow_call {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("workflow_call", yaml);
-                } else {
-                    simple_names.push("workflow_call");
+                if let Some(args) = &args {
+                    validate_string_vec(args, ALLOWED_ACTION_RUNS_DOCKER)?;
                 }
-            }
-            if let Some(event) = &self.workflow_dispatch {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("workflow_dispatch", yaml);
-                } else {
-                    simple_names.push("workflow_dispatch");
+                if let Some(env) = &env {
+                    validate_string_map(env, ALLOWED_ACTION_RUNS_DOCKER)?;
                 }
-            }
-            if let Some(event) = &self.workflow_run {
-                if let Some(yaml) = event.maybe_as_yaml() {
-                    configured.insert_yaml("workflow_run", yaml);
-                } else {
-                    simple_names.push("workflow_run");
+                ActionRunsKind::Docker {
+                    image,
+                    entrypoint,
+                    args,
+                    env,
                 }
             }
-
-            if self.create {
-                simple_names.push("create");
-            }
-            if self.delete {
-                simple_names.push("delete");
-            }
-            if self.deployment {
-                simple_names.push("deployment");
-            }
-            if self.deployment_status {
-                simple_names.push("deployment_status");
-            }
-            if self.fork {
-                simple_names.push("fork");
-            }
-            if self.gollum {
-                simple_names.push("gollum");
-            }
-            if self.page_build {
-                simple_names.push("page_build");
-            }
-            if self.public {
-                simple_names.push("public");
-            }
-            if self.status {
-                simple_names.push("status");
-            }
-
-            if configured.is_empty() {
-                match simple_names.len() {
-                    0 => None,
-                    1 => Some(simple_names[0].as_yaml()),
-                    _ => {
-                        let mut arr = Array::new();
-                        for name in simple_names {
-                            arr.push_yaml(name);
-                        }
-                        Some(Yaml::Array(arr))
-                    }
-                }
-            } else {
-                for name in simple_names {
-                    configured.insert_yaml(name, Yaml::Null);
+            "composite" => {
+                let steps = hash_take(&mut hash, "steps").ok_or_else(|| {
+                    PyValueError::new_err(format!("Expected '{what}' to have a 'steps' key"))
+                })?;
+                let steps = steps
+                    .as_vec()
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!("Expected '{what}.steps' to be a list"))
+                    })?
+                    .iter()
+                    .map(|s| step_from_hash(expect_hash(s, &format!("{what}.steps"))?.clone()))
+                    .collect::<PyResult<Vec<_>>>()?;
+                ActionRunsKind::Composite { steps }
+            }
+            _ => {
+                let main = hash_take(&mut hash, "main")
+                    .map(|y| yaml_scalar_to_string(&y, &format!("{what}.main")))
+                    .transpose()?
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!("Expected '{what}' to have a 'main' key"))
+                    })?;
+                let pre = hash_take(&mut hash, "pre")
+                    .map(|y| yaml_scalar_to_string(&y, &format!("{what}.pre")))
+                    .transpose()?;
+                let post = hash_take(&mut hash, "post")
+                    .map(|y| yaml_scalar_to_string(&y, &format!("{what}.post")))
+                    .transpose()?;
+                ActionRunsKind::JavaScript {
+                    using,
+                    main,
+                    pre,
+                    post,
                 }
-                Some(Yaml::Hash(configured))
             }
-        }
+        };
+        reject_unknown_keys(&hash, what)?;
+        Ok(ActionRuns(kind))
     }
 
+    /// The metadata file (`action.yml`/`action.yaml`) describing a reusable action, modeling the
+    /// same composite/JavaScript/Docker surface as GitHub's own schema.
     #[pyclass]
-    struct Workflow {
-        name: Option<String>,
-        run_name: Option<StringLike>,
-        on: Events,
-        permissions: Option<Permissions>,
-        env: Option<PyMap<String, StringLike>>,
-        defaults: Option<Defaults>,
-        concurrency: Option<Concurrency>,
-        jobs: PyMap<String, Job>,
+    struct Action {
+        name: String,
+        description: String,
+        author: Option<String>,
+        branding: Option<ActionBranding>,
+        inputs: Option<PyMap<String, ActionInput>>,
+        outputs: Option<PyMap<String, ActionOutput>>,
+        runs: ActionRuns,
     }
     #[pymethods]
-    impl Workflow {
-        /// A configurable automated process made up of one or more jobs.
-        ///
-        /// Workflows are the primary entrypoint for ``yamloom``. Typical actions include constructing
-        /// workflows and then writing them to a file with ``Workflow.dump('path/to/file.yml')``.
-        ///
+    impl Action {
         /// Parameters
         /// ----------
-        /// jobs
-        ///     Jobs to run (in parallel by default).
-        /// on
-        ///     Events which may trigger the workflow.
         /// name
-        ///     The name of the workflow.
-        /// run_name
-        ///     The name given to a particular run of the workflow.
-        /// permissions
-        ///     The default permissions granted to the ``GITHUB_TOKEN``.
-        /// env
-        ///     Global environment variables available at any step of any job in the workflow.
-        /// defaults
-        ///     Default settings which are applied to all jobs.
-        /// concurrency
-        ///     Settings to ensure only a single workflow of the given concurrency group runs at a time.
+        ///     The name of the action, shown on the GitHub Marketplace and in run logs.
+        /// description
+        ///     A short description of the action.
+        /// runs
+        ///     How the action is executed, built with `ActionRuns.javascript`,
+        ///     `ActionRuns.docker`, or `ActionRuns.composite`.
+        /// author
+        ///     The name of the action's author.
+        /// branding
+        ///     The icon and color displayed for the action on the GitHub Marketplace.
+        /// inputs
+        ///     Input parameters the action accepts, keyed by input id.
+        /// outputs
+        ///     Output parameters the action sets, keyed by output id.
         ///
         /// Returns
         /// -------
-        /// Workflow
+        /// Action
         ///
         #[new]
-        #[pyo3(signature = (*, jobs, on, name = None, run_name = None, permissions = None, env = None, defaults = None, concurrency = None))]
+        #[pyo3(signature = (name, description, runs, *, author = None, branding = None, inputs = None, outputs = None))]
         fn new(
-            jobs: PyMap<String, Job>,
-            on: Events,
-            name: Option<String>,
-            run_name: Option<StringLike>,
-            permissions: Option<Permissions>,
-            env: Option<PyMap<String, StringLike>>,
-            defaults: Option<Defaults>,
-            concurrency: Option<Concurrency>,
-        ) -> PyResult<Self> {
-            if let Some(run_name) = &run_name {
-                validate_string_like(run_name, ALLOWED_WORKFLOW_RUN_NAME)?;
-            }
-            if let Some(env) = &env {
-                validate_string_map(env, ALLOWED_WORKFLOW_ENV)?;
-            }
-            if let Some(concurrency) = &concurrency {
-                validate_concurrency(concurrency, ALLOWED_WORKFLOW_CONCURRENCY)?;
-            }
-            Ok(Self {
+            name: String,
+            description: String,
+            runs: ActionRuns,
+            author: Option<String>,
+            branding: Option<ActionBranding>,
+            inputs: Option<PyMap<String, ActionInput>>,
+            outputs: Option<PyMap<String, ActionOutput>>,
+        ) -> Self {
+            Self {
                 name,
-                run_name,
-                on,
-                permissions,
-                env,
-                defaults,
-                concurrency,
-                jobs,
-            })
+                description,
+                author,
+                branding,
+                inputs,
+                outputs,
+                runs,
+            }
         }
 
-        /// Run validation against the schemastore JSON schema for GitHub Workflows and raise a
-        /// RuntimeError if validation fails.
-        fn validate(&self) -> PyResult<()> {
-            let workflow_yaml = self.as_yaml();
-            let workflow_json = yaml_to_json(&workflow_yaml)?;
-            WORKFLOW_SCHEMA
-                .validate(&workflow_json)
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+        /// Run validation against the schemastore JSON schema for GitHub Actions, raising a
+        /// `ValidationError` listing every violation (not just the first) if any are found.
+        fn validate(&self, py: Python<'_>) -> PyResult<()> {
+            let action_yaml = self.as_yaml();
+            let action_json = yaml_to_json(&action_yaml)?;
+            validate_against_schema(&ACTION_SCHEMA, &action_json, py)
         }
 
-        /// Check if the workflow is valid YAML according to the schemastore JSON schema for GitHub
-        /// Workflows.
-        fn is_valid(&self) -> bool {
-            self.validate().is_ok()
+        /// Check if the action is valid YAML according to the schemastore JSON schema for GitHub
+        /// Actions.
+        fn is_valid(&self, py: Python<'_>) -> bool {
+            self.validate(py).is_ok()
         }
 
-        /// Write the YAML representation of the workflow to a file.
+        /// Write the YAML representation of the action to a file.
         ///
         /// Parameters
         /// ----------
@@ -6360,17 +13926,36 @@ mod yamloom {
         ///     If True, the file is overwritten if it already exists, otherwise nothing will happen.
         /// validate
         ///     If True, perform validation against the schemastore JSON schema for GitHub
-        ///     Workflows.
+        ///     Actions.
+        /// mode
+        ///     Either ``"generate"`` (the default) to write the file, or ``"check"`` to instead
+        ///     verify that the file on disk already matches the generated output, raising a
+        ///     RuntimeError naming the stale file otherwise.
+        /// header
+        ///     An optional comment block prepended to the emitted YAML, e.g. a
+        ///     "automatically generated, DO NOT EDIT" banner.
         ///
-        #[pyo3(signature = (path, *, overwrite = true, validate = true))]
-        fn dump(&self, path: &Bound<PyAny>, overwrite: bool, validate: bool) -> PyResult<()> {
+        #[pyo3(signature = (path, *, overwrite = true, validate = true, mode = None, header = None))]
+        fn dump(
+            &self,
+            py: Python<'_>,
+            path: &Bound<PyAny>,
+            overwrite: bool,
+            validate: bool,
+            mode: Option<String>,
+            header: Option<String>,
+        ) -> PyResult<()> {
             if validate {
-                self.validate()?;
+                self.validate(py)?;
             }
+            let mode = mode
+                .map(|m| m.parse())
+                .transpose()?
+                .unwrap_or(WriteMode::Generate);
             if let Ok(p) = path.extract::<PathBuf>() {
-                self.write_to_file(p, overwrite)
+                self.write_to_file_with_mode(p, overwrite, mode, header.as_deref())
             } else if let Ok(s) = path.extract::<String>() {
-                self.write_to_file(s, overwrite)
+                self.write_to_file_with_mode(s, overwrite, mode, header.as_deref())
             } else {
                 Err(PyValueError::new_err("Invalid path"))
             }
@@ -6379,23 +13964,86 @@ mod yamloom {
         fn __str__(&self) -> PyResult<String> {
             self.as_yaml_string()
         }
+
+        /// Parse an existing `action.yml`/`action.yaml` file back into an `Action`.
+        #[staticmethod]
+        fn from_yaml(yaml: &str) -> PyResult<Self> {
+            action_from_hash(expect_hash(&parse_yaml_document(yaml)?, "action")?.clone())
+        }
     }
-    impl Yamlable for &Workflow {
+    impl Yamlable for &Action {
         fn as_yaml(&self) -> Yaml {
             let mut out = Hash::new();
-            out.insert_yaml_opt("name", &self.name);
-            out.insert_yaml_opt("run-name", &self.run_name);
-            out.insert_yaml_opt("on", (&self.on).maybe_as_yaml());
-            out.insert_yaml_opt("permissions", &self.permissions);
-            out.insert_yaml_opt("env", &self.env);
-            if let Some(defaults) = &self.defaults {
-                out.insert_yaml_opt("defaults", defaults.maybe_as_yaml());
-            }
-            out.insert_yaml_opt("concurrency", &self.concurrency);
-            out.insert_yaml("jobs", &self.jobs);
+            out.insert_yaml("name", self.name.as_str());
+            out.insert_yaml("description", self.description.as_str());
+            out.insert_yaml_opt("author", &self.author);
+            out.insert_yaml_opt("branding", &self.branding);
+            out.insert_yaml_opt("inputs", &self.inputs);
+            out.insert_yaml_opt("outputs", &self.outputs);
+            out.insert_yaml("runs", &self.runs);
             Yaml::Hash(out)
         }
     }
+    fn action_from_hash(mut hash: Hash) -> PyResult<Action> {
+        let what = "action";
+        let name = hash_take(&mut hash, "name")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.name")))
+            .transpose()?
+            .ok_or_else(|| PyValueError::new_err(format!("Expected '{what}' to have a 'name' key")))?;
+        let description = hash_take(&mut hash, "description")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.description")))
+            .transpose()?
+            .ok_or_else(|| {
+                PyValueError::new_err(format!("Expected '{what}' to have a 'description' key"))
+            })?;
+        let author = hash_take(&mut hash, "author")
+            .map(|y| yaml_scalar_to_string(&y, &format!("{what}.author")))
+            .transpose()?;
+        let branding = hash_take(&mut hash, "branding")
+            .map(|y| action_branding_from_hash(expect_hash(&y, &format!("{what}.branding"))?.clone()))
+            .transpose()?;
+        let inputs = hash_take(&mut hash, "inputs")
+            .map(|y| {
+                expect_hash(&y, &format!("{what}.inputs"))?
+                    .iter()
+                    .map(|(k, v)| {
+                        Ok((
+                            yaml_scalar_to_string(k, &format!("{what}.inputs"))?,
+                            action_input_from_hash(expect_hash(v, &format!("{what}.inputs"))?.clone())?,
+                        ))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+            })
+            .transpose()?
+            .map(|v| v.into_iter().collect());
+        let outputs = hash_take(&mut hash, "outputs")
+            .map(|y| {
+                expect_hash(&y, &format!("{what}.outputs"))?
+                    .iter()
+                    .map(|(k, v)| {
+                        Ok((
+                            yaml_scalar_to_string(k, &format!("{what}.outputs"))?,
+                            action_output_from_hash(expect_hash(v, &format!("{what}.outputs"))?.clone())?,
+                        ))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+            })
+            .transpose()?
+            .map(|v| v.into_iter().collect());
+        let runs = hash_take(&mut hash, "runs")
+            .ok_or_else(|| PyValueError::new_err(format!("Expected '{what}' to have a 'runs' key")))
+            .and_then(|y| action_runs_from_hash(expect_hash(&y, &format!("{what}.runs"))?.clone()))?;
+        reject_unknown_keys(&hash, what)?;
+        Ok(Action {
+            name,
+            description,
+            author,
+            branding,
+            inputs,
+            outputs,
+            runs,
+        })
+    }
 }
 
 fn yaml_to_json(yaml: &Yaml) -> PyResult<Value> {
@@ -6432,6 +14080,22 @@ fn yaml_to_json(yaml: &Yaml) -> PyResult<Value> {
     })
 }
 
+/// Run every validator against `json` (rather than stopping at the first failure) and return one
+/// `(instance_path, schema_path, message)` triple per violation, in the order the validator
+/// reports them.
+fn collect_schema_issues(schema: &Validator, json: &Value) -> Vec<(String, String, String)> {
+    schema
+        .iter_errors(json)
+        .map(|e| {
+            (
+                e.instance_path.to_string(),
+                e.schema_path.to_string(),
+                e.to_string(),
+            )
+        })
+        .collect()
+}
+
 static WORKFLOW_SCHEMA: LazyLock<Validator> = LazyLock::new(|| {
     let schema: Value = serde_json::from_str(include_str!("../schemas/github-workflow.json"))
         .expect("invalid JSON schema");
@@ -6446,3 +14110,18 @@ static WORKFLOW_SCHEMA: LazyLock<Validator> = LazyLock::new(|| {
         .build(&schema)
         .expect("schema compilation failed")
 });
+
+static ACTION_SCHEMA: LazyLock<Validator> = LazyLock::new(|| {
+    let schema: Value = serde_json::from_str(include_str!("../schemas/github-action.json"))
+        .expect("invalid JSON schema");
+    jsonschema::options()
+        .with_base_uri(
+            schema
+                .get("$id")
+                .and_then(|v| v.as_str())
+                .unwrap_or("urn:github-action-schema")
+                .to_string(),
+        )
+        .build(&schema)
+        .expect("schema compilation failed")
+});